@@ -4,20 +4,25 @@ use crate::parse::TryParse;
 
 mod prelude {
     pub use crate::network::*;
-    pub use crate::parse::{Apply, Input, Options, parse_options, TryParse};
+    pub use crate::parse::{Apply, ApplyToStore, Input, Options, parse_options, TryParse};
     pub use crate::storage::{clamp, clamp_range, IfKindResult, Kind, Store, Value};
 }
 
 mod common;
 mod expiration;
+mod hashes;
 mod lists;
+mod pubsub;
 mod server;
 mod sets;
+mod sorted_sets;
+mod streams;
 mod strings;
+mod transactions;
 
 pub struct CommandTree {
     parser: Option<Box<dyn TryParse>>,
-    children: HashMap<char, CommandTree>,
+    children: HashMap<u8, CommandTree>,
 }
 
 impl CommandTree {
@@ -26,8 +31,48 @@ impl CommandTree {
             //
             // server
             //
+            .insert(
+                "BGREWRITEAOF",
+                Box::new(server::rewrite_aof::RewriteAofParser::new()),
+            )
+            .insert(
+                "BGSAVE",
+                Box::new(server::background_save::BackgroundSaveParser::new()),
+            )
             .insert("COMMAND", Box::new(server::command::CommandParser::new()))
+            .insert("CONFIG", Box::new(server::config::ConfigParser::new()))
+            .insert("DBSIZE", Box::new(server::db_size::DbSizeParser::new()))
             .insert("ECHO", Box::new(server::echo::EchoParser::new()))
+            .insert(
+                "FLUSHALL",
+                Box::new(server::flush_all::FlushAllParser::new()),
+            )
+            .insert("FLUSHDB", Box::new(server::flush_db::FlushDbParser::new()))
+            .insert("HELLO", Box::new(server::hello::HelloParser::new()))
+            .insert("INFO", Box::new(server::info::InfoParser::new()))
+            .insert(
+                "LASTSAVE",
+                Box::new(server::last_save::LastSaveParser::new()),
+            )
+            .insert("PING", Box::new(server::ping::PingParser::new()))
+            .insert("PSYNC", Box::new(server::psync::PsyncParser::new()))
+            .insert(
+                "REPLCONF",
+                Box::new(server::replica_conf::ReplicaConfParser::new()),
+            )
+            .insert(
+                "REPLICAOF",
+                Box::new(server::replica_of::ReplicaOfParser::new()),
+            )
+            .insert("ROLE", Box::new(server::role::RoleParser::new()))
+            .insert("SAVE", Box::new(server::save::SaveParser::new()))
+            .insert("SELECT", Box::new(server::select::SelectParser::new()))
+            .insert(
+                "SLAVEOF",
+                Box::new(server::replica_of::ReplicaOfParser::new()),
+            )
+            .insert("SWAPDB", Box::new(server::swap_db::SwapDbParser::new()))
+            .insert("WAIT", Box::new(server::wait::WaitParser::new()))
             //
             // common
             //
@@ -35,6 +80,7 @@ impl CommandTree {
             .insert("DEL", Box::new(common::del::DelParser::new()))
             .insert("EXISTS", Box::new(common::exists::ExistsParser::new()))
             .insert("KEYS", Box::new(common::keys::KeysParser::new()))
+            .insert("MOVE", Box::new(common::r#move::MoveParser::new()))
             .insert("RENAME", Box::new(common::rename::RenameParser::new()))
             .insert("TYPE", Box::new(common::r#type::TypeParser::new()))
             //
@@ -61,7 +107,7 @@ impl CommandTree {
                 Box::new(expiration::expire_ms::ExpireMillisParser::new()),
             )
             .insert(
-                "ExpireAtMillis",
+                "PEXPIREAT",
                 Box::new(expiration::expire_at_ms::ExpireAtMillisParser::new()),
             )
             .insert(
@@ -74,8 +120,89 @@ impl CommandTree {
             )
             .insert("TTL", Box::new(expiration::ttl_s::TimeToLiveParser::new()))
             //
+            // hashes
+            //
+            .insert("HDEL", Box::new(hashes::del::DelParser::new()))
+            .insert("HEXISTS", Box::new(hashes::exists::ExistsParser::new()))
+            .insert(
+                "HEXPIRE",
+                Box::new(hashes::expire_s::ExpireSecsParser::new()),
+            )
+            .insert(
+                "HEXPIREAT",
+                Box::new(hashes::expire_at_s::ExpireAtSecsParser::new()),
+            )
+            .insert(
+                "HEXPIRETIME",
+                Box::new(hashes::expire_time_s::ExpireTimeSecsParser::new()),
+            )
+            .insert("HGET", Box::new(hashes::get::GetParser::new()))
+            .insert("HGETALL", Box::new(hashes::get_all::GetAllParser::new()))
+            .insert("HGETEX", Box::new(hashes::get_ex::GetExParser::new()))
+            .insert(
+                "HINCRBY",
+                Box::new(hashes::increment_by::IncrementByParser::new()),
+            )
+            .insert(
+                "HINCRBYFLOAT",
+                Box::new(hashes::increment_by_float::IncrementByFloatParser::new()),
+            )
+            .insert("HKEYS", Box::new(hashes::keys::KeysParser::new()))
+            .insert("HLEN", Box::new(hashes::len::LenParser::new()))
+            .insert(
+                "HMGET",
+                Box::new(hashes::get_multiple::GetMultipleParser::new()),
+            )
+            .insert("HPERSIST", Box::new(hashes::persist::PersistParser::new()))
+            .insert(
+                "HPEXPIRE",
+                Box::new(hashes::expire_ms::ExpireMillisParser::new()),
+            )
+            .insert(
+                "HPEXPIREAT",
+                Box::new(hashes::expire_at_ms::ExpireAtMillisParser::new()),
+            )
+            .insert(
+                "HPEXPIRETIME",
+                Box::new(hashes::expire_time_ms::ExpireTimeMillisParser::new()),
+            )
+            .insert("HPTTL", Box::new(hashes::ttl_ms::PTimeToLiveParser::new()))
+            .insert(
+                "HRANDFIELD",
+                Box::new(hashes::random_fields::RandomFieldsParser::new()),
+            )
+            .insert("HSET", Box::new(hashes::set::SetParser::new()))
+            .insert("HSETEX", Box::new(hashes::set_ex::SetExParser::new()))
+            .insert(
+                "HSETNX",
+                Box::new(hashes::set_if_not_set::SetIfNotSetParser::new()),
+            )
+            .insert("HSTRLEN", Box::new(hashes::str_len::StrLenParser::new()))
+            .insert("HTTL", Box::new(hashes::ttl_s::TimeToLiveParser::new()))
+            .insert("HVALS", Box::new(hashes::values::ValuesParser::new()))
+            //
             // lists
             //
+            .insert(
+                "BLMOVE",
+                Box::new(lists::blocking_move::BlockingMoveParser::new()),
+            )
+            .insert(
+                "BLMPOP",
+                Box::new(lists::blocking_pop_multiple::BlockingPopMultipleParser::new()),
+            )
+            .insert(
+                "BLPOP",
+                Box::new(lists::blocking_pop::BlockingLeftPopParser::new()),
+            )
+            .insert(
+                "BRPOP",
+                Box::new(lists::blocking_pop::BlockingRightPopParser::new()),
+            )
+            .insert(
+                "BRPOPLPUSH",
+                Box::new(lists::blocking_move::BlockingRightPopLeftPushParser::new()),
+            )
             .insert("LINDEX", Box::new(lists::index::IndexParser::new()))
             .insert("LINSERT", Box::new(lists::insert::InsertParser::new()))
             .insert("LLEN", Box::new(lists::len::LenParser::new()))
@@ -102,6 +229,39 @@ impl CommandTree {
                 Box::new(lists::right_push_exists::RightPushExistsParser::new()),
             )
             //
+            // pubsub
+            //
+            .insert(
+                "PSUBSCRIBE",
+                Box::new(pubsub::pattern_subscribe::PatternSubscribeParser::new()),
+            )
+            .insert("PUBLISH", Box::new(pubsub::publish::PublishParser::new()))
+            .insert("PUBSUB", Box::new(pubsub::pub_sub::PubSubParser::new()))
+            .insert(
+                "PUNSUBSCRIBE",
+                Box::new(pubsub::pattern_unsubscribe::PatternUnsubscribeParser::new()),
+            )
+            .insert(
+                "SPUBLISH",
+                Box::new(pubsub::shard_publish::ShardPublishParser::new()),
+            )
+            .insert(
+                "SSUBSCRIBE",
+                Box::new(pubsub::shard_subscribe::ShardSubscribeParser::new()),
+            )
+            .insert(
+                "SUBSCRIBE",
+                Box::new(pubsub::subscribe::SubscribeParser::new()),
+            )
+            .insert(
+                "SUNSUBSCRIBE",
+                Box::new(pubsub::shard_unsubscribe::ShardUnsubscribeParser::new()),
+            )
+            .insert(
+                "UNSUBSCRIBE",
+                Box::new(pubsub::unsubscribe::UnsubscribeParser::new()),
+            )
+            //
             // sets
             //
             .insert("SADD", Box::new(sets::add::AddParser::new()))
@@ -142,16 +302,140 @@ impl CommandTree {
                 Box::new(sets::union_store::UnionStoreParser::new()),
             )
             //
+            // sorted sets
+            //
+            .insert(
+                "BZMPOP",
+                Box::new(sorted_sets::blocking_pop_multiple::BlockingPopMultipleParser::new()),
+            )
+            .insert(
+                "BZPOPMAX",
+                Box::new(sorted_sets::blocking_pop::BlockingPopMaxParser::new()),
+            )
+            .insert(
+                "BZPOPMIN",
+                Box::new(sorted_sets::blocking_pop::BlockingPopMinParser::new()),
+            )
+            .insert("ZADD", Box::new(sorted_sets::add::AddParser::new()))
+            .insert("ZCARD", Box::new(sorted_sets::card::CardParser::new()))
+            .insert("ZCOUNT", Box::new(sorted_sets::count::CountParser::new()))
+            .insert("ZDIFF", Box::new(sorted_sets::diff::DiffParser::new()))
+            .insert(
+                "ZDIFFSTORE",
+                Box::new(sorted_sets::diff_store::DiffStoreParser::new()),
+            )
+            .insert(
+                "ZINCRBY",
+                Box::new(sorted_sets::increment_by::IncrementByParser::new()),
+            )
+            .insert(
+                "ZINTER",
+                Box::new(sorted_sets::intersect::IntersectParser::new()),
+            )
+            .insert(
+                "ZINTERCARD",
+                Box::new(sorted_sets::intersect_card::IntersectCardParser::new()),
+            )
+            .insert(
+                "ZINTERSTORE",
+                Box::new(sorted_sets::intersect_store::IntersectStoreParser::new()),
+            )
+            .insert(
+                "ZMPOP",
+                Box::new(sorted_sets::pop_multiple::PopMultipleParser::new()),
+            )
+            .insert(
+                "ZMSCORE",
+                Box::new(sorted_sets::score_multiple::ScoreMultipleParser::new()),
+            )
+            .insert("ZPOPMAX", Box::new(sorted_sets::pop::PopMaxParser::new()))
+            .insert("ZPOPMIN", Box::new(sorted_sets::pop::PopMinParser::new()))
+            .insert(
+                "ZRANDMEMBER",
+                Box::new(sorted_sets::random_members::RandomMembersParser::new()),
+            )
+            .insert("ZRANGE", Box::new(sorted_sets::range::RangeParser::new()))
+            .insert(
+                "ZRANGESTORE",
+                Box::new(sorted_sets::range_store::RangeStoreParser::new()),
+            )
+            .insert("ZRANK", Box::new(sorted_sets::rank::RankParser::new()))
+            .insert("ZREM", Box::new(sorted_sets::remove::RemoveParser::new()))
+            .insert(
+                "ZREVRANK",
+                Box::new(sorted_sets::rank::RevRankParser::new()),
+            )
+            .insert("ZSCORE", Box::new(sorted_sets::score::ScoreParser::new()))
+            .insert("ZUNION", Box::new(sorted_sets::union::UnionParser::new()))
+            .insert(
+                "ZUNIONSTORE",
+                Box::new(sorted_sets::union_store::UnionStoreParser::new()),
+            )
+            //
+            // streams
+            //
+            .insert("XACK", Box::new(streams::ack::AckParser::new()))
+            .insert("XADD", Box::new(streams::add::AddParser::new()))
+            .insert(
+                "XAUTOCLAIM",
+                Box::new(streams::auto_claim::AutoClaimParser::new()),
+            )
+            .insert("XCLAIM", Box::new(streams::claim::ClaimParser::new()))
+            .insert("XDEL", Box::new(streams::del::DelParser::new()))
+            .insert("XGROUP", Box::new(streams::group::GroupParser::new()))
+            .insert("XINFO", Box::new(streams::info::InfoParser::new()))
+            .insert("XLEN", Box::new(streams::len::LenParser::new()))
+            .insert("XPENDING", Box::new(streams::pending::PendingParser::new()))
+            .insert("XRANGE", Box::new(streams::range::RangeParser::new()))
+            .insert("XREAD", Box::new(streams::read::ReadParser::new()))
+            .insert(
+                "XREADGROUP",
+                Box::new(streams::read_group::ReadGroupParser::new()),
+            )
+            .insert("XREVRANGE", Box::new(streams::range::RevRangeParser::new()))
+            .insert("XSETID", Box::new(streams::set_id::SetIdParser::new()))
+            .insert("XTRIM", Box::new(streams::trim::TrimParser::new()))
+            //
             // strings
             //
             .insert("APPEND", Box::new(strings::append::AppendParser::new()))
+            .insert(
+                "BITCOUNT",
+                Box::new(strings::bit_count::BitCountParser::new()),
+            )
+            .insert("BITOP", Box::new(strings::bit_op::BitOpParser::new()))
+            .insert(
+                "BITPOS",
+                Box::new(strings::bit_position::BitPositionParser::new()),
+            )
+            .insert(
+                "DECR",
+                Box::new(strings::increment_by::DecrementParser::new()),
+            )
+            .insert(
+                "DECRBY",
+                Box::new(strings::increment_by::DecrementByParser::new()),
+            )
             .insert("GET", Box::new(strings::get::GetParser::new()))
+            .insert("GETBIT", Box::new(strings::get_bit::GetBitParser::new()))
             .insert("GETDEL", Box::new(strings::get_del::GetDelParser::new()))
             .insert("GETEX", Box::new(strings::get_ex::GetExParser::new()))
             .insert(
                 "GETRANGE",
                 Box::new(strings::get_range::GetRangeParser::new()),
             )
+            .insert(
+                "INCR",
+                Box::new(strings::increment_by::IncrementParser::new()),
+            )
+            .insert(
+                "INCRBY",
+                Box::new(strings::increment_by::IncrementByParser::new()),
+            )
+            .insert(
+                "INCRBYFLOAT",
+                Box::new(strings::increment_by_float::IncrementByFloatParser::new()),
+            )
             .insert(
                 "MSET",
                 Box::new(strings::set_multiple::SetMultipleParser::new()),
@@ -161,7 +445,22 @@ impl CommandTree {
                 Box::new(strings::set_multiple_if_not_set::SetMultipleIfNotSetParser::new()),
             )
             .insert("SET", Box::new(strings::set::SetParser::new()))
+            .insert("SETBIT", Box::new(strings::set_bit::SetBitParser::new()))
             .insert("STRLEN", Box::new(strings::str_len::StrLenParser::new()))
+            //
+            // transactions
+            //
+            .insert(
+                "DISCARD",
+                Box::new(transactions::discard::DiscardParser::new()),
+            )
+            .insert("EXEC", Box::new(transactions::exec::ExecParser::new()))
+            .insert("MULTI", Box::new(transactions::multi::MultiParser::new()))
+            .insert(
+                "UNWATCH",
+                Box::new(transactions::unwatch::UnwatchParser::new()),
+            )
+            .insert("WATCH", Box::new(transactions::watch::WatchParser::new()))
     }
 
     fn new_node() -> Self {
@@ -174,7 +473,7 @@ impl CommandTree {
     fn insert(mut self, command: &str, parser: Box<dyn TryParse>) -> Self {
         let mut current = &mut self;
 
-        for c in command.chars() {
+        for c in command.bytes() {
             current = current
                 .children
                 .entry(c.to_ascii_uppercase())
@@ -185,10 +484,10 @@ impl CommandTree {
         self
     }
 
-    pub fn get(&self, command: &str) -> Option<&dyn TryParse> {
+    pub fn get(&self, command: &[u8]) -> Option<&dyn TryParse> {
         let mut current = self;
 
-        for c in command.chars() {
+        for c in command {
             if let Some(next) = current.children.get(&(c.to_ascii_uppercase())) {
                 current = next;
             } else {
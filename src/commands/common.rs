@@ -2,5 +2,6 @@ pub mod copy;
 pub mod del;
 pub mod exists;
 pub mod keys;
+pub mod r#move;
 pub mod rename;
 pub mod r#type;
@@ -1,30 +1,51 @@
 use crate::commands::prelude::*;
 
 struct Copy {
-    source: String,
-    destination: String,
+    source: Vec<u8>,
+    destination: Vec<u8>,
+    db: Option<i64>,
     replace: bool,
 }
 
 impl Copy {
-    pub fn new(source: String, destination: String) -> Self {
+    pub fn new(source: Vec<u8>, destination: Vec<u8>) -> Self {
         Self {
             source,
             destination,
+            db: None,
             replace: false,
         }
     }
 }
 
 impl Apply for Copy {
-    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
-        if (self.replace || !store.contains_key(&self.destination))
-            && store.copy(&self.source, &self.destination)
-        {
-            Ok(Response::One)
-        } else {
-            Ok(Response::Zero)
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let source_db = context.session.db();
+        let db = match self.db {
+            Some(db) => context.check_db(db)?,
+            None => source_db,
+        };
+        if db == source_db && self.source == self.destination {
+            return Err(Error::Raw(
+                b"-ERR source and destination objects are the same\r\n",
+            ));
         }
+
+        let (value, expires) = match context.databases[source_db].clone_entry(&self.source) {
+            Some(entry) => entry,
+            None => return Ok(Response::Zero),
+        };
+        let store = &mut context.databases[db];
+        if !self.replace && store.contains_key(&self.destination) {
+            return Ok(Response::Zero);
+        }
+
+        store.set_entry(&self.destination, value, expires);
+        Ok(Response::One)
+    }
+
+    fn is_write(&self) -> bool {
+        true
     }
 }
 
@@ -35,10 +56,18 @@ pub struct CopyParser {
 impl CopyParser {
     pub fn new() -> Self {
         Self {
-            options: vec![(vec!["REPLACE"], CopyParser::try_replace)],
+            options: vec![
+                (vec!["DB"], CopyParser::try_db),
+                (vec!["REPLACE"], CopyParser::try_replace),
+            ],
         }
     }
 
+    fn try_db(copy: &mut Copy, _: &str, input: &mut Input) -> Result<(), Error> {
+        copy.db = Some(input.next_i64()?);
+        Ok(())
+    }
+
     fn try_replace(copy: &mut Copy, _: &str, _: &mut Input) -> Result<(), Error> {
         copy.replace = true;
         Ok(())
@@ -47,8 +76,8 @@ impl CopyParser {
 
 impl TryParse for CopyParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let source = input.next_string()?;
-        let destination = input.next_string()?;
+        let source = input.next_bytes()?;
+        let destination = input.next_bytes()?;
 
         Ok(Box::new(parse_options(
             "COPY",
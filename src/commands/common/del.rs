@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Del {
-    keys: Vec<String>,
+    keys: Vec<Vec<u8>>,
 }
 
 impl Del {
-    pub fn new(keys: Vec<String>) -> Self {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
         Self { keys }
     }
 }
 
-impl Apply for Del {
+impl ApplyToStore for Del {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         Ok(Response::Usize(
             self.keys
@@ -19,6 +19,10 @@ impl Apply for Del {
                 .count(),
         ))
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct DelParser {}
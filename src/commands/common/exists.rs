@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Exists {
-    keys: Vec<String>,
+    keys: Vec<Vec<u8>>,
 }
 
 impl Exists {
-    pub fn new(keys: Vec<String>) -> Self {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
         Self { keys }
     }
 }
 
-impl Apply for Exists {
+impl ApplyToStore for Exists {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         Ok(Response::Usize(
             self.keys
@@ -2,16 +2,16 @@ use crate::commands::prelude::*;
 use crate::storage::Pattern;
 
 struct Keys {
-    pattern: String,
+    pattern: Vec<u8>,
 }
 
 impl Keys {
-    pub fn new(pattern: String) -> Self {
+    pub fn new(pattern: Vec<u8>) -> Self {
         Self { pattern }
     }
 }
 
-impl Apply for Keys {
+impl ApplyToStore for Keys {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         if let Ok(pattern) = Pattern::try_parse(&self.pattern) {
             Ok(Response::List(store.keys(&pattern)))
@@ -31,6 +31,6 @@ impl KeysParser {
 
 impl TryParse for KeysParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Keys::new(input.next_string()?)))
+        Ok(Box::new(Keys::new(input.next_bytes()?)))
     }
 }
@@ -0,0 +1,53 @@
+use crate::commands::prelude::*;
+
+struct Move {
+    key: Vec<u8>,
+    db: i64,
+}
+
+impl Move {
+    pub fn new(key: Vec<u8>, db: i64) -> Self {
+        Self { key, db }
+    }
+}
+
+impl Apply for Move {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let source_db = context.session.db();
+        let db = context.check_db(self.db)?;
+        if db == source_db {
+            return Err(Error::Raw(
+                b"-ERR source and destination objects are the same\r\n",
+            ));
+        }
+
+        if context.databases[db].contains_key(&self.key) {
+            return Ok(Response::Zero);
+        }
+        match context.databases[source_db].remove_entry(&self.key) {
+            Some((value, expires)) => {
+                context.databases[db].set_entry(&self.key, value, expires);
+                Ok(Response::One)
+            }
+            None => Ok(Response::Zero),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct MoveParser {}
+
+impl MoveParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for MoveParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Move::new(input.next_bytes()?, input.next_i64()?)))
+    }
+}
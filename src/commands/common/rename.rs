@@ -1,17 +1,17 @@
 use crate::commands::prelude::*;
 
 struct Rename {
-    key: String,
-    new_key: String,
+    key: Vec<u8>,
+    new_key: Vec<u8>,
 }
 
 impl Rename {
-    pub fn new(key: String, new_key: String) -> Self {
+    pub fn new(key: Vec<u8>, new_key: Vec<u8>) -> Self {
         Self { key, new_key }
     }
 }
 
-impl Apply for Rename {
+impl ApplyToStore for Rename {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         if store.rename(&self.key, &self.new_key) {
             Ok(Response::Ok)
@@ -19,6 +19,10 @@ impl Apply for Rename {
             Err(Error::KeyNotFound)
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct RenameParser {}
@@ -32,8 +36,8 @@ impl RenameParser {
 impl TryParse for RenameParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(Rename::new(
-            input.next_string()?,
-            input.next_string()?,
+            input.next_bytes()?,
+            input.next_bytes()?,
         )))
     }
 }
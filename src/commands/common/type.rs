@@ -2,28 +2,32 @@ use crate::commands::prelude::*;
 
 const NONE: Response = Response::Raw(b"+none\r\n");
 
-const INTEGER: Response = Response::Raw(b"+integer\r\n");
+const HASH: Response = Response::Raw(b"+hash\r\n");
 const LIST: Response = Response::Raw(b"+list\r\n");
 const SET: Response = Response::Raw(b"+set\r\n");
+const SORTED_SET: Response = Response::Raw(b"+zset\r\n");
+const STREAM: Response = Response::Raw(b"+stream\r\n");
 const STRING: Response = Response::Raw(b"+string\r\n");
 
 struct Type {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Type {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for Type {
+impl ApplyToStore for Type {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get(&self.key) {
             Some(value) => match value.kind() {
-                Kind::Integer => Ok(INTEGER),
+                Kind::Hash => Ok(HASH),
                 Kind::List => Ok(LIST),
                 Kind::Set => Ok(SET),
+                Kind::SortedSet => Ok(SORTED_SET),
+                Kind::Stream => Ok(STREAM),
                 Kind::String => Ok(STRING),
             },
             None => Ok(NONE),
@@ -41,6 +45,6 @@ impl TypeParser {
 
 impl TryParse for TypeParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Type::new(input.next_string()?)))
+        Ok(Box::new(Type::new(input.next_bytes()?)))
     }
 }
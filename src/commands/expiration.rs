@@ -1,4 +1,4 @@
-pub use expire::{Expire, try_expiry};
+pub use expire::{Expire, try_expiry, When};
 
 pub mod expire_at_ms;
 pub mod expire_at_s;
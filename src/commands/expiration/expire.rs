@@ -3,31 +3,23 @@ use chrono::{DateTime, Utc};
 use crate::commands::prelude::*;
 
 pub struct Expire {
-    key: String,
+    key: Vec<u8>,
     at: DateTime<Utc>,
     expiry: When,
 }
 
 impl Expire {
-    pub fn new(key: String, at: DateTime<Utc>) -> Self {
+    pub fn new(key: Vec<u8>, at: DateTime<Utc>) -> Self {
         Self {
             key,
             at,
             expiry: When::Always,
         }
     }
-}
 
-impl Apply for Expire {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
-        let allow = store.contains_key(&self.key)
-            && match self.expiry {
-                When::Always => true,
-                When::None => store.expires(&self.key).is_none(),
-                When::Has => store.expires(&self.key).is_some(),
-                When::LessThan => store.expires(&self.key).is_some_and(|at| self.at < *at),
-                When::GreaterThan => store.expires(&self.key).is_some_and(|at| self.at > *at),
-            };
+        let allow =
+            store.contains_key(&self.key) && self.expiry.allows(store.expires(&self.key), &self.at);
         if !allow {
             return Ok(Response::Zero);
         }
@@ -42,7 +34,29 @@ impl Apply for Expire {
     }
 }
 
-enum When {
+impl Apply for Expire {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        // replay at the same moment no matter when
+        let mut args = vec![
+            b"PEXPIREAT".to_vec(),
+            self.key.clone(),
+            self.at.timestamp_millis().to_string().into_bytes(),
+        ];
+        if let Some(token) = self.expiry.token() {
+            args.push(token.as_bytes().to_vec());
+        }
+
+        context.propagate(args);
+        self.apply(context.store())
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+/// When is the condition on the current expiration for setting a new one.
+pub enum When {
     Always,
     Has,
     None,
@@ -50,18 +64,40 @@ enum When {
     GreaterThan,
 }
 
-pub fn try_expiry(expire: &mut Expire, token: &str, _input: &mut Input) -> Result<(), Error> {
-    expire.expiry = match token {
-        "NX" => When::None,
-        "XX" => When::Has,
-        "LT" => When::LessThan,
-        "GT" => When::GreaterThan,
-        _ => {
-            return Err(Error::UnknownOption(
-                "EXPIRE".to_string(),
-                token.to_string(),
-            ))
+impl When {
+    pub fn try_parse(command: &str, token: &str) -> Result<Self, Error> {
+        match token {
+            "NX" => Ok(When::None),
+            "XX" => Ok(When::Has),
+            "LT" => Ok(When::LessThan),
+            "GT" => Ok(When::GreaterThan),
+            _ => Err(Error::UnknownOption(command.to_string(), token.to_string())),
         }
-    };
+    }
+
+    /// Returns `true` if the expiration may change from `current` to `at`.
+    pub fn allows(&self, current: Option<&DateTime<Utc>>, at: &DateTime<Utc>) -> bool {
+        match self {
+            When::Always => true,
+            When::None => current.is_none(),
+            When::Has => current.is_some(),
+            When::LessThan => current.is_some_and(|current| at < current),
+            When::GreaterThan => current.is_some_and(|current| at > current),
+        }
+    }
+
+    pub fn token(&self) -> Option<&'static str> {
+        match self {
+            When::Always => None,
+            When::Has => Some("XX"),
+            When::None => Some("NX"),
+            When::LessThan => Some("LT"),
+            When::GreaterThan => Some("GT"),
+        }
+    }
+}
+
+pub fn try_expiry(expire: &mut Expire, token: &str, _input: &mut Input) -> Result<(), Error> {
+    expire.expiry = When::try_parse("EXPIRE", token)?;
     Ok(())
 }
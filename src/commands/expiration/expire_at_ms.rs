@@ -18,11 +18,11 @@ impl ExpireAtMillisParser {
 
 impl TryParse for ExpireAtMillisParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
         let unix_time_milliseconds = input.next_i64()?;
 
         Ok(Box::new(parse_options(
-            "ExpireAtMillis",
+            "PEXPIREAT",
             &self.options,
             input,
             Expire::new(
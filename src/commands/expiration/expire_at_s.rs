@@ -18,7 +18,7 @@ impl ExpireAtSecsParser {
 
 impl TryParse for ExpireAtSecsParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
         let unix_time_seconds = input.next_i64()?;
 
         Ok(Box::new(parse_options(
@@ -20,7 +20,7 @@ impl ExpireMillisParser {
 
 impl TryParse for ExpireMillisParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
         let milliseconds = input.next_u64()?;
 
         Ok(Box::new(parse_options(
@@ -20,7 +20,7 @@ impl ExpireSecsParser {
 
 impl TryParse for ExpireSecsParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
         let seconds = input.next_u64()?;
 
         Ok(Box::new(parse_options(
@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct ExpireTimeSecs {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl ExpireTimeSecs {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for ExpireTimeSecs {
+impl ApplyToStore for ExpireTimeSecs {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         if !store.contains_key(&self.key) {
             Ok(Response::I64(-2))
@@ -32,6 +32,6 @@ impl ExpireTimeSecsParser {
 
 impl TryParse for ExpireTimeSecsParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(ExpireTimeSecs::new(input.next_string()?)))
+        Ok(Box::new(ExpireTimeSecs::new(input.next_bytes()?)))
     }
 }
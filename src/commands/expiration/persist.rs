@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Persist {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Persist {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for Persist {
+impl ApplyToStore for Persist {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         if store.contains_key(&self.key) && store.persist(&self.key) {
             Ok(Response::One)
@@ -18,6 +18,10 @@ impl Apply for Persist {
             Ok(Response::Zero)
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct PersistParser {}
@@ -30,6 +34,6 @@ impl PersistParser {
 
 impl TryParse for PersistParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Persist::new(input.next_string()?)))
+        Ok(Box::new(Persist::new(input.next_bytes()?)))
     }
 }
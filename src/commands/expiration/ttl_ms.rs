@@ -3,16 +3,16 @@ use chrono::Utc;
 use crate::commands::prelude::*;
 
 struct PTimeToLive {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl PTimeToLive {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for PTimeToLive {
+impl ApplyToStore for PTimeToLive {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         if !store.contains_key(&self.key) {
             Ok(Response::I64(-2))
@@ -36,6 +36,6 @@ impl PTimeToLiveParser {
 
 impl TryParse for PTimeToLiveParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(PTimeToLive::new(input.next_string()?)))
+        Ok(Box::new(PTimeToLive::new(input.next_bytes()?)))
     }
 }
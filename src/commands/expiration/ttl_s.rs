@@ -3,16 +3,16 @@ use chrono::Utc;
 use crate::commands::prelude::*;
 
 struct TimeToLive {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl TimeToLive {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for TimeToLive {
+impl ApplyToStore for TimeToLive {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         if !store.contains_key(&self.key) {
             Ok(Response::I64(-2))
@@ -36,6 +36,6 @@ impl TimeToLiveParser {
 
 impl TryParse for TimeToLiveParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(TimeToLive::new(input.next_string()?)))
+        Ok(Box::new(TimeToLive::new(input.next_bytes()?)))
     }
 }
@@ -0,0 +1,33 @@
+pub use expire::{expire_options, invalid_expire_time, parse_expire, FieldExpire};
+pub use fields::{
+    missing_fields, next_fields, no_fields, try_fields, EXPIRED, NO_EXPIRATION, NO_FIELD, NOT_MET,
+};
+
+pub mod del;
+pub mod exists;
+pub mod expire_at_ms;
+pub mod expire_at_s;
+pub mod expire_ms;
+pub mod expire_s;
+pub mod expire_time_ms;
+pub mod expire_time_s;
+pub mod get;
+pub mod get_all;
+pub mod get_ex;
+pub mod get_multiple;
+pub mod increment_by;
+pub mod increment_by_float;
+pub mod keys;
+pub mod len;
+pub mod persist;
+pub mod random_fields;
+pub mod set;
+pub mod set_ex;
+pub mod set_if_not_set;
+pub mod str_len;
+pub mod ttl_ms;
+pub mod ttl_s;
+pub mod values;
+
+mod expire;
+mod fields;
@@ -0,0 +1,58 @@
+use crate::commands::prelude::*;
+
+struct Del {
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl Del {
+    pub fn new(key: Vec<u8>, fields: Vec<Vec<u8>>) -> Self {
+        Self { key, fields }
+    }
+}
+
+impl ApplyToStore for Del {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(ref mut fields)) => {
+                let removed = self
+                    .fields
+                    .iter()
+                    .filter(|field| fields.remove(field).is_some())
+                    .count();
+                if fields.is_empty() {
+                    store.remove(&self.key);
+                }
+                Ok(Response::Usize(removed))
+            }
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct DelParser {}
+
+impl DelParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for DelParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        if !input.has_next() {
+            return Err(Error::MissingArgument(
+                "HDEL".to_string(),
+                "field".to_string(),
+            ));
+        }
+
+        Ok(Box::new(Del::new(key, input.rest()?)))
+    }
+}
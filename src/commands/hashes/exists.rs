@@ -0,0 +1,41 @@
+use crate::commands::prelude::*;
+
+struct Exists {
+    key: Vec<u8>,
+    field: Vec<u8>,
+}
+
+impl Exists {
+    pub fn new(key: Vec<u8>, field: Vec<u8>) -> Self {
+        Self { key, field }
+    }
+}
+
+impl ApplyToStore for Exists {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(fields)) => {
+                Ok(Response::int_from_bool(fields.contains_key(&self.field)))
+            }
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct ExistsParser {}
+
+impl ExistsParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ExistsParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Exists::new(
+            input.next_bytes()?,
+            input.next_bytes()?,
+        )))
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+
+use crate::commands::expiration::When;
+use crate::commands::prelude::*;
+use crate::storage::Hash;
+
+use super::{EXPIRED, NO_FIELD, NOT_MET, missing_fields, no_fields, try_fields};
+
+pub fn invalid_expire_time(command: &str) -> Error {
+    Error::String(format!(
+        "ERR invalid expire time in '{}' command",
+        command.to_lowercase()
+    ))
+}
+
+pub struct FieldExpire {
+    key: Vec<u8>,
+    at: DateTime<Utc>,
+    expiry: When,
+    fields: Vec<Vec<u8>>,
+}
+
+impl FieldExpire {
+    pub fn new(key: Vec<u8>, at: DateTime<Utc>) -> Self {
+        Self {
+            key,
+            at,
+            expiry: When::Always,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Returns the reply for the field after setting its expiration if allowed.
+    fn expire(&self, hash: &mut Hash, field: &[u8], now: &DateTime<Utc>) -> i64 {
+        if !hash.contains_key(field) {
+            NO_FIELD
+        } else if !self.expiry.allows(hash.expires(field), &self.at) {
+            NOT_MET
+        } else if self.at <= *now {
+            hash.remove(field);
+            EXPIRED
+        } else {
+            hash.expire_at(field, &self.at);
+            1
+        }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let now = Utc::now();
+        let (replies, empty) = match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(hash)) => {
+                let replies = self
+                    .fields
+                    .iter()
+                    .map(|field| Response::I64(self.expire(hash, field, &now)))
+                    .collect_vec();
+                (replies, hash.is_empty())
+            }
+            IfKindResult::NotSet => return Ok(no_fields(&self.fields)),
+            _ => return Err(Error::WrongType),
+        };
+
+        if empty {
+            store.remove(&self.key);
+        } else {
+            store.schedule_fields(&self.key);
+        }
+        Ok(Response::Array(replies))
+    }
+}
+
+impl Apply for FieldExpire {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        // replay at the same moment no matter when
+        let mut args = vec![
+            b"HPEXPIREAT".to_vec(),
+            self.key.clone(),
+            self.at.timestamp_millis().to_string().into_bytes(),
+        ];
+        if let Some(token) = self.expiry.token() {
+            args.push(token.as_bytes().to_vec());
+        }
+        args.push(b"FIELDS".to_vec());
+        args.push(self.fields.len().to_string().into_bytes());
+        args.extend(self.fields.iter().cloned());
+
+        context.propagate(args);
+        self.apply(context.store())
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the options shared by the commands that set field expirations.
+pub fn expire_options() -> Options<FieldExpire> {
+    vec![
+        (vec!["NX", "XX", "LT", "GT"], try_expiry),
+        (vec!["FIELDS"], try_expire_fields),
+    ]
+}
+
+fn try_expiry(expire: &mut FieldExpire, token: &str, _input: &mut Input) -> Result<(), Error> {
+    expire.expiry = When::try_parse("HEXPIRE", token)?;
+    Ok(())
+}
+
+fn try_expire_fields(
+    expire: &mut FieldExpire,
+    _token: &str,
+    input: &mut Input,
+) -> Result<(), Error> {
+    expire.fields = try_fields(input, 1)?;
+    Ok(())
+}
+
+/// Parses the options after the time and checks that FIELDS was given.
+pub fn parse_expire(
+    command: &str,
+    options: &Options<FieldExpire>,
+    input: &mut Input,
+    expire: FieldExpire,
+) -> Result<Box<dyn Apply>, Error> {
+    let expire = parse_options(command, options, input, expire)?;
+    if expire.fields.is_empty() {
+        return Err(missing_fields());
+    }
+    Ok(Box::new(expire))
+}
@@ -0,0 +1,31 @@
+use crate::commands::prelude::*;
+use crate::parse::expire_at;
+
+use super::{FieldExpire, expire_options, invalid_expire_time, parse_expire};
+
+pub struct ExpireAtMillisParser {
+    options: Options<FieldExpire>,
+}
+
+impl ExpireAtMillisParser {
+    pub fn new() -> Self {
+        Self {
+            options: expire_options(),
+        }
+    }
+}
+
+impl TryParse for ExpireAtMillisParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let at = expire_at("PXAT", input.next_i64()?)
+            .ok_or_else(|| invalid_expire_time("HPEXPIREAT"))?;
+
+        parse_expire(
+            "HPEXPIREAT",
+            &self.options,
+            input,
+            FieldExpire::new(key, at),
+        )
+    }
+}
@@ -0,0 +1,26 @@
+use crate::commands::prelude::*;
+use crate::parse::expire_at;
+
+use super::{FieldExpire, expire_options, invalid_expire_time, parse_expire};
+
+pub struct ExpireAtSecsParser {
+    options: Options<FieldExpire>,
+}
+
+impl ExpireAtSecsParser {
+    pub fn new() -> Self {
+        Self {
+            options: expire_options(),
+        }
+    }
+}
+
+impl TryParse for ExpireAtSecsParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let at =
+            expire_at("EXAT", input.next_i64()?).ok_or_else(|| invalid_expire_time("HEXPIREAT"))?;
+
+        parse_expire("HEXPIREAT", &self.options, input, FieldExpire::new(key, at))
+    }
+}
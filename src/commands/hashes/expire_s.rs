@@ -0,0 +1,28 @@
+use crate::commands::prelude::*;
+use crate::parse::expire_at;
+
+use super::{FieldExpire, expire_options, invalid_expire_time, parse_expire};
+
+pub struct ExpireSecsParser {
+    options: Options<FieldExpire>,
+}
+
+impl ExpireSecsParser {
+    pub fn new() -> Self {
+        Self {
+            options: expire_options(),
+        }
+    }
+}
+
+impl TryParse for ExpireSecsParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let at = i64::try_from(input.next_u64()?)
+            .ok()
+            .and_then(|time| expire_at("EX", time))
+            .ok_or_else(|| invalid_expire_time("HEXPIRE"))?;
+
+        parse_expire("HEXPIRE", &self.options, input, FieldExpire::new(key, at))
+    }
+}
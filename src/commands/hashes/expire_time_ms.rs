@@ -0,0 +1,51 @@
+use crate::commands::prelude::*;
+
+use super::{NO_EXPIRATION, NO_FIELD, next_fields, no_fields};
+
+struct ExpireTimeMillis {
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl ExpireTimeMillis {
+    pub fn new(key: Vec<u8>, fields: Vec<Vec<u8>>) -> Self {
+        Self { key, fields }
+    }
+}
+
+impl ApplyToStore for ExpireTimeMillis {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(hash)) => Ok(Response::Array(
+                self.fields
+                    .iter()
+                    .map(|field| {
+                        Response::I64(if hash.contains_key(field) {
+                            hash.expires(field)
+                                .map_or(NO_EXPIRATION, |at| at.timestamp_millis())
+                        } else {
+                            NO_FIELD
+                        })
+                    })
+                    .collect(),
+            )),
+            IfKindResult::NotSet => Ok(no_fields(&self.fields)),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct ExpireTimeMillisParser {}
+
+impl ExpireTimeMillisParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ExpireTimeMillisParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        Ok(Box::new(ExpireTimeMillis::new(key, next_fields(input)?)))
+    }
+}
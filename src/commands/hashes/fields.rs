@@ -0,0 +1,42 @@
+use crate::commands::prelude::*;
+
+// the replies for each field
+pub const NO_FIELD: i64 = -2;
+pub const NO_EXPIRATION: i64 = -1;
+pub const NOT_MET: i64 = 0;
+pub const EXPIRED: i64 = 2;
+
+/// Reads the count and fields after FIELDS, which must end the command,
+/// taking `per_field` arguments for each field.
+pub fn try_fields(input: &mut Input, per_field: usize) -> Result<Vec<Vec<u8>>, Error> {
+    let count = input.next_u64()? as usize;
+    if count == 0 {
+        return Err(Error::Raw(
+            b"-ERR Parameter `numFields` should be greater than 0\r\n",
+        ));
+    }
+    if input.len() != count * per_field {
+        return Err(Error::Raw(
+            b"-ERR The `numfields` parameter must match the number of arguments\r\n",
+        ));
+    }
+
+    input.rest()
+}
+
+/// Reads the FIELDS that follow the key of a command without options.
+pub fn next_fields(input: &mut Input) -> Result<Vec<Vec<u8>>, Error> {
+    if input.next_token()? != "FIELDS" {
+        return Err(missing_fields());
+    }
+    try_fields(input, 1)
+}
+
+pub fn missing_fields() -> Error {
+    Error::Raw(b"-ERR Mandatory argument FIELDS is missing or not at the right position\r\n")
+}
+
+/// Returns the reply for each field of a key that doesn't exist.
+pub fn no_fields<'a>(fields: &[Vec<u8>]) -> Response<'a> {
+    Response::Array(fields.iter().map(|_| Response::I64(NO_FIELD)).collect())
+}
@@ -0,0 +1,39 @@
+use crate::commands::prelude::*;
+
+struct Get {
+    key: Vec<u8>,
+    field: Vec<u8>,
+}
+
+impl Get {
+    pub fn new(key: Vec<u8>, field: Vec<u8>) -> Self {
+        Self { key, field }
+    }
+}
+
+impl ApplyToStore for Get {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(fields)) => match fields.get(&self.field) {
+                Some(value) => Ok(Response::BulkStringRef(value)),
+                None => Ok(Response::Null),
+            },
+            IfKindResult::NotSet => Ok(Response::Null),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct GetParser {}
+
+impl GetParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for GetParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Get::new(input.next_bytes()?, input.next_bytes()?)))
+    }
+}
@@ -0,0 +1,35 @@
+use crate::commands::prelude::*;
+
+struct GetAll {
+    key: Vec<u8>,
+}
+
+impl GetAll {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self { key }
+    }
+}
+
+impl ApplyToStore for GetAll {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(value) => Ok(Response::ValueRef(value)),
+            IfKindResult::NotSet => Ok(Response::EmptyMap),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct GetAllParser {}
+
+impl GetAllParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for GetAllParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(GetAll::new(input.next_bytes()?)))
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::Utc;
+use itertools::Itertools;
+
+use crate::commands::prelude::*;
+use crate::parse::Expiration;
+
+use super::{invalid_expire_time, missing_fields, try_fields};
+
+struct GetEx {
+    key: Vec<u8>,
+    expire: Expiration,
+    fields: Vec<Vec<u8>>,
+}
+
+impl GetEx {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self {
+            key,
+            expire: Expiration::Keep,
+            fields: Vec::new(),
+        }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let now = Utc::now();
+        let (values, empty) = match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(hash)) => {
+                let values = self
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let value = hash.get(field).cloned();
+                        match self.expire {
+                            Expiration::Keep => (),
+                            Expiration::Never => {
+                                hash.persist(field);
+                            }
+                            Expiration::At(at) if at > now => hash.expire_at(field, &at),
+                            Expiration::At(_) => {
+                                hash.remove(field);
+                            }
+                        }
+                        value.map_or(Response::Null, Response::BulkString)
+                    })
+                    .collect_vec();
+                (values, hash.is_empty())
+            }
+            IfKindResult::NotSet => {
+                return Ok(Response::Array(
+                    self.fields.iter().map(|_| Response::Null).collect(),
+                ))
+            }
+            _ => return Err(Error::WrongType),
+        };
+
+        if empty {
+            store.remove(&self.key);
+        } else {
+            store.schedule_fields(&self.key);
+        }
+        Ok(Response::Array(values))
+    }
+}
+
+impl Apply for GetEx {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let mut args = match self.expire {
+            Expiration::Keep => vec![],
+            Expiration::Never => vec![b"HPERSIST".to_vec(), self.key.clone()],
+            Expiration::At(at) => vec![
+                b"HPEXPIREAT".to_vec(),
+                self.key.clone(),
+                at.timestamp_millis().to_string().into_bytes(),
+            ],
+        };
+        if args.is_empty() {
+            context.propagate_nothing();
+        } else {
+            args.push(b"FIELDS".to_vec());
+            args.push(self.fields.len().to_string().into_bytes());
+            args.extend(self.fields.iter().cloned());
+            context.propagate(args);
+        }
+
+        self.apply(context.store())
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct GetExParser {
+    options: Options<GetEx>,
+}
+
+impl GetExParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![
+                (
+                    vec!["EX", "PX", "EXAT", "PXAT", "PERSIST"],
+                    GetExParser::try_expire,
+                ),
+                (vec!["FIELDS"], GetExParser::try_fields),
+            ],
+        }
+    }
+
+    fn try_expire(get_ex: &mut GetEx, token: &str, input: &mut Input) -> Result<(), Error> {
+        get_ex.expire = Expiration::try_parse(token, input).map_err(|err| match err {
+            Error::ExpireTime => invalid_expire_time("HGETEX"),
+            err => err,
+        })?;
+        Ok(())
+    }
+
+    fn try_fields(get_ex: &mut GetEx, _token: &str, input: &mut Input) -> Result<(), Error> {
+        get_ex.fields = try_fields(input, 1)?;
+        Ok(())
+    }
+}
+
+impl TryParse for GetExParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let get_ex = parse_options("HGETEX", &self.options, input, GetEx::new(key))?;
+
+        if get_ex.fields.is_empty() {
+            return Err(missing_fields());
+        }
+        Ok(Box::new(get_ex))
+    }
+}
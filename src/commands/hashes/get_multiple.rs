@@ -0,0 +1,54 @@
+use crate::commands::prelude::*;
+
+struct GetMultiple {
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl GetMultiple {
+    pub fn new(key: Vec<u8>, fields: Vec<Vec<u8>>) -> Self {
+        Self { key, fields }
+    }
+}
+
+impl ApplyToStore for GetMultiple {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(fields)) => Ok(Response::Array(
+                self.fields
+                    .iter()
+                    .map(|field| match fields.get(field) {
+                        Some(value) => Response::BulkStringRef(value),
+                        None => Response::Null,
+                    })
+                    .collect(),
+            )),
+            IfKindResult::NotSet => Ok(Response::Array(
+                self.fields.iter().map(|_| Response::Null).collect(),
+            )),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct GetMultipleParser {}
+
+impl GetMultipleParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for GetMultipleParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        if !input.has_next() {
+            return Err(Error::MissingArgument(
+                "HMGET".to_string(),
+                "field".to_string(),
+            ));
+        }
+
+        Ok(Box::new(GetMultiple::new(key, input.rest()?)))
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use crate::commands::prelude::*;
+
+struct IncrementBy {
+    key: Vec<u8>,
+    field: Vec<u8>,
+    increment: i64,
+}
+
+impl IncrementBy {
+    pub fn new(key: Vec<u8>, field: Vec<u8>, increment: i64) -> Self {
+        Self {
+            key,
+            field,
+            increment,
+        }
+    }
+
+    fn increment(&self, value: Option<&Vec<u8>>) -> Result<i64, Error> {
+        let value = match value {
+            Some(value) => std::str::from_utf8(value)
+                .ok()
+                .and_then(|s| s.parse::<i64>().ok())
+                .ok_or(Error::Raw(b"-ERR hash value is not an integer\r\n"))?,
+            None => 0,
+        };
+
+        value.checked_add(self.increment).ok_or(Error::Raw(
+            b"-ERR increment or decrement would overflow\r\n",
+        ))
+    }
+}
+
+impl ApplyToStore for IncrementBy {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(ref mut fields)) => {
+                let value = self.increment(fields.get(&self.field))?;
+                fields.insert(self.field.clone(), value.to_string().into_bytes());
+                Ok(Response::I64(value))
+            }
+            IfKindResult::NotSet => {
+                let value = self.increment(None)?;
+                store.set(
+                    &self.key,
+                    Value::from(HashMap::from([(
+                        self.field.clone(),
+                        value.to_string().into_bytes(),
+                    )])),
+                );
+                Ok(Response::I64(value))
+            }
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct IncrementByParser {}
+
+impl IncrementByParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for IncrementByParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(IncrementBy::new(
+            input.next_bytes()?,
+            input.next_bytes()?,
+            input.next_i64()?,
+        )))
+    }
+}
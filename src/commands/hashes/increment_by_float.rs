@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use crate::commands::prelude::*;
+use crate::parse::parse_f64;
+
+struct IncrementByFloat {
+    key: Vec<u8>,
+    field: Vec<u8>,
+    increment: f64,
+}
+
+impl IncrementByFloat {
+    pub fn new(key: Vec<u8>, field: Vec<u8>, increment: f64) -> Self {
+        Self {
+            key,
+            field,
+            increment,
+        }
+    }
+
+    fn increment(&self, value: Option<&Vec<u8>>) -> Result<Vec<u8>, Error> {
+        let value = match value {
+            Some(value) => {
+                parse_f64(value).map_err(|_| Error::Raw(b"-ERR hash value is not a float\r\n"))?
+            }
+            None => 0.0,
+        };
+
+        let value = value + self.increment;
+        if value.is_finite() {
+            Ok(value.to_string().into_bytes())
+        } else {
+            Err(Error::Raw(
+                b"-ERR increment would produce NaN or Infinity\r\n",
+            ))
+        }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(ref mut fields)) => {
+                let value = self.increment(fields.get(&self.field))?;
+                fields.insert(self.field.clone(), value.clone());
+                Ok(Response::BulkString(value))
+            }
+            IfKindResult::NotSet => {
+                let value = self.increment(None)?;
+                store.set(
+                    &self.key,
+                    Value::from(HashMap::from([(self.field.clone(), value.clone())])),
+                );
+                Ok(Response::BulkString(value))
+            }
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+impl Apply for IncrementByFloat {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let propagated = context.propagated;
+        let response = self.apply(&mut context.databases[context.session.db()])?;
+
+        // replay sets the same value instead of repeating the rounding
+        if let Response::BulkString(value) = &response {
+            *propagated = Some(vec![vec![
+                b"HSET".to_vec(),
+                self.key.clone(),
+                self.field.clone(),
+                value.clone(),
+            ]]);
+        }
+        Ok(response)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct IncrementByFloatParser {}
+
+impl IncrementByFloatParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for IncrementByFloatParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let field = input.next_bytes()?;
+        let increment = input.next_f64()?;
+
+        Ok(Box::new(IncrementByFloat::new(key, field, increment)))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::commands::prelude::*;
+
+struct Keys {
+    key: Vec<u8>,
+}
+
+impl Keys {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self { key }
+    }
+}
+
+impl ApplyToStore for Keys {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(fields)) => {
+                Ok(Response::List(fields.keys().cloned().collect()))
+            }
+            IfKindResult::NotSet => Ok(Response::EmptyList),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct KeysParser {}
+
+impl KeysParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for KeysParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Keys::new(input.next_bytes()?)))
+    }
+}
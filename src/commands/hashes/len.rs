@@ -0,0 +1,35 @@
+use crate::commands::prelude::*;
+
+struct Len {
+    key: Vec<u8>,
+}
+
+impl Len {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self { key }
+    }
+}
+
+impl ApplyToStore for Len {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(fields)) => Ok(Response::Usize(fields.len())),
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct LenParser {}
+
+impl LenParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for LenParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Len::new(input.next_bytes()?)))
+    }
+}
@@ -0,0 +1,58 @@
+use itertools::Itertools;
+
+use crate::commands::prelude::*;
+
+use super::{NO_EXPIRATION, NO_FIELD, next_fields, no_fields};
+
+struct Persist {
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl Persist {
+    pub fn new(key: Vec<u8>, fields: Vec<Vec<u8>>) -> Self {
+        Self { key, fields }
+    }
+}
+
+impl ApplyToStore for Persist {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(hash)) => Ok(Response::Array(
+                self.fields
+                    .iter()
+                    .map(|field| {
+                        Response::I64(if !hash.contains_key(field) {
+                            NO_FIELD
+                        } else if hash.persist(field) {
+                            1
+                        } else {
+                            NO_EXPIRATION
+                        })
+                    })
+                    .collect_vec(),
+            )),
+            IfKindResult::NotSet => Ok(no_fields(&self.fields)),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct PersistParser {}
+
+impl PersistParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PersistParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        Ok(Box::new(Persist::new(key, next_fields(input)?)))
+    }
+}
@@ -0,0 +1,122 @@
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::{Rng, thread_rng};
+
+use crate::commands::prelude::*;
+use crate::network::Protocol;
+
+/// The most fields to make room for up front when repeats are allowed.
+const MAX_PREALLOCATED: usize = 1024;
+
+struct RandomFields {
+    key: Vec<u8>,
+    count: Option<i64>,
+    with_values: bool,
+}
+
+impl RandomFields {
+    pub fn new(key: Vec<u8>, count: Option<i64>, with_values: bool) -> Self {
+        Self {
+            key,
+            count,
+            with_values,
+        }
+    }
+}
+
+impl Apply for RandomFields {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let protocol = context.session.protocol();
+        let fields = match context.store().get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(fields)) => fields.iter().collect_vec(),
+            IfKindResult::NotSet => Vec::new(),
+            _ => return Err(Error::WrongType),
+        };
+        let mut rng = thread_rng();
+
+        let count = match self.count {
+            None if fields.is_empty() => return Ok(Response::Null),
+            None => {
+                let (field, _) = fields[rng.gen_range(0..fields.len())];
+                return Ok(Response::BulkString(field.clone()));
+            }
+            Some(count) => count,
+        };
+        if count == 0 || fields.is_empty() {
+            return Ok(Response::EmptyList);
+        }
+
+        // a negative count may return the same field more than once
+        let chosen = if count < 0 {
+            let count = count.unsigned_abs() as usize;
+            let mut chosen = Vec::with_capacity(count.min(MAX_PREALLOCATED));
+            for _ in 0..count {
+                chosen.push(fields[rng.gen_range(0..fields.len())]);
+            }
+            chosen
+        } else {
+            let mut pool = fields;
+            pool.shuffle(&mut rng);
+            pool.truncate(count as usize);
+            pool
+        };
+
+        if !self.with_values {
+            return Ok(Response::List(
+                chosen.into_iter().map(|(field, _)| field.clone()).collect(),
+            ));
+        }
+        Ok(Response::Array(match protocol {
+            Protocol::Resp2 => chosen
+                .into_iter()
+                .flat_map(|(field, value)| {
+                    [
+                        Response::BulkString(field.clone()),
+                        Response::BulkString(value.clone()),
+                    ]
+                })
+                .collect(),
+            Protocol::Resp3 => chosen
+                .into_iter()
+                .map(|(field, value)| {
+                    Response::Array(vec![
+                        Response::BulkString(field.clone()),
+                        Response::BulkString(value.clone()),
+                    ])
+                })
+                .collect(),
+        }))
+    }
+}
+
+pub struct RandomFieldsParser {}
+
+impl RandomFieldsParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for RandomFieldsParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        if !input.has_next() {
+            return Ok(Box::new(RandomFields::new(key, None, false)));
+        }
+
+        let count = input.next_i64()?;
+        if count < -(i64::MAX / 2) {
+            return Err(Error::Raw(b"-ERR value is out of range\r\n"));
+        }
+        let with_values = match input.has_next() {
+            true if input.next_token()? == "WITHVALUES" => true,
+            true => return Err(Error::Syntax),
+            false => false,
+        };
+        if input.has_next() {
+            return Err(Error::Syntax);
+        }
+
+        Ok(Box::new(RandomFields::new(key, Some(count), with_values)))
+    }
+}
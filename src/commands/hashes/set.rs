@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::commands::prelude::*;
+
+struct Set {
+    key: Vec<u8>,
+    fields: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Set {
+    pub fn new(key: Vec<u8>, fields: Vec<(Vec<u8>, Vec<u8>)>) -> Self {
+        Self { key, fields }
+    }
+}
+
+impl ApplyToStore for Set {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(ref mut fields)) => {
+                let mut added = 0;
+                for (field, value) in &self.fields {
+                    if fields.insert(field.clone(), value.clone()).is_none() {
+                        added += 1;
+                    }
+                }
+                Ok(Response::Usize(added))
+            }
+            IfKindResult::NotSet => {
+                let fields = self.fields.iter().cloned().collect::<HashMap<_, _>>();
+                let added = fields.len();
+                store.set(&self.key, Value::from(fields));
+                Ok(Response::Usize(added))
+            }
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct SetParser {}
+
+impl SetParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for SetParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        if !input.has_next() || !input.len().is_multiple_of(2) {
+            return Err(Error::MissingArgument(
+                "HSET".to_string(),
+                "value".to_string(),
+            ));
+        }
+
+        let mut fields = Vec::with_capacity(input.len() / 2);
+        while input.has_next() {
+            fields.push((input.next_bytes()?, input.next_bytes()?));
+        }
+        Ok(Box::new(Set::new(key, fields)))
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::Utc;
+use itertools::Itertools;
+
+use crate::commands::prelude::*;
+use crate::parse::Expiration;
+use crate::storage::Hash;
+
+use super::{invalid_expire_time, missing_fields, try_fields};
+
+struct SetEx {
+    key: Vec<u8>,
+    when: When,
+    expire: Expiration,
+    fields: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl SetEx {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self {
+            key,
+            when: When::Always,
+            expire: Expiration::Never,
+            fields: Vec::new(),
+        }
+    }
+
+    fn set_fields(&self, hash: &mut Hash) {
+        let now = Utc::now();
+
+        for (field, value) in &self.fields {
+            match self.expire {
+                Expiration::Keep => {
+                    hash.replace(field.clone(), value.clone());
+                }
+                Expiration::Never => {
+                    hash.insert(field.clone(), value.clone());
+                }
+                Expiration::At(at) if at > now => {
+                    hash.insert(field.clone(), value.clone());
+                    hash.expire_at(field, &at);
+                }
+                Expiration::At(_) => {
+                    hash.remove(field);
+                }
+            }
+        }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let empty = match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(hash)) => {
+                let allow = match self.when {
+                    When::Always => true,
+                    When::NoneExist => self
+                        .fields
+                        .iter()
+                        .all(|(field, _)| !hash.contains_key(field)),
+                    When::AllExist => self
+                        .fields
+                        .iter()
+                        .all(|(field, _)| hash.contains_key(field)),
+                };
+                if !allow {
+                    return Ok(Response::Zero);
+                }
+
+                self.set_fields(hash);
+                hash.is_empty()
+            }
+            IfKindResult::NotSet => {
+                if let When::AllExist = self.when {
+                    return Ok(Response::Zero);
+                }
+
+                let mut hash = Hash::new();
+                self.set_fields(&mut hash);
+                if !hash.is_empty() {
+                    store.set(&self.key, Value::from(hash));
+                }
+                return Ok(Response::One);
+            }
+            _ => return Err(Error::WrongType),
+        };
+
+        if empty {
+            store.remove(&self.key);
+        } else {
+            store.schedule_fields(&self.key);
+        }
+        Ok(Response::One)
+    }
+}
+
+impl Apply for SetEx {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        // replay at the same moment no matter when
+        let mut args = vec![b"HSETEX".to_vec(), self.key.clone()];
+        if let Some(token) = self.when.token() {
+            args.push(token.as_bytes().to_vec());
+        }
+        match self.expire {
+            Expiration::Keep => args.push(b"KEEPTTL".to_vec()),
+            Expiration::Never => (),
+            Expiration::At(at) => {
+                args.push(b"PXAT".to_vec());
+                args.push(at.timestamp_millis().to_string().into_bytes());
+            }
+        }
+        args.push(b"FIELDS".to_vec());
+        args.push(self.fields.len().to_string().into_bytes());
+        for (field, value) in &self.fields {
+            args.push(field.clone());
+            args.push(value.clone());
+        }
+
+        context.propagate(args);
+        self.apply(context.store())
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+enum When {
+    Always,
+    NoneExist,
+    AllExist,
+}
+
+impl When {
+    fn token(&self) -> Option<&'static str> {
+        match self {
+            When::Always => None,
+            When::NoneExist => Some("FNX"),
+            When::AllExist => Some("FXX"),
+        }
+    }
+}
+
+pub struct SetExParser {
+    options: Options<SetEx>,
+}
+
+impl SetExParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![
+                (vec!["FNX", "FXX"], SetExParser::try_when),
+                (
+                    vec!["EX", "PX", "EXAT", "PXAT", "KEEPTTL"],
+                    SetExParser::try_expire,
+                ),
+                (vec!["FIELDS"], SetExParser::try_fields),
+            ],
+        }
+    }
+
+    fn try_when(set_ex: &mut SetEx, token: &str, _: &mut Input) -> Result<(), Error> {
+        set_ex.when = match token {
+            "FNX" => When::NoneExist,
+            "FXX" => When::AllExist,
+            _ => panic!("unexpected token"),
+        };
+        Ok(())
+    }
+
+    fn try_expire(set_ex: &mut SetEx, token: &str, input: &mut Input) -> Result<(), Error> {
+        set_ex.expire = Expiration::try_parse(token, input).map_err(|err| match err {
+            Error::ExpireTime => invalid_expire_time("HSETEX"),
+            err => err,
+        })?;
+        Ok(())
+    }
+
+    fn try_fields(set_ex: &mut SetEx, _token: &str, input: &mut Input) -> Result<(), Error> {
+        set_ex.fields = try_fields(input, 2)?.into_iter().tuples().collect();
+        Ok(())
+    }
+}
+
+impl TryParse for SetExParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let set_ex = parse_options("HSETEX", &self.options, input, SetEx::new(key))?;
+
+        if set_ex.fields.is_empty() {
+            return Err(missing_fields());
+        }
+        Ok(Box::new(set_ex))
+    }
+}
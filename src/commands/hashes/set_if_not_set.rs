@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::commands::prelude::*;
+
+struct SetIfNotSet {
+    key: Vec<u8>,
+    field: Vec<u8>,
+    value: Vec<u8>,
+}
+
+impl SetIfNotSet {
+    pub fn new(key: Vec<u8>, field: Vec<u8>, value: Vec<u8>) -> Self {
+        Self { key, field, value }
+    }
+}
+
+impl ApplyToStore for SetIfNotSet {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(ref mut fields)) => {
+                if fields.contains_key(&self.field) {
+                    Ok(Response::Zero)
+                } else {
+                    fields.insert(self.field.clone(), self.value.clone());
+                    Ok(Response::One)
+                }
+            }
+            IfKindResult::NotSet => {
+                store.set(
+                    &self.key,
+                    Value::from(HashMap::from([(self.field.clone(), self.value.clone())])),
+                );
+                Ok(Response::One)
+            }
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct SetIfNotSetParser {}
+
+impl SetIfNotSetParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for SetIfNotSetParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(SetIfNotSet::new(
+            input.next_bytes()?,
+            input.next_bytes()?,
+            input.next_bytes()?,
+        )))
+    }
+}
@@ -0,0 +1,41 @@
+use crate::commands::prelude::*;
+
+struct StrLen {
+    key: Vec<u8>,
+    field: Vec<u8>,
+}
+
+impl StrLen {
+    pub fn new(key: Vec<u8>, field: Vec<u8>) -> Self {
+        Self { key, field }
+    }
+}
+
+impl ApplyToStore for StrLen {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(fields)) => Ok(Response::Usize(
+                fields.get(&self.field).map_or(0, |value| value.len()),
+            )),
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct StrLenParser {}
+
+impl StrLenParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for StrLenParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(StrLen::new(
+            input.next_bytes()?,
+            input.next_bytes()?,
+        )))
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::Utc;
+
+use crate::commands::prelude::*;
+
+use super::{NO_EXPIRATION, NO_FIELD, next_fields, no_fields};
+
+struct PTimeToLive {
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl PTimeToLive {
+    pub fn new(key: Vec<u8>, fields: Vec<Vec<u8>>) -> Self {
+        Self { key, fields }
+    }
+}
+
+impl ApplyToStore for PTimeToLive {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(hash)) => Ok(Response::Array(
+                self.fields
+                    .iter()
+                    .map(|field| {
+                        Response::I64(if hash.contains_key(field) {
+                            hash.expires(field).map_or(NO_EXPIRATION, |at| {
+                                at.signed_duration_since(Utc::now()).num_milliseconds()
+                            })
+                        } else {
+                            NO_FIELD
+                        })
+                    })
+                    .collect(),
+            )),
+            IfKindResult::NotSet => Ok(no_fields(&self.fields)),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct PTimeToLiveParser {}
+
+impl PTimeToLiveParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PTimeToLiveParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        Ok(Box::new(PTimeToLive::new(key, next_fields(input)?)))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::commands::prelude::*;
+
+struct Values {
+    key: Vec<u8>,
+}
+
+impl Values {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self { key }
+    }
+}
+
+impl ApplyToStore for Values {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::Hash, &self.key) {
+            IfKindResult::Matched(Value::Hash(fields)) => {
+                Ok(Response::List(fields.values().cloned().collect()))
+            }
+            IfKindResult::NotSet => Ok(Response::EmptyList),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct ValuesParser {}
+
+impl ValuesParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ValuesParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Values::new(input.next_bytes()?)))
+    }
+}
@@ -1,5 +1,9 @@
 use crate::network::Error;
+use crate::storage::{IfKindResult, Kind, Store, Value};
 
+pub mod blocking_move;
+pub mod blocking_pop;
+pub mod blocking_pop_multiple;
 pub mod index;
 pub mod insert;
 pub mod left_pop;
@@ -30,3 +34,19 @@ fn parse_end(tokey: &str) -> Result<End, Error> {
         _ => Err(Error::Syntax),
     }
 }
+
+/// Returns the first of the keys holding a list that isn't empty,
+/// looking them up without counting them as changed.
+fn first_nonempty<'k>(
+    store: &mut Store,
+    keys: &'k [Vec<u8>],
+) -> Result<Option<&'k Vec<u8>>, Error> {
+    for key in keys {
+        match store.get_if_kind(Kind::List, key) {
+            IfKindResult::Matched(Value::List(list)) if !list.is_empty() => return Ok(Some(key)),
+            IfKindResult::NotMatched => return Err(Error::WrongType),
+            _ => continue,
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use crate::commands::prelude::*;
+
+use super::{End, parse_end};
+use super::r#move::Move;
+
+/// BlockingMove moves an item between lists like LMOVE,
+/// blocking until the source is pushed to when it's empty.
+struct BlockingMove {
+    inner: Move,
+    /// the source list to wait for
+    keys: Vec<Vec<u8>>,
+    /// the LMOVE that replays the move without blocking
+    args: Vec<Vec<u8>>,
+    timeout: Option<Duration>,
+}
+
+impl BlockingMove {
+    pub fn new(
+        from: Vec<u8>,
+        from_end: End,
+        to: Vec<u8>,
+        to_end: End,
+        timeout: Option<Duration>,
+    ) -> Self {
+        let args = vec![
+            b"LMOVE".to_vec(),
+            from.clone(),
+            to.clone(),
+            end_name(from_end),
+            end_name(to_end),
+        ];
+
+        Self {
+            inner: Move::new(from.clone(), from_end, to, to_end),
+            keys: vec![from],
+            args,
+            timeout,
+        }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match self.inner.apply(store)? {
+            Response::Null => Ok(Response::Block(self.timeout)),
+            response => Ok(response),
+        }
+    }
+}
+
+impl Apply for BlockingMove {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        context.propagate(self.args.clone());
+        self.apply(context.store())
+    }
+
+    fn timeout<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        context.propagate_nothing();
+        Ok(Response::NullArray)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+
+    fn blocking_keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+}
+
+fn end_name(end: End) -> Vec<u8> {
+    match end {
+        End::Left => b"LEFT".to_vec(),
+        End::Right => b"RIGHT".to_vec(),
+    }
+}
+
+pub struct BlockingMoveParser {}
+
+impl BlockingMoveParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BlockingMoveParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let from = input.next_bytes()?;
+        let to = input.next_bytes()?;
+        let from_end = parse_end(&input.next_token()?)?;
+        let to_end = parse_end(&input.next_token()?)?;
+
+        Ok(Box::new(BlockingMove::new(
+            from,
+            from_end,
+            to,
+            to_end,
+            input.next_timeout()?,
+        )))
+    }
+}
+
+/// Parses BRPOPLPUSH, the older form of BLMOVE that moves from the right to the left.
+pub struct BlockingRightPopLeftPushParser {}
+
+impl BlockingRightPopLeftPushParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BlockingRightPopLeftPushParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let from = input.next_bytes()?;
+        let to = input.next_bytes()?;
+
+        Ok(Box::new(BlockingMove::new(
+            from,
+            End::Right,
+            to,
+            End::Left,
+            input.next_timeout()?,
+        )))
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use crate::commands::prelude::*;
+
+use super::{End, first_nonempty};
+
+/// BlockingPop pops an item from the first of the lists that isn't empty,
+/// blocking until one of them is pushed to when they are all empty.
+struct BlockingPop {
+    keys: Vec<Vec<u8>>,
+    end: End,
+    timeout: Option<Duration>,
+}
+
+impl BlockingPop {
+    pub fn new(keys: Vec<Vec<u8>>, end: End, timeout: Option<Duration>) -> Self {
+        Self { keys, end, timeout }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        // look before taking the list to change so retries leave the keys alone
+        let Some(key) = first_nonempty(store, &self.keys)? else {
+            return Ok(Response::Block(self.timeout));
+        };
+        let IfKindResult::Matched(Value::List(list)) = store.get_mut_if_kind(Kind::List, key)
+        else {
+            unreachable!("checked");
+        };
+
+        let value = match self.end {
+            End::Left => list.remove(0),
+            End::Right => list.pop().expect("not empty"),
+        };
+        if list.is_empty() {
+            store.remove(key);
+        }
+        Ok(Response::List(vec![key.clone(), value]))
+    }
+}
+
+impl Apply for BlockingPop {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let propagated = context.propagated;
+        let response = self.apply(&mut context.databases[context.session.db()])?;
+
+        // replay pops from the list that was served without blocking
+        if let Response::List(popped) = &response {
+            let command = match self.end {
+                End::Left => b"LPOP".to_vec(),
+                End::Right => b"RPOP".to_vec(),
+            };
+            *propagated = Some(vec![vec![command, popped[0].clone()]]);
+        }
+        Ok(response)
+    }
+
+    fn timeout<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        context.propagate_nothing();
+        Ok(Response::NullArray)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+
+    fn blocking_keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+}
+
+fn try_parse(command: &str, end: End, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+    let keys = input.next_multiple(command, "key", input.len().saturating_sub(1).max(1))?;
+    let timeout = input.next_timeout()?;
+
+    Ok(Box::new(BlockingPop::new(keys, end, timeout)))
+}
+
+pub struct BlockingLeftPopParser {}
+
+impl BlockingLeftPopParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BlockingLeftPopParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        try_parse("BLPOP", End::Left, input)
+    }
+}
+
+pub struct BlockingRightPopParser {}
+
+impl BlockingRightPopParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BlockingRightPopParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        try_parse("BRPOP", End::Right, input)
+    }
+}
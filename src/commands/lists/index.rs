@@ -1,17 +1,17 @@
 use crate::commands::prelude::*;
 
 struct Index {
-    key: String,
+    key: Vec<u8>,
     index: i64,
 }
 
 impl Index {
-    pub fn new(key: String, index: i64) -> Self {
+    pub fn new(key: Vec<u8>, index: i64) -> Self {
         Self { key, index }
     }
 }
 
-impl Apply for Index {
+impl ApplyToStore for Index {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(list)) => {
@@ -39,9 +39,6 @@ impl IndexParser {
 
 impl TryParse for IndexParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Index::new(
-            input.next_string()?,
-            input.next_i64()?,
-        )))
+        Ok(Box::new(Index::new(input.next_bytes()?, input.next_i64()?)))
     }
 }
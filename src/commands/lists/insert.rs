@@ -1,14 +1,14 @@
 use crate::commands::prelude::*;
 
 pub struct Insert {
-    key: String,
+    key: Vec<u8>,
     before: bool,
-    pivot: String,
-    value: String,
+    pivot: Vec<u8>,
+    value: Vec<u8>,
 }
 
 impl Insert {
-    pub fn new(key: String, before: bool, pivot: String, value: String) -> Self {
+    pub fn new(key: Vec<u8>, before: bool, pivot: Vec<u8>, value: Vec<u8>) -> Self {
         Self {
             key,
             before,
@@ -18,7 +18,7 @@ impl Insert {
     }
 }
 
-impl Apply for Insert {
+impl ApplyToStore for Insert {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(ref mut list)) => {
@@ -37,6 +37,10 @@ impl Apply for Insert {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct InsertParser {}
@@ -49,14 +53,14 @@ impl InsertParser {
 
 impl TryParse for InsertParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
         let before = match input.next_token()?.as_str() {
             "BEFORE" => true,
             "AFTER" => false,
             _ => return Err(Error::Syntax),
         };
-        let pivot = input.next_string()?;
-        let value = input.next_string()?;
+        let pivot = input.next_bytes()?;
+        let value = input.next_bytes()?;
 
         Ok(Box::new(Insert::new(key, before, pivot, value)))
     }
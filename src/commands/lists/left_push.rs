@@ -1,13 +1,13 @@
 use crate::commands::prelude::*;
 
 pub struct LeftPush {
-    key: String,
-    values: Vec<String>,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
     create: bool,
 }
 
 impl LeftPush {
-    pub fn new(key: String, values: Vec<String>, create: bool) -> Self {
+    pub fn new(key: Vec<u8>, values: Vec<Vec<u8>>, create: bool) -> Self {
         Self {
             key,
             values,
@@ -16,7 +16,7 @@ impl LeftPush {
     }
 }
 
-impl Apply for LeftPush {
+impl ApplyToStore for LeftPush {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(ref mut list)) => {
@@ -37,6 +37,10 @@ impl Apply for LeftPush {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct LeftPushParser {}
@@ -50,7 +54,7 @@ impl LeftPushParser {
 impl TryParse for LeftPushParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(LeftPush::new(
-            input.next_string()?,
+            input.next_bytes()?,
             input.rest()?,
             true,
         )))
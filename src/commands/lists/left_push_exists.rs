@@ -13,7 +13,7 @@ impl LeftPushExistsParser {
 impl TryParse for LeftPushExistsParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(LeftPush::new(
-            input.next_string()?,
+            input.next_bytes()?,
             input.rest()?,
             false,
         )))
@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Len {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Len {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for Len {
+impl ApplyToStore for Len {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(list)) => Ok(Response::Usize(list.len())),
@@ -30,6 +30,6 @@ impl LenParser {
 
 impl TryParse for LenParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Len::new(input.next_string()?)))
+        Ok(Box::new(Len::new(input.next_bytes()?)))
     }
 }
@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
-use super::{End, parse_end};
+use super::{End, first_nonempty, parse_end};
 
-struct Move {
-    from: String,
+pub(super) struct Move {
+    from: Vec<u8>,
     from_end: End,
-    to: String,
+    to: Vec<u8>,
     to_end: End,
 }
 
 impl Move {
-    pub fn new(from: String, from_end: End, to: String, to_end: End) -> Self {
+    pub fn new(from: Vec<u8>, from_end: End, to: Vec<u8>, to_end: End) -> Self {
         Self {
             from,
             from_end,
@@ -20,18 +20,19 @@ impl Move {
     }
 }
 
-impl Apply for Move {
+impl ApplyToStore for Move {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         if store.is_not_kind(&self.to, Kind::List) {
             return Err(Error::WrongType);
         }
 
+        // look before taking the list to change so BLMOVE retries leave the key alone
+        if first_nonempty(store, std::slice::from_ref(&self.from))?.is_none() {
+            return Ok(Response::Null);
+        }
+
         let value = match store.get_mut_if_kind(Kind::List, &self.from) {
             IfKindResult::Matched(Value::List(list)) => {
-                if list.is_empty() {
-                    return Ok(Response::Null);
-                }
-
                 if self.from == self.to {
                     return Ok(Response::BulkString(
                         if list.len() == 1 {
@@ -76,6 +77,10 @@ impl Apply for Move {
 
         Ok(Response::BulkString(value))
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct MoveParser {}
@@ -88,8 +93,8 @@ impl MoveParser {
 
 impl TryParse for MoveParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let from = input.next_string()?;
-        let to = input.next_string()?;
+        let from = input.next_bytes()?;
+        let to = input.next_bytes()?;
 
         Ok(Box::new(Move::new(
             from,
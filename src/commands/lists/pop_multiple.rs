@@ -1,54 +1,63 @@
 use crate::commands::prelude::*;
 
-use super::{End, parse_end};
+use super::{End, first_nonempty, parse_end};
 
-struct PopMultiple {
-    keys: Vec<String>,
+pub(super) struct PopMultiple {
+    keys: Vec<Vec<u8>>,
     end: End,
     count: usize,
 }
 
 impl PopMultiple {
-    pub fn new(keys: Vec<String>, end: End) -> Self {
+    pub fn new(keys: Vec<Vec<u8>>, end: End) -> Self {
         Self {
             keys,
             end,
             count: 1,
         }
     }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    /// Returns the command that pops from the same end of a single list.
+    pub fn pop_command(&self) -> Vec<u8> {
+        match self.end {
+            End::Left => b"LPOP".to_vec(),
+            End::Right => b"RPOP".to_vec(),
+        }
+    }
 }
 
-impl Apply for PopMultiple {
+impl ApplyToStore for PopMultiple {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
-        for key in &self.keys {
-            match store.get_mut_if_kind(Kind::List, key) {
-                IfKindResult::Matched(Value::List(list)) => {
-                    if list.is_empty() {
-                        store.remove(key);
-                        continue;
-                    }
-
-                    if self.count >= list.len() {
-                        return Ok(Response::ValueList(vec![
-                            Value::from(key.clone()),
-                            store.remove(key).expect("found"),
-                        ]));
-                    } else {
-                        return Ok(Response::ValueList(vec![
-                            Value::from(key.clone()),
-                            Value::from(match self.end {
-                                End::Left => list.drain(0..self.count).collect(),
-                                End::Right => list.split_off(list.len() - self.count),
-                            }),
-                        ]));
-                    }
-                }
-                IfKindResult::NotSet => continue,
-                _ => return Err(Error::WrongType),
-            }
+        let Some(key) = first_nonempty(store, &self.keys)? else {
+            return Ok(Response::NullArray);
+        };
+        let IfKindResult::Matched(Value::List(list)) = store.get_mut_if_kind(Kind::List, key)
+        else {
+            unreachable!("checked");
+        };
+
+        if self.count >= list.len() {
+            Ok(Response::ValueList(vec![
+                Value::from(key.clone()),
+                store.remove(key).expect("found"),
+            ]))
+        } else {
+            Ok(Response::ValueList(vec![
+                Value::from(key.clone()),
+                Value::from(match self.end {
+                    End::Left => list.drain(0..self.count).collect(),
+                    End::Right => list.split_off(list.len() - self.count),
+                }),
+            ]))
         }
+    }
 
-        Ok(Response::Null)
+    fn is_write(&self) -> bool {
+        true
     }
 }
 
@@ -73,19 +82,23 @@ impl PopMultipleParser {
         position.count = count;
         Ok(())
     }
-}
 
-impl TryParse for PopMultipleParser {
-    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+    /// Parses the keys, end and options shared with BLMPOP.
+    pub(super) fn try_parse_pop(
+        &self,
+        command: &str,
+        input: &mut Input,
+    ) -> Result<PopMultiple, Error> {
         let key_count = input.next_u64_min(1)? as usize;
-        let keys = input.next_strings("LMPOP", "key", key_count)?;
+        let keys = input.next_multiple(command, "key", key_count)?;
         let end = parse_end(&input.next_token()?)?;
 
-        Ok(Box::new(parse_options(
-            "LMPOP",
-            &self.options,
-            input,
-            PopMultiple::new(keys, end),
-        )?))
+        parse_options(command, &self.options, input, PopMultiple::new(keys, end))
+    }
+}
+
+impl TryParse for PopMultipleParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(self.try_parse_pop("LMPOP", input)?))
     }
 }
@@ -3,15 +3,15 @@ use itertools::Itertools;
 use crate::commands::prelude::*;
 
 pub struct Position {
-    key: String,
-    value: String,
+    key: Vec<u8>,
+    value: Vec<u8>,
     rank: i64,
     count: usize,
     max_len: usize,
 }
 
 impl Position {
-    pub fn new(key: String, value: String) -> Self {
+    pub fn new(key: Vec<u8>, value: Vec<u8>) -> Self {
         Self {
             key,
             value,
@@ -22,7 +22,7 @@ impl Position {
     }
 }
 
-impl Apply for Position {
+impl ApplyToStore for Position {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(list)) => {
@@ -48,18 +48,18 @@ impl Apply for Position {
                     }
                 } else {
                     if self.rank > 0 {
-                        Ok(Response::ValueList(
+                        Ok(Response::Array(
                             list.iter()
                                 .enumerate()
                                 .take(self.max_len)
                                 .filter(|(_, v)| **v == self.value)
                                 .skip(self.rank as usize - 1)
                                 .take(self.count)
-                                .map(|(index, _)| Value::from(index))
+                                .map(|(index, _)| Response::Usize(index))
                                 .collect_vec(),
                         ))
                     } else {
-                        Ok(Response::ValueList(
+                        Ok(Response::Array(
                             list.iter()
                                 .rev()
                                 .enumerate()
@@ -67,7 +67,7 @@ impl Apply for Position {
                                 .filter(|(_, v)| **v == self.value)
                                 .skip(-self.rank as usize - 1)
                                 .take(self.count)
-                                .map(|(index, _)| Value::from(list.len() - 1 - index))
+                                .map(|(index, _)| Response::Usize(list.len() - 1 - index))
                                 .collect_vec(),
                         ))
                     }
@@ -122,8 +122,8 @@ impl PositionParser {
 
 impl TryParse for PositionParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
-        let value = input.next_string()?;
+        let key = input.next_bytes()?;
+        let value = input.next_bytes()?;
 
         Ok(Box::new(parse_options(
             "LPOS",
@@ -1,18 +1,18 @@
 use crate::commands::prelude::*;
 
 struct Range {
-    key: String,
+    key: Vec<u8>,
     start: i64,
     end: i64,
 }
 
 impl Range {
-    pub fn new(key: String, start: i64, end: i64) -> Self {
+    pub fn new(key: Vec<u8>, start: i64, end: i64) -> Self {
         Self { key, start, end }
     }
 }
 
-impl Apply for Range {
+impl ApplyToStore for Range {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(list)) => {
@@ -41,7 +41,7 @@ impl RangeParser {
 impl TryParse for RangeParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(Range::new(
-            input.next_string()?,
+            input.next_bytes()?,
             input.next_i64()?,
             input.next_i64()?,
         )))
@@ -1,18 +1,18 @@
 use crate::commands::prelude::*;
 
 pub struct Remove {
-    key: String,
+    key: Vec<u8>,
     count: i64,
-    value: String,
+    value: Vec<u8>,
 }
 
 impl Remove {
-    pub fn new(key: String, count: i64, value: String) -> Self {
+    pub fn new(key: Vec<u8>, count: i64, value: Vec<u8>) -> Self {
         Self { key, count, value }
     }
 }
 
-impl Apply for Remove {
+impl ApplyToStore for Remove {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(list)) => {
@@ -56,6 +56,10 @@ impl Apply for Remove {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct RemoveParser {}
@@ -68,9 +72,9 @@ impl RemoveParser {
 
 impl TryParse for RemoveParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
         let count = input.next_i64()?;
-        let value = input.next_string()?;
+        let value = input.next_bytes()?;
 
         Ok(Box::new(Remove::new(
             key,
@@ -1,17 +1,17 @@
 use crate::commands::prelude::*;
 
 pub struct RightPop {
-    key: String,
+    key: Vec<u8>,
     count: Option<usize>,
 }
 
 impl RightPop {
-    pub fn new(key: String, count: Option<usize>) -> Self {
+    pub fn new(key: Vec<u8>, count: Option<usize>) -> Self {
         Self { key, count }
     }
 }
 
-impl Apply for RightPop {
+impl ApplyToStore for RightPop {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(list)) => match self.count {
@@ -38,10 +38,15 @@ impl Apply for RightPop {
                     }
                 }
             },
+            IfKindResult::NotSet if self.count.is_some() => Ok(Response::NullArray),
             IfKindResult::NotSet => Ok(Response::Null),
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct RightPopParser {}
@@ -54,7 +59,7 @@ impl RightPopParser {
 
 impl TryParse for RightPopParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
         let count = if input.has_next() {
             Some(input.next_usize()?)
         } else {
@@ -1,13 +1,13 @@
 use crate::commands::prelude::*;
 
 pub struct RightPush {
-    key: String,
-    values: Vec<String>,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
     create: bool,
 }
 
 impl RightPush {
-    pub fn new(key: String, values: Vec<String>, create: bool) -> Self {
+    pub fn new(key: Vec<u8>, values: Vec<Vec<u8>>, create: bool) -> Self {
         Self {
             key,
             values,
@@ -16,7 +16,7 @@ impl RightPush {
     }
 }
 
-impl Apply for RightPush {
+impl ApplyToStore for RightPush {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(ref mut list)) => {
@@ -33,6 +33,10 @@ impl Apply for RightPush {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct RightPushParser {}
@@ -46,7 +50,7 @@ impl RightPushParser {
 impl TryParse for RightPushParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(RightPush::new(
-            input.next_string()?,
+            input.next_bytes()?,
             input.rest()?,
             true,
         )))
@@ -13,7 +13,7 @@ impl RightPushExistsParser {
 impl TryParse for RightPushExistsParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(RightPush::new(
-            input.next_string()?,
+            input.next_bytes()?,
             input.rest()?,
             false,
         )))
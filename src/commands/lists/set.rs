@@ -1,18 +1,18 @@
 use crate::commands::prelude::*;
 
 pub struct Set {
-    key: String,
+    key: Vec<u8>,
     index: i64,
-    value: String,
+    value: Vec<u8>,
 }
 
 impl Set {
-    pub fn new(key: String, index: i64, value: String) -> Self {
+    pub fn new(key: Vec<u8>, index: i64, value: Vec<u8>) -> Self {
         Self { key, index, value }
     }
 }
 
-impl Apply for Set {
+impl ApplyToStore for Set {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(list)) => {
@@ -34,6 +34,10 @@ impl Apply for Set {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct SetParser {}
@@ -46,9 +50,9 @@ impl SetParser {
 
 impl TryParse for SetParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
         let index = input.next_i64()?;
-        let value = input.next_string()?;
+        let value = input.next_bytes()?;
 
         Ok(Box::new(Set::new(key, index, value)))
     }
@@ -1,18 +1,18 @@
 use crate::commands::prelude::*;
 
 struct Trim {
-    key: String,
+    key: Vec<u8>,
     start: i64,
     end: i64,
 }
 
 impl Trim {
-    pub fn new(key: String, start: i64, end: i64) -> Self {
+    pub fn new(key: Vec<u8>, start: i64, end: i64) -> Self {
         Self { key, start, end }
     }
 }
 
-impl Apply for Trim {
+impl ApplyToStore for Trim {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::List, &self.key) {
             IfKindResult::Matched(Value::List(list)) => {
@@ -30,6 +30,10 @@ impl Apply for Trim {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct TrimParser {}
@@ -43,7 +47,7 @@ impl TrimParser {
 impl TryParse for TrimParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(Trim::new(
-            input.next_string()?,
+            input.next_bytes()?,
             input.next_i64()?,
             input.next_i64()?,
         )))
@@ -0,0 +1,14 @@
+pub mod pattern_subscribe;
+pub mod pattern_unsubscribe;
+pub mod pub_sub;
+pub mod publish;
+pub mod pubsub_channels;
+pub mod pubsub_num_pat;
+pub mod pubsub_num_sub;
+pub mod pubsub_shard_channels;
+pub mod pubsub_shard_num_sub;
+pub mod shard_publish;
+pub mod shard_subscribe;
+pub mod shard_unsubscribe;
+pub mod subscribe;
+pub mod unsubscribe;
@@ -0,0 +1,58 @@
+use crate::commands::prelude::*;
+
+struct PatternSubscribe {
+    patterns: Vec<Vec<u8>>,
+}
+
+impl PatternSubscribe {
+    pub fn new(patterns: Vec<Vec<u8>>) -> Self {
+        Self { patterns }
+    }
+}
+
+impl Apply for PatternSubscribe {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+
+        Ok(Response::Multiple(
+            self.patterns
+                .iter()
+                .map(|pattern| {
+                    if session.add_pattern(pattern) {
+                        context.pubsub.pattern_subscribe(session.id(), pattern);
+                    }
+                    Response::Push(vec![
+                        Response::BulkStringRef(b"psubscribe"),
+                        Response::BulkString(pattern.clone()),
+                        Response::Usize(session.subscriptions()),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+
+    fn is_subscriber(&self) -> bool {
+        true
+    }
+}
+
+pub struct PatternSubscribeParser {}
+
+impl PatternSubscribeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PatternSubscribeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if input.has_next() {
+            Ok(Box::new(PatternSubscribe::new(input.rest()?)))
+        } else {
+            Err(Error::MissingArgument(
+                "PSUBSCRIBE".to_string(),
+                "pattern".to_string(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::commands::prelude::*;
+
+struct PatternUnsubscribe {
+    /// unsubscribes from every pattern when empty
+    patterns: Vec<Vec<u8>>,
+}
+
+impl PatternUnsubscribe {
+    pub fn new(patterns: Vec<Vec<u8>>) -> Self {
+        Self { patterns }
+    }
+}
+
+impl Apply for PatternUnsubscribe {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+        let patterns = if self.patterns.is_empty() {
+            session.patterns().iter().cloned().collect()
+        } else {
+            self.patterns.clone()
+        };
+
+        if patterns.is_empty() {
+            return Ok(Response::Push(vec![
+                Response::BulkStringRef(b"punsubscribe"),
+                Response::Null,
+                Response::Usize(session.subscriptions()),
+            ]));
+        }
+        Ok(Response::Multiple(
+            patterns
+                .into_iter()
+                .map(|pattern| {
+                    if session.remove_pattern(&pattern) {
+                        context.pubsub.pattern_unsubscribe(session.id(), &pattern);
+                    }
+                    Response::Push(vec![
+                        Response::BulkStringRef(b"punsubscribe"),
+                        Response::BulkString(pattern),
+                        Response::Usize(session.subscriptions()),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+
+    fn is_subscriber(&self) -> bool {
+        true
+    }
+}
+
+pub struct PatternUnsubscribeParser {}
+
+impl PatternUnsubscribeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PatternUnsubscribeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(PatternUnsubscribe::new(input.rest()?)))
+    }
+}
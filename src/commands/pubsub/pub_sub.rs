@@ -0,0 +1,42 @@
+use crate::commands::prelude::*;
+
+use super::pubsub_channels::PubSubChannelsParser;
+use super::pubsub_num_pat::PubSubNumPatParser;
+use super::pubsub_num_sub::PubSubNumSubParser;
+use super::pubsub_shard_channels::PubSubShardChannelsParser;
+use super::pubsub_shard_num_sub::PubSubShardNumSubParser;
+
+pub struct PubSubParser {
+    channels: PubSubChannelsParser,
+    num_pat: PubSubNumPatParser,
+    num_sub: PubSubNumSubParser,
+    shard_channels: PubSubShardChannelsParser,
+    shard_num_sub: PubSubShardNumSubParser,
+}
+
+impl PubSubParser {
+    pub fn new() -> Self {
+        Self {
+            channels: PubSubChannelsParser::new(),
+            num_pat: PubSubNumPatParser::new(),
+            num_sub: PubSubNumSubParser::new(),
+            shard_channels: PubSubShardChannelsParser::new(),
+            shard_num_sub: PubSubShardNumSubParser::new(),
+        }
+    }
+}
+
+impl TryParse for PubSubParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let subcommand = input.next_token()?;
+
+        match subcommand.as_str() {
+            "CHANNELS" => self.channels.try_parse(input),
+            "NUMPAT" => self.num_pat.try_parse(input),
+            "NUMSUB" => self.num_sub.try_parse(input),
+            "SHARDCHANNELS" => self.shard_channels.try_parse(input),
+            "SHARDNUMSUB" => self.shard_num_sub.try_parse(input),
+            _ => Err(Error::UnknownSubcommand("PUBSUB".to_string(), subcommand)),
+        }
+    }
+}
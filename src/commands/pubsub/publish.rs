@@ -0,0 +1,37 @@
+use crate::commands::prelude::*;
+
+struct Publish {
+    channel: Vec<u8>,
+    message: Vec<u8>,
+}
+
+impl Publish {
+    pub fn new(channel: Vec<u8>, message: Vec<u8>) -> Self {
+        Self { channel, message }
+    }
+}
+
+impl Apply for Publish {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        Ok(Response::Usize(
+            context.pubsub.publish(&self.channel, &self.message),
+        ))
+    }
+}
+
+pub struct PublishParser {}
+
+impl PublishParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PublishParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Publish::new(
+            input.next_bytes()?,
+            input.next_bytes()?,
+        )))
+    }
+}
@@ -0,0 +1,45 @@
+use crate::commands::prelude::*;
+use crate::storage::Glob;
+
+struct PubSubChannels {
+    pattern: Option<Vec<u8>>,
+}
+
+impl PubSubChannels {
+    pub fn new(pattern: Option<Vec<u8>>) -> Self {
+        Self { pattern }
+    }
+}
+
+impl Apply for PubSubChannels {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let glob = self.pattern.as_ref().map(|pattern| Glob::new(pattern));
+
+        Ok(Response::List(
+            context
+                .pubsub
+                .channels()
+                .filter(|channel| glob.as_ref().is_none_or(|glob| glob.matches(channel)))
+                .cloned()
+                .collect(),
+        ))
+    }
+}
+
+pub struct PubSubChannelsParser {}
+
+impl PubSubChannelsParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PubSubChannelsParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if input.has_next() {
+            Ok(Box::new(PubSubChannels::new(Some(input.next_bytes()?))))
+        } else {
+            Ok(Box::new(PubSubChannels::new(None)))
+        }
+    }
+}
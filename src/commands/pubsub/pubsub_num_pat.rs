@@ -0,0 +1,29 @@
+use crate::commands::prelude::*;
+
+struct PubSubNumPat {}
+
+impl PubSubNumPat {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for PubSubNumPat {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        Ok(Response::Usize(context.pubsub.pattern_count()))
+    }
+}
+
+pub struct PubSubNumPatParser {}
+
+impl PubSubNumPatParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PubSubNumPatParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(PubSubNumPat::new()))
+    }
+}
@@ -0,0 +1,42 @@
+use crate::commands::prelude::*;
+
+struct PubSubNumSub {
+    channels: Vec<Vec<u8>>,
+}
+
+impl PubSubNumSub {
+    pub fn new(channels: Vec<Vec<u8>>) -> Self {
+        Self { channels }
+    }
+}
+
+impl Apply for PubSubNumSub {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        // a flat array of channels and counts even in RESP3
+        Ok(Response::Array(
+            self.channels
+                .iter()
+                .flat_map(|channel| {
+                    [
+                        Response::BulkString(channel.clone()),
+                        Response::Usize(context.pubsub.subscribers(channel)),
+                    ]
+                })
+                .collect(),
+        ))
+    }
+}
+
+pub struct PubSubNumSubParser {}
+
+impl PubSubNumSubParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PubSubNumSubParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(PubSubNumSub::new(input.rest()?)))
+    }
+}
@@ -0,0 +1,47 @@
+use crate::commands::prelude::*;
+use crate::storage::Glob;
+
+struct PubSubShardChannels {
+    pattern: Option<Vec<u8>>,
+}
+
+impl PubSubShardChannels {
+    pub fn new(pattern: Option<Vec<u8>>) -> Self {
+        Self { pattern }
+    }
+}
+
+impl Apply for PubSubShardChannels {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let glob = self.pattern.as_ref().map(|pattern| Glob::new(pattern));
+
+        Ok(Response::List(
+            context
+                .pubsub
+                .shard_channels()
+                .filter(|channel| glob.as_ref().is_none_or(|glob| glob.matches(channel)))
+                .cloned()
+                .collect(),
+        ))
+    }
+}
+
+pub struct PubSubShardChannelsParser {}
+
+impl PubSubShardChannelsParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PubSubShardChannelsParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if input.has_next() {
+            Ok(Box::new(PubSubShardChannels::new(Some(
+                input.next_bytes()?,
+            ))))
+        } else {
+            Ok(Box::new(PubSubShardChannels::new(None)))
+        }
+    }
+}
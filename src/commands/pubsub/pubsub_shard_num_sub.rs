@@ -0,0 +1,42 @@
+use crate::commands::prelude::*;
+
+struct PubSubShardNumSub {
+    channels: Vec<Vec<u8>>,
+}
+
+impl PubSubShardNumSub {
+    pub fn new(channels: Vec<Vec<u8>>) -> Self {
+        Self { channels }
+    }
+}
+
+impl Apply for PubSubShardNumSub {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        // a flat array of channels and counts even in RESP3
+        Ok(Response::Array(
+            self.channels
+                .iter()
+                .flat_map(|channel| {
+                    [
+                        Response::BulkString(channel.clone()),
+                        Response::Usize(context.pubsub.shard_subscribers(channel)),
+                    ]
+                })
+                .collect(),
+        ))
+    }
+}
+
+pub struct PubSubShardNumSubParser {}
+
+impl PubSubShardNumSubParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PubSubShardNumSubParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(PubSubShardNumSub::new(input.rest()?)))
+    }
+}
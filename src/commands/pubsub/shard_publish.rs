@@ -0,0 +1,37 @@
+use crate::commands::prelude::*;
+
+struct ShardPublish {
+    channel: Vec<u8>,
+    message: Vec<u8>,
+}
+
+impl ShardPublish {
+    pub fn new(channel: Vec<u8>, message: Vec<u8>) -> Self {
+        Self { channel, message }
+    }
+}
+
+impl Apply for ShardPublish {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        Ok(Response::Usize(
+            context.pubsub.shard_publish(&self.channel, &self.message),
+        ))
+    }
+}
+
+pub struct ShardPublishParser {}
+
+impl ShardPublishParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ShardPublishParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(ShardPublish::new(
+            input.next_bytes()?,
+            input.next_bytes()?,
+        )))
+    }
+}
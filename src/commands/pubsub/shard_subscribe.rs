@@ -0,0 +1,58 @@
+use crate::commands::prelude::*;
+
+struct ShardSubscribe {
+    channels: Vec<Vec<u8>>,
+}
+
+impl ShardSubscribe {
+    pub fn new(channels: Vec<Vec<u8>>) -> Self {
+        Self { channels }
+    }
+}
+
+impl Apply for ShardSubscribe {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+
+        Ok(Response::Multiple(
+            self.channels
+                .iter()
+                .map(|channel| {
+                    if session.add_shard_channel(channel) {
+                        context.pubsub.shard_subscribe(session.id(), channel);
+                    }
+                    Response::Push(vec![
+                        Response::BulkStringRef(b"ssubscribe"),
+                        Response::BulkString(channel.clone()),
+                        Response::Usize(session.shard_channels().len()),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+
+    fn is_subscriber(&self) -> bool {
+        true
+    }
+}
+
+pub struct ShardSubscribeParser {}
+
+impl ShardSubscribeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ShardSubscribeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if input.has_next() {
+            Ok(Box::new(ShardSubscribe::new(input.rest()?)))
+        } else {
+            Err(Error::MissingArgument(
+                "SSUBSCRIBE".to_string(),
+                "channel".to_string(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::commands::prelude::*;
+
+struct ShardUnsubscribe {
+    /// unsubscribes from every shard channel when empty
+    channels: Vec<Vec<u8>>,
+}
+
+impl ShardUnsubscribe {
+    pub fn new(channels: Vec<Vec<u8>>) -> Self {
+        Self { channels }
+    }
+}
+
+impl Apply for ShardUnsubscribe {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+        let channels = if self.channels.is_empty() {
+            session.shard_channels().iter().cloned().collect()
+        } else {
+            self.channels.clone()
+        };
+
+        if channels.is_empty() {
+            return Ok(Response::Push(vec![
+                Response::BulkStringRef(b"sunsubscribe"),
+                Response::Null,
+                Response::Usize(session.shard_channels().len()),
+            ]));
+        }
+        Ok(Response::Multiple(
+            channels
+                .into_iter()
+                .map(|channel| {
+                    if session.remove_shard_channel(&channel) {
+                        context.pubsub.shard_unsubscribe(session.id(), &channel);
+                    }
+                    Response::Push(vec![
+                        Response::BulkStringRef(b"sunsubscribe"),
+                        Response::BulkString(channel),
+                        Response::Usize(session.shard_channels().len()),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+
+    fn is_subscriber(&self) -> bool {
+        true
+    }
+}
+
+pub struct ShardUnsubscribeParser {}
+
+impl ShardUnsubscribeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ShardUnsubscribeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(ShardUnsubscribe::new(input.rest()?)))
+    }
+}
@@ -0,0 +1,58 @@
+use crate::commands::prelude::*;
+
+struct Subscribe {
+    channels: Vec<Vec<u8>>,
+}
+
+impl Subscribe {
+    pub fn new(channels: Vec<Vec<u8>>) -> Self {
+        Self { channels }
+    }
+}
+
+impl Apply for Subscribe {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+
+        Ok(Response::Multiple(
+            self.channels
+                .iter()
+                .map(|channel| {
+                    if session.add_channel(channel) {
+                        context.pubsub.subscribe(session.id(), channel);
+                    }
+                    Response::Push(vec![
+                        Response::BulkStringRef(b"subscribe"),
+                        Response::BulkString(channel.clone()),
+                        Response::Usize(session.subscriptions()),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+
+    fn is_subscriber(&self) -> bool {
+        true
+    }
+}
+
+pub struct SubscribeParser {}
+
+impl SubscribeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for SubscribeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if input.has_next() {
+            Ok(Box::new(Subscribe::new(input.rest()?)))
+        } else {
+            Err(Error::MissingArgument(
+                "SUBSCRIBE".to_string(),
+                "channel".to_string(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::commands::prelude::*;
+
+struct Unsubscribe {
+    /// unsubscribes from every channel when empty
+    channels: Vec<Vec<u8>>,
+}
+
+impl Unsubscribe {
+    pub fn new(channels: Vec<Vec<u8>>) -> Self {
+        Self { channels }
+    }
+}
+
+impl Apply for Unsubscribe {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+        let channels = if self.channels.is_empty() {
+            session.channels().iter().cloned().collect()
+        } else {
+            self.channels.clone()
+        };
+
+        if channels.is_empty() {
+            return Ok(Response::Push(vec![
+                Response::BulkStringRef(b"unsubscribe"),
+                Response::Null,
+                Response::Usize(session.subscriptions()),
+            ]));
+        }
+        Ok(Response::Multiple(
+            channels
+                .into_iter()
+                .map(|channel| {
+                    if session.remove_channel(&channel) {
+                        context.pubsub.unsubscribe(session.id(), &channel);
+                    }
+                    Response::Push(vec![
+                        Response::BulkStringRef(b"unsubscribe"),
+                        Response::BulkString(channel),
+                        Response::Usize(session.subscriptions()),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+
+    fn is_subscriber(&self) -> bool {
+        true
+    }
+}
+
+pub struct UnsubscribeParser {}
+
+impl UnsubscribeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for UnsubscribeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Unsubscribe::new(input.rest()?)))
+    }
+}
@@ -1,2 +1,24 @@
+pub mod background_save;
 pub mod command;
+pub mod config;
+pub mod config_get;
+pub mod config_reset_stat;
+pub mod config_rewrite;
+pub mod config_set;
+pub mod db_size;
 pub mod echo;
+pub mod flush_all;
+pub mod flush_db;
+pub mod hello;
+pub mod info;
+pub mod last_save;
+pub mod ping;
+pub mod psync;
+pub mod replica_conf;
+pub mod replica_of;
+pub mod rewrite_aof;
+pub mod role;
+pub mod save;
+pub mod select;
+pub mod swap_db;
+pub mod wait;
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use crate::commands::prelude::*;
+
+struct BackgroundSave {
+    schedule: bool,
+}
+
+impl BackgroundSave {
+    pub fn new() -> Self {
+        Self { schedule: false }
+    }
+}
+
+impl Apply for BackgroundSave {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        if context.snapshots.in_progress() {
+            if self.schedule {
+                context.snapshots.schedule();
+                return Ok(Response::SimpleString(
+                    "Background saving scheduled".to_string(),
+                ));
+            }
+            return Err(Error::Raw(b"-ERR Background save already in progress\r\n"));
+        }
+
+        let path = PathBuf::from(&context.config.db_filename);
+        context
+            .snapshots
+            .start_background_save(path, context.databases);
+        Ok(Response::SimpleString(
+            "Background saving started".to_string(),
+        ))
+    }
+}
+
+pub struct BackgroundSaveParser {
+    options: Options<BackgroundSave>,
+}
+
+impl BackgroundSaveParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![(vec!["SCHEDULE"], BackgroundSaveParser::try_schedule)],
+        }
+    }
+
+    fn try_schedule(save: &mut BackgroundSave, _: &str, _: &mut Input) -> Result<(), Error> {
+        save.schedule = true;
+        Ok(())
+    }
+}
+
+impl TryParse for BackgroundSaveParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(parse_options(
+            "BGSAVE",
+            &self.options,
+            input,
+            BackgroundSave::new(),
+        )?))
+    }
+}
@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Command {
-    args: Vec<String>,
+    args: Vec<Vec<u8>>,
 }
 
 impl Command {
-    pub fn new(args: Vec<String>) -> Self {
+    pub fn new(args: Vec<Vec<u8>>) -> Self {
         Self { args }
     }
 }
 
-impl Apply for Command {
+impl ApplyToStore for Command {
     fn apply<'a>(&self, _store: &'a mut Store) -> Result<Response<'a>, Error> {
         if self.args.is_empty() {
             Ok(Response::EmptyList)
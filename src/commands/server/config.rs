@@ -0,0 +1,38 @@
+use crate::commands::prelude::*;
+
+use super::config_get::ConfigGetParser;
+use super::config_reset_stat::ConfigResetStatParser;
+use super::config_rewrite::ConfigRewriteParser;
+use super::config_set::ConfigSetParser;
+
+pub struct ConfigParser {
+    get: ConfigGetParser,
+    reset_stat: ConfigResetStatParser,
+    rewrite: ConfigRewriteParser,
+    set: ConfigSetParser,
+}
+
+impl ConfigParser {
+    pub fn new() -> Self {
+        Self {
+            get: ConfigGetParser::new(),
+            reset_stat: ConfigResetStatParser::new(),
+            rewrite: ConfigRewriteParser::new(),
+            set: ConfigSetParser::new(),
+        }
+    }
+}
+
+impl TryParse for ConfigParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let subcommand = input.next_token()?;
+
+        match subcommand.as_str() {
+            "GET" => self.get.try_parse(input),
+            "RESETSTAT" => self.reset_stat.try_parse(input),
+            "REWRITE" => self.rewrite.try_parse(input),
+            "SET" => self.set.try_parse(input),
+            _ => Err(Error::UnknownSubcommand("CONFIG".to_string(), subcommand)),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::commands::prelude::*;
+use crate::config::PARAMS;
+use crate::storage::Glob;
+
+struct ConfigGet {
+    patterns: Vec<Vec<u8>>,
+}
+
+impl ConfigGet {
+    pub fn new(patterns: Vec<Vec<u8>>) -> Self {
+        Self { patterns }
+    }
+}
+
+impl Apply for ConfigGet {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let globs = self
+            .patterns
+            .iter()
+            .map(|pattern| Glob::new_no_case(pattern))
+            .collect::<Vec<_>>();
+
+        Ok(Response::Map(
+            PARAMS
+                .iter()
+                .filter(|param| globs.iter().any(|glob| glob.matches(param.name.as_bytes())))
+                .map(|param| {
+                    (
+                        Response::BulkStringRef(param.name.as_bytes()),
+                        Response::BulkString(
+                            context
+                                .config
+                                .get(param.name)
+                                .unwrap_or_default()
+                                .into_bytes(),
+                        ),
+                    )
+                })
+                .collect(),
+        ))
+    }
+}
+
+pub struct ConfigGetParser {}
+
+impl ConfigGetParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ConfigGetParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if input.has_next() {
+            Ok(Box::new(ConfigGet::new(input.rest()?)))
+        } else {
+            Err(Error::MissingArgument(
+                "CONFIG GET".to_string(),
+                "parameter".to_string(),
+            ))
+        }
+    }
+}
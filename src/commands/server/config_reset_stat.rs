@@ -0,0 +1,30 @@
+use crate::commands::prelude::*;
+
+struct ConfigResetStat {}
+
+impl ConfigResetStat {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for ConfigResetStat {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        context.stats.reset();
+        Ok(Response::Ok)
+    }
+}
+
+pub struct ConfigResetStatParser {}
+
+impl ConfigResetStatParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ConfigResetStatParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(ConfigResetStat::new()))
+    }
+}
@@ -0,0 +1,32 @@
+use crate::commands::prelude::*;
+
+struct ConfigRewrite {}
+
+impl ConfigRewrite {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for ConfigRewrite {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        match context.config.rewrite() {
+            Ok(()) => Ok(Response::Ok),
+            Err(err) => Err(Error::String(format!("ERR {}", err))),
+        }
+    }
+}
+
+pub struct ConfigRewriteParser {}
+
+impl ConfigRewriteParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ConfigRewriteParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(ConfigRewrite::new()))
+    }
+}
@@ -0,0 +1,69 @@
+use crate::commands::prelude::*;
+
+struct ConfigSet {
+    params: Vec<(String, String)>,
+}
+
+impl ConfigSet {
+    pub fn new(params: Vec<(String, String)>) -> Self {
+        Self { params }
+    }
+}
+
+impl Apply for ConfigSet {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        // apply all parameters or none of them
+        let mut config = context.config.clone();
+
+        for (name, value) in &self.params {
+            if let Err(err) = config.set_live(name, value) {
+                return Err(Error::String(format!(
+                    "ERR CONFIG SET failed (possibly related to argument '{}') - {}",
+                    name, err
+                )));
+            }
+        }
+        if let Err(err) = config.apply() {
+            context.config.apply().ok();
+            return Err(Error::String(format!("ERR CONFIG SET failed - {}", err)));
+        }
+
+        *context.config = config;
+        Ok(Response::Ok)
+    }
+}
+
+pub struct ConfigSetParser {}
+
+impl ConfigSetParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ConfigSetParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if !input.has_next() || !input.len().is_multiple_of(2) {
+            return Err(Error::MissingArgument(
+                "CONFIG SET".to_string(),
+                "value".to_string(),
+            ));
+        }
+
+        let mut params = Vec::with_capacity(input.len() / 2);
+        while input.has_next() {
+            let name = input.next_string()?.to_lowercase();
+            let value = input.next_string()?;
+
+            if params.iter().any(|(param, _)| *param == name) {
+                return Err(Error::String(format!(
+                    "ERR CONFIG SET failed (possibly related to argument '{}') - duplicate parameter",
+                    name
+                )));
+            }
+            params.push((name, value));
+        }
+
+        Ok(Box::new(ConfigSet::new(params)))
+    }
+}
@@ -0,0 +1,29 @@
+use crate::commands::prelude::*;
+
+struct DbSize {}
+
+impl DbSize {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ApplyToStore for DbSize {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        Ok(Response::Usize(store.len()))
+    }
+}
+
+pub struct DbSizeParser {}
+
+impl DbSizeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for DbSizeParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(DbSize::new()))
+    }
+}
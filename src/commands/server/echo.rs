@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Echo {
-    message: String,
+    message: Vec<u8>,
 }
 
 impl Echo {
-    pub fn new(message: String) -> Self {
+    pub fn new(message: Vec<u8>) -> Self {
         Self { message }
     }
 }
 
-impl Apply for Echo {
+impl ApplyToStore for Echo {
     fn apply<'a>(&self, _store: &'a mut Store) -> Result<Response<'a>, Error> {
         Ok(Response::BulkString(self.message.clone()))
     }
@@ -26,6 +26,6 @@ impl EchoParser {
 
 impl TryParse for EchoParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Echo::new(input.next_string()?)))
+        Ok(Box::new(Echo::new(input.next_bytes()?)))
     }
 }
@@ -0,0 +1,61 @@
+use std::thread;
+
+use crate::commands::prelude::*;
+
+struct FlushAll {
+    lazy: bool,
+}
+
+impl FlushAll {
+    pub fn new() -> Self {
+        Self { lazy: false }
+    }
+}
+
+impl Apply for FlushAll {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let old = context
+            .databases
+            .iter_mut()
+            .map(|store| store.flush())
+            .collect::<Vec<_>>();
+
+        if self.lazy {
+            // free the keys without blocking the event loop
+            thread::spawn(move || drop(old));
+        }
+        Ok(Response::Ok)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct FlushAllParser {
+    options: Options<FlushAll>,
+}
+
+impl FlushAllParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![(vec!["ASYNC", "SYNC"], FlushAllParser::try_mode)],
+        }
+    }
+
+    fn try_mode(flush: &mut FlushAll, mode: &str, _: &mut Input) -> Result<(), Error> {
+        flush.lazy = mode == "ASYNC";
+        Ok(())
+    }
+}
+
+impl TryParse for FlushAllParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(parse_options(
+            "FLUSHALL",
+            &self.options,
+            input,
+            FlushAll::new(),
+        )?))
+    }
+}
@@ -0,0 +1,57 @@
+use std::thread;
+
+use crate::commands::prelude::*;
+
+struct FlushDb {
+    lazy: bool,
+}
+
+impl FlushDb {
+    pub fn new() -> Self {
+        Self { lazy: false }
+    }
+}
+
+impl ApplyToStore for FlushDb {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let old = store.flush();
+
+        if self.lazy {
+            // free the keys without blocking the event loop
+            thread::spawn(move || drop(old));
+        }
+        Ok(Response::Ok)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct FlushDbParser {
+    options: Options<FlushDb>,
+}
+
+impl FlushDbParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![(vec!["ASYNC", "SYNC"], FlushDbParser::try_mode)],
+        }
+    }
+
+    fn try_mode(flush: &mut FlushDb, mode: &str, _: &mut Input) -> Result<(), Error> {
+        flush.lazy = mode == "ASYNC";
+        Ok(())
+    }
+}
+
+impl TryParse for FlushDbParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(parse_options(
+            "FLUSHDB",
+            &self.options,
+            input,
+            FlushDb::new(),
+        )?))
+    }
+}
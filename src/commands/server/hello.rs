@@ -0,0 +1,128 @@
+use crate::commands::prelude::*;
+use crate::network::{Protocol, VERSION};
+
+struct Hello {
+    protocol: Option<Protocol>,
+    auth: Option<(String, String)>,
+    name: Option<String>,
+}
+
+impl Hello {
+    pub fn new(protocol: Option<Protocol>) -> Self {
+        Self {
+            protocol,
+            auth: None,
+            name: None,
+        }
+    }
+}
+
+impl Apply for Hello {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        if let Some((username, _)) = &self.auth {
+            if username != "default" {
+                return Err(Error::Raw(
+                    b"-WRONGPASS invalid username-password pair or user is disabled.\r\n",
+                ));
+            }
+        }
+
+        let session = context.session;
+        if let Some(protocol) = self.protocol {
+            session.set_protocol(protocol);
+        }
+        if let Some(name) = &self.name {
+            session.set_name(if name.is_empty() {
+                None
+            } else {
+                Some(name.clone())
+            });
+        }
+
+        Ok(Response::Map(vec![
+            (
+                Response::BulkStringRef(b"server"),
+                Response::BulkStringRef(b"redis"),
+            ),
+            (
+                Response::BulkStringRef(b"version"),
+                Response::BulkStringRef(VERSION.as_bytes()),
+            ),
+            (
+                Response::BulkStringRef(b"proto"),
+                Response::I64(session.protocol().version()),
+            ),
+            (
+                Response::BulkStringRef(b"id"),
+                Response::Usize(session.id()),
+            ),
+            (
+                Response::BulkStringRef(b"mode"),
+                Response::BulkStringRef(b"standalone"),
+            ),
+            (
+                Response::BulkStringRef(b"role"),
+                Response::BulkStringRef(if context.config.replica_of.is_some() {
+                    b"replica"
+                } else {
+                    b"master"
+                }),
+            ),
+            (Response::BulkStringRef(b"modules"), Response::EmptyList),
+        ]))
+    }
+}
+
+pub struct HelloParser {
+    options: Options<Hello>,
+}
+
+impl HelloParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![
+                (vec!["AUTH"], HelloParser::try_auth),
+                (vec!["SETNAME"], HelloParser::try_set_name),
+            ],
+        }
+    }
+
+    fn try_auth(hello: &mut Hello, _: &str, input: &mut Input) -> Result<(), Error> {
+        hello.auth = Some((input.next_string()?, input.next_string()?));
+        Ok(())
+    }
+
+    fn try_set_name(hello: &mut Hello, _: &str, input: &mut Input) -> Result<(), Error> {
+        let name = input.next_string()?;
+
+        if name.chars().any(|c| !('!'..='~').contains(&c)) {
+            return Err(Error::Raw(
+                b"-ERR Client names cannot contain spaces, newlines or special characters.\r\n",
+            ));
+        }
+
+        hello.name = Some(name);
+        Ok(())
+    }
+}
+
+impl TryParse for HelloParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if !input.has_next() {
+            return Ok(Box::new(Hello::new(None)));
+        }
+
+        let version = input.next_i64().map_err(|_| {
+            Error::Raw(b"-ERR Protocol version is not an integer or out of range\r\n")
+        })?;
+        let protocol = Protocol::try_from_version(version)
+            .ok_or(Error::Raw(b"-NOPROTO unsupported protocol version\r\n"))?;
+
+        Ok(Box::new(parse_options(
+            "HELLO",
+            &self.options,
+            input,
+            Hello::new(Some(protocol)),
+        )?))
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::Write;
+use std::process;
+
+use crate::commands::prelude::*;
+use crate::network::VERSION;
+
+const DEFAULT_SECTIONS: [&str; 5] = ["server", "persistence", "stats", "replication", "keyspace"];
+
+struct Info {
+    sections: Vec<String>,
+}
+
+impl Info {
+    pub fn new(sections: Vec<String>) -> Self {
+        Self { sections }
+    }
+
+    fn includes(&self, section: &str) -> bool {
+        self.sections.is_empty()
+            || self.sections.iter().any(|name| {
+                name == section || name == "all" || name == "default" || name == "everything"
+            })
+    }
+}
+
+impl Apply for Info {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let mut info = String::new();
+
+        for section in DEFAULT_SECTIONS
+            .iter()
+            .filter(|section| self.includes(section))
+        {
+            if !info.is_empty() {
+                info.push_str("\r\n");
+            }
+            let _ = writeln!(info, "# {}{}\r", section[..1].to_uppercase(), &section[1..]);
+
+            let _ = match *section {
+                "server" => write_server(&mut info, &context),
+                "persistence" => write_persistence(&mut info, &context),
+                "stats" => write_stats(&mut info, &context),
+                "replication" => write_replication(&mut info, &context),
+                _ => write_keyspace(&mut info, context.databases),
+            };
+        }
+
+        Ok(Response::BulkString(info.into_bytes()))
+    }
+}
+
+fn write_server(info: &mut String, context: &Context) -> std::fmt::Result {
+    write!(info, "redis_version:{}\r\n", VERSION)?;
+    write!(info, "redis_mode:standalone\r\n")?;
+    write!(info, "process_id:{}\r\n", process::id())?;
+    write!(info, "tcp_port:{}\r\n", context.config.port)?;
+    write!(
+        info,
+        "config_file:{}\r\n",
+        context
+            .config
+            .file
+            .as_ref()
+            .map(|file| file.display().to_string())
+            .unwrap_or_default()
+    )
+}
+
+fn write_persistence(info: &mut String, context: &Context) -> std::fmt::Result {
+    let snapshots = &context.snapshots;
+
+    write!(
+        info,
+        "rdb_changes_since_last_save:{}\r\n",
+        snapshots.changes(context.databases)
+    )?;
+    write!(
+        info,
+        "rdb_bgsave_in_progress:{}\r\n",
+        snapshots.in_progress() as u8
+    )?;
+    write!(
+        info,
+        "rdb_last_save_time:{}\r\n",
+        snapshots.last_save().timestamp()
+    )?;
+    write!(
+        info,
+        "rdb_last_bgsave_status:{}\r\n",
+        if snapshots.last_ok() { "ok" } else { "err" }
+    )?;
+    write!(info, "aof_enabled:{}\r\n", context.config.append_only as u8)?;
+    write!(
+        info,
+        "aof_rewrite_in_progress:{}\r\n",
+        context.aof.in_progress() as u8
+    )
+}
+
+fn write_stats(info: &mut String, context: &Context) -> std::fmt::Result {
+    let stats = &context.stats;
+
+    write!(
+        info,
+        "total_connections_received:{}\r\n",
+        stats.total_connections_received
+    )?;
+    write!(
+        info,
+        "total_commands_processed:{}\r\n",
+        stats.total_commands_processed
+    )?;
+    write!(
+        info,
+        "rejected_connections:{}\r\n",
+        stats.rejected_connections
+    )?;
+    write!(info, "expired_keys:{}\r\n", stats.expired_keys)?;
+    write!(info, "expired_subkeys:{}\r\n", stats.expired_subkeys)
+}
+
+fn write_replication(info: &mut String, context: &Context) -> std::fmt::Result {
+    let replication = &context.replication;
+
+    match &context.config.replica_of {
+        Some((host, port)) => {
+            let up = replication.link() == LinkState::Connected;
+
+            write!(info, "role:slave\r\n")?;
+            write!(info, "master_host:{}\r\n", host)?;
+            write!(info, "master_port:{}\r\n", port)?;
+            write!(
+                info,
+                "master_link_status:{}\r\n",
+                if up { "up" } else { "down" }
+            )?;
+            write!(
+                info,
+                "master_sync_in_progress:{}\r\n",
+                (replication.link() == LinkState::Sync) as u8
+            )?;
+            write!(info, "slave_repl_offset:{}\r\n", replication.offset())?;
+            write!(
+                info,
+                "slave_read_only:{}\r\n",
+                context.config.replica_read_only as u8
+            )?;
+        }
+        None => write!(info, "role:master\r\n")?,
+    }
+
+    write!(
+        info,
+        "connected_slaves:{}\r\n",
+        replication.replicas().len()
+    )?;
+    for (index, replica) in replication.replicas().iter().enumerate() {
+        write!(
+            info,
+            "slave{}:ip={},port={},state=online,offset={}\r\n",
+            index, replica.ip, replica.port, replica.ack
+        )?;
+    }
+    write!(info, "master_replid:{}\r\n", replication.id())?;
+    write!(info, "master_repl_offset:{}\r\n", replication.offset())?;
+    write!(
+        info,
+        "repl_backlog_active:{}\r\n",
+        replication.has_backlog() as u8
+    )?;
+    write!(
+        info,
+        "repl_backlog_size:{}\r\n",
+        context.config.repl_backlog_size
+    )?;
+    write!(
+        info,
+        "repl_backlog_histlen:{}\r\n",
+        replication.backlog_len()
+    )
+}
+
+fn write_keyspace(info: &mut String, databases: &mut [Store]) -> std::fmt::Result {
+    for (index, store) in databases.iter_mut().enumerate() {
+        let keys = store.len();
+        if keys > 0 {
+            write!(
+                info,
+                "db{}:keys={},expires={}\r\n",
+                index,
+                keys,
+                store.volatile_len()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub struct InfoParser {}
+
+impl InfoParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for InfoParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let mut sections = Vec::new();
+
+        while input.has_next() {
+            sections.push(input.next_string()?.to_lowercase());
+        }
+        Ok(Box::new(Info::new(sections)))
+    }
+}
@@ -0,0 +1,29 @@
+use crate::commands::prelude::*;
+
+struct LastSave {}
+
+impl LastSave {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for LastSave {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        Ok(Response::I64(context.snapshots.last_save().timestamp()))
+    }
+}
+
+pub struct LastSaveParser {}
+
+impl LastSaveParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for LastSaveParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(LastSave::new()))
+    }
+}
@@ -0,0 +1,54 @@
+use crate::commands::prelude::*;
+use crate::network::Protocol;
+
+const PONG: Response = Response::Raw(b"+PONG\r\n");
+
+struct Ping {
+    message: Option<Vec<u8>>,
+}
+
+impl Ping {
+    pub fn new(message: Option<Vec<u8>>) -> Self {
+        Self { message }
+    }
+}
+
+impl Apply for Ping {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+
+        // a RESP2 subscriber can't tell a reply from a message unless it's an array
+        if session.is_subscribed() && session.protocol() == Protocol::Resp2 {
+            return Ok(Response::Array(vec![
+                Response::BulkStringRef(b"pong"),
+                Response::BulkString(self.message.clone().unwrap_or_default()),
+            ]));
+        }
+        match &self.message {
+            Some(message) => Ok(Response::BulkString(message.clone())),
+            None => Ok(PONG),
+        }
+    }
+
+    fn is_subscriber(&self) -> bool {
+        true
+    }
+}
+
+pub struct PingParser {}
+
+impl PingParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PingParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if input.has_next() {
+            Ok(Box::new(Ping::new(Some(input.next_bytes()?))))
+        } else {
+            Ok(Box::new(Ping::new(None)))
+        }
+    }
+}
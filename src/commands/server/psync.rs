@@ -0,0 +1,86 @@
+use crate::commands::prelude::*;
+use crate::persistence::encode_snapshot;
+
+/// Psync starts streaming write commands to a replica, continuing from its offset
+/// when the backlog still holds it and otherwise after sending it a snapshot of the databases.
+struct Psync {
+    id: String,
+    /// the offset of the next byte of the stream the replica needs
+    offset: i64,
+}
+
+impl Psync {
+    pub fn new(id: String, offset: i64) -> Self {
+        Self { id, offset }
+    }
+}
+
+impl Apply for Psync {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let replication = context.replication;
+        if context.config.replica_of.is_some() && replication.link() != LinkState::Connected {
+            return Err(Error::Raw(
+                b"-NOMASTERLINK Can't SYNC while not connected with my master\r\n",
+            ));
+        }
+
+        let session = context.session;
+        let ip = session.ip().unwrap_or("?").to_string();
+        let port = session.listening_port().unwrap_or(0);
+        let backlog = u64::try_from(self.offset)
+            .ok()
+            .and_then(|offset| replication.try_continue(&self.id, offset));
+
+        let reply = match backlog {
+            Some(backlog) => {
+                notice!(
+                    "Partial resynchronization request from {}:{} accepted, sending {} bytes of backlog",
+                    ip,
+                    port,
+                    backlog.len()
+                );
+                let mut reply = format!("+CONTINUE {}\r\n", replication.id()).into_bytes();
+                reply.extend_from_slice(&backlog);
+                reply
+            }
+            None => {
+                notice!(
+                    "Replica {}:{} asks for synchronization, starting full resync",
+                    ip,
+                    port
+                );
+                let snapshot = encode_snapshot(context.databases);
+                let mut reply = format!(
+                    "+FULLRESYNC {} {}\r\n${}\r\n",
+                    replication.id(),
+                    replication.offset(),
+                    snapshot.len()
+                )
+                .into_bytes();
+                reply.extend_from_slice(&snapshot);
+                reply
+            }
+        };
+
+        session.set_peer(Peer::Replica);
+        replication.add_replica(session.id(), ip, port);
+        Ok(Response::RawBytes(reply))
+    }
+}
+
+pub struct PsyncParser {}
+
+impl PsyncParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PsyncParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Psync::new(
+            input.next_string()?,
+            input.next_i64()?,
+        )))
+    }
+}
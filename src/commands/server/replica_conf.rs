@@ -0,0 +1,91 @@
+use crate::commands::prelude::*;
+
+/// ReplicaConf records what a replica tells its leader about itself,
+/// such as the port it accepts clients on and the offset of the stream it applied.
+struct ReplicaConf {
+    listening_port: Option<u16>,
+    ack: Option<u64>,
+    getack: bool,
+}
+
+impl ReplicaConf {
+    pub fn new() -> Self {
+        Self {
+            listening_port: None,
+            ack: None,
+            getack: false,
+        }
+    }
+}
+
+impl Apply for ReplicaConf {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+
+        if let Some(offset) = self.ack {
+            // a replica would read a reply as part of the stream
+            if session.peer() == Peer::Replica {
+                context.replication.acknowledge(session.id(), offset);
+            }
+            return Ok(Response::NoReply);
+        }
+        if self.getack {
+            // the replica sends the acknowledgement from the server loop
+            if session.peer() == Peer::Leader {
+                context.replication.request_ack();
+            }
+            return Ok(Response::NoReply);
+        }
+
+        if let Some(port) = self.listening_port {
+            session.set_listening_port(port);
+        }
+        Ok(Response::Ok)
+    }
+}
+
+pub struct ReplicaConfParser {}
+
+impl ReplicaConfParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ReplicaConfParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let mut conf = ReplicaConf::new();
+
+        // options may repeat, such as "capa eof capa psync2"
+        while input.has_next() {
+            let option = input.next_token()?;
+
+            match option.as_str() {
+                "LISTENING-PORT" => {
+                    conf.listening_port =
+                        Some(input.next_string()?.parse().map_err(|_| Error::Integer)?);
+                }
+                "ACK" => {
+                    conf.ack = Some(input.next_u64()?);
+                    // skip the optional FACK offset
+                    input.rest()?;
+                }
+                "GETACK" => {
+                    input.next()?;
+                    conf.getack = true;
+                }
+                "CAPA" | "IP-ADDRESS" => {
+                    input.next()?;
+                }
+                _ => {
+                    return Err(Error::String(format!(
+                        "ERR Unrecognized REPLCONF option: {}",
+                        option.to_lowercase()
+                    )));
+                }
+            }
+        }
+
+        Ok(Box::new(conf))
+    }
+}
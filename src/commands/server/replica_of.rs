@@ -0,0 +1,60 @@
+use crate::commands::prelude::*;
+
+struct ReplicaOf {
+    /// the host and port of the new leader, or `None` to become a leader
+    leader: Option<(String, u16)>,
+}
+
+impl ReplicaOf {
+    pub fn new(leader: Option<(String, u16)>) -> Self {
+        Self { leader }
+    }
+}
+
+impl Apply for ReplicaOf {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        match &self.leader {
+            None => {
+                if context.config.replica_of.take().is_some() {
+                    notice!("MASTER MODE enabled (user request)");
+                }
+                Ok(Response::Ok)
+            }
+            Some(leader) if context.config.replica_of.as_ref() == Some(leader) => {
+                notice!("Already connected to the specified master, no operation performed");
+                Ok(Response::Raw(
+                    b"+OK Already connected to specified master\r\n",
+                ))
+            }
+            Some((host, port)) => {
+                context.config.replica_of = Some((host.clone(), *port));
+                notice!("REPLICAOF {}:{} enabled (user request)", host, port);
+                Ok(Response::Ok)
+            }
+        }
+    }
+}
+
+pub struct ReplicaOfParser {}
+
+impl ReplicaOfParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ReplicaOfParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let host = input.next_string()?;
+        let port = input.next_string()?;
+
+        if host.eq_ignore_ascii_case("NO") && port.eq_ignore_ascii_case("ONE") {
+            return Ok(Box::new(ReplicaOf::new(None)));
+        }
+        let port = port
+            .parse()
+            .map_err(|_| Error::Raw(b"-ERR Invalid master port\r\n"))?;
+
+        Ok(Box::new(ReplicaOf::new(Some((host, port)))))
+    }
+}
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use crate::commands::prelude::*;
+
+struct RewriteAof {}
+
+impl RewriteAof {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for RewriteAof {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        if context.aof.in_progress() {
+            return Err(Error::Raw(
+                b"-ERR Background append only file rewriting already in progress\r\n",
+            ));
+        }
+
+        let path = PathBuf::from(&context.config.append_filename);
+        context.aof.start_rewrite(&path, context.databases);
+        Ok(Response::SimpleString(
+            "Background append only file rewriting started".to_string(),
+        ))
+    }
+}
+
+pub struct RewriteAofParser {}
+
+impl RewriteAofParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for RewriteAofParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(RewriteAof::new()))
+    }
+}
@@ -0,0 +1,56 @@
+use crate::commands::prelude::*;
+
+struct Role {}
+
+impl Role {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for Role {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let replication = context.replication;
+
+        match &context.config.replica_of {
+            Some((host, port)) => Ok(Response::Array(vec![
+                Response::BulkStringRef(b"slave"),
+                Response::BulkString(host.clone().into_bytes()),
+                Response::I64(*port as i64),
+                Response::BulkStringRef(replication.link().as_str().as_bytes()),
+                Response::I64(replication.offset() as i64),
+            ])),
+            None => Ok(Response::Array(vec![
+                Response::BulkStringRef(b"master"),
+                Response::I64(replication.offset() as i64),
+                Response::Array(
+                    replication
+                        .replicas()
+                        .iter()
+                        .map(|replica| {
+                            Response::Array(vec![
+                                Response::BulkString(replica.ip.clone().into_bytes()),
+                                Response::BulkString(replica.port.to_string().into_bytes()),
+                                Response::BulkString(replica.ack.to_string().into_bytes()),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ])),
+        }
+    }
+}
+
+pub struct RoleParser {}
+
+impl RoleParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for RoleParser {
+    fn try_parse(&self, _: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Role::new()))
+    }
+}
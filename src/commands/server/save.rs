@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use crate::commands::prelude::*;
+
+struct Save {}
+
+impl Save {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for Save {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        if context.snapshots.in_progress() {
+            return Err(Error::Raw(b"-ERR Background save already in progress\r\n"));
+        }
+
+        let path = PathBuf::from(&context.config.db_filename);
+        match context.snapshots.save(&path, context.databases) {
+            Ok(()) => Ok(Response::Ok),
+            Err(_) => Err(Error::Raw(b"-ERR\r\n")),
+        }
+    }
+}
+
+pub struct SaveParser {}
+
+impl SaveParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for SaveParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Save::new()))
+    }
+}
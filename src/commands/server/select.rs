@@ -0,0 +1,34 @@
+use crate::commands::prelude::*;
+
+struct Select {
+    db: i64,
+}
+
+impl Select {
+    pub fn new(db: i64) -> Self {
+        Self { db }
+    }
+}
+
+impl Apply for Select {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let db = context.check_db(self.db)?;
+
+        context.session.set_db(db);
+        Ok(Response::Ok)
+    }
+}
+
+pub struct SelectParser {}
+
+impl SelectParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for SelectParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Select::new(input.next_i64()?)))
+    }
+}
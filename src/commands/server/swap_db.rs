@@ -0,0 +1,50 @@
+use crate::commands::prelude::*;
+
+struct SwapDb {
+    first: i64,
+    second: i64,
+}
+
+impl SwapDb {
+    pub fn new(first: i64, second: i64) -> Self {
+        Self { first, second }
+    }
+}
+
+impl Apply for SwapDb {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let first = context.check_db(self.first)?;
+        let second = context.check_db(self.second)?;
+
+        if first != second {
+            let (low, high) = context.databases.split_at_mut(first.max(second));
+            low[first.min(second)].swap(&mut high[0]);
+        }
+        Ok(Response::Ok)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct SwapDbParser {}
+
+impl SwapDbParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for SwapDbParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let first = input
+            .next_i64()
+            .map_err(|_| Error::Raw(b"-ERR invalid first DB index\r\n"))?;
+        let second = input
+            .next_i64()
+            .map_err(|_| Error::Raw(b"-ERR invalid second DB index\r\n"))?;
+
+        Ok(Box::new(SwapDb::new(first, second)))
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use crate::commands::prelude::*;
+
+/// Wait blocks the client until enough replicas acknowledge applying its writes.
+struct Wait {
+    replicas: usize,
+    timeout: Option<Duration>,
+}
+
+impl Wait {
+    pub fn new(replicas: usize, timeout: Option<Duration>) -> Self {
+        Self { replicas, timeout }
+    }
+}
+
+impl Apply for Wait {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        if context.config.replica_of.is_some() {
+            return Err(Error::Raw(
+                b"-ERR WAIT cannot be used with replica instances.\r\n",
+            ));
+        }
+
+        let acknowledged = context
+            .replication
+            .acknowledged(context.session.repl_offset());
+        if acknowledged >= self.replicas {
+            return Ok(Response::Usize(acknowledged));
+        }
+
+        context.replication.request_getack();
+        Ok(Response::Block(self.timeout))
+    }
+
+    fn retry<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let acknowledged = context
+            .replication
+            .acknowledged(context.session.repl_offset());
+
+        if acknowledged >= self.replicas {
+            Ok(Response::Usize(acknowledged))
+        } else {
+            Ok(Response::Block(self.timeout))
+        }
+    }
+
+    fn timeout<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        Ok(Response::Usize(
+            context
+                .replication
+                .acknowledged(context.session.repl_offset()),
+        ))
+    }
+}
+
+pub struct WaitParser {}
+
+impl WaitParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for WaitParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let replicas = input.next_usize()?;
+        let timeout = input.next_i64()?;
+        if timeout < 0 {
+            return Err(Error::Raw(b"-ERR timeout is negative\r\n"));
+        }
+
+        Ok(Box::new(Wait::new(
+            replicas,
+            (timeout > 0).then(|| Duration::from_millis(timeout as u64)),
+        )))
+    }
+}
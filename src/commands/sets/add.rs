@@ -1,17 +1,17 @@
 use crate::commands::prelude::*;
 
 struct Add {
-    key: String,
-    values: Vec<String>,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
 }
 
 impl Add {
-    pub fn new(key: String, values: Vec<String>) -> Self {
+    pub fn new(key: Vec<u8>, values: Vec<Vec<u8>>) -> Self {
         Self { key, values }
     }
 }
 
-impl Apply for Add {
+impl ApplyToStore for Add {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::Set, &self.key) {
             IfKindResult::Matched(Value::Set(ref mut members)) => {
@@ -30,6 +30,10 @@ impl Apply for Add {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct AddParser {}
@@ -42,6 +46,6 @@ impl AddParser {
 
 impl TryParse for AddParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Add::new(input.next_string()?, input.rest()?)))
+        Ok(Box::new(Add::new(input.next_bytes()?, input.rest()?)))
     }
 }
@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Card {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Card {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for Card {
+impl ApplyToStore for Card {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::Set, &self.key) {
             IfKindResult::Matched(Value::Set(members)) => Ok(Response::Usize(members.len())),
@@ -30,6 +30,6 @@ impl CardParser {
 
 impl TryParse for CardParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Card::new(input.next_string()?)))
+        Ok(Box::new(Card::new(input.next_bytes()?)))
     }
 }
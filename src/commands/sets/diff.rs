@@ -2,16 +2,16 @@ use crate::commands::prelude::*;
 use crate::storage::{diff, SetOp};
 
 struct Diff {
-    keys: Vec<String>,
+    keys: Vec<Vec<u8>>,
 }
 
 impl Diff {
-    pub fn new(keys: Vec<String>) -> Self {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
         Self { keys }
     }
 }
 
-impl Apply for Diff {
+impl ApplyToStore for Diff {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match diff(store, &self.keys, usize::MAX) {
             SetOp::New(members) => Ok(Response::Set(members)),
@@ -2,17 +2,17 @@ use crate::commands::prelude::*;
 use crate::storage::{diff, SetOp};
 
 struct DiffStore {
-    from: Vec<String>,
-    to: String,
+    from: Vec<Vec<u8>>,
+    to: Vec<u8>,
 }
 
 impl DiffStore {
-    pub fn new(from: Vec<String>, to: String) -> Self {
+    pub fn new(from: Vec<Vec<u8>>, to: Vec<u8>) -> Self {
         Self { from, to }
     }
 }
 
-impl Apply for DiffStore {
+impl ApplyToStore for DiffStore {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         let diff = match diff(store, &self.from, usize::MAX) {
             SetOp::New(members) => members,
@@ -25,6 +25,10 @@ impl Apply for DiffStore {
         store.set(&self.to, Value::from(diff));
         Ok(Response::Usize(len))
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct DiffStoreParser {}
@@ -37,7 +41,7 @@ impl DiffStoreParser {
 
 impl TryParse for DiffStoreParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let to = input.next_string()?;
+        let to = input.next_bytes()?;
 
         if input.has_next() {
             Ok(Box::new(DiffStore::new(input.rest()?, to)))
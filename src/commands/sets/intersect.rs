@@ -2,16 +2,16 @@ use crate::commands::prelude::*;
 use crate::storage::{intersect, SetOp};
 
 struct Intersect {
-    keys: Vec<String>,
+    keys: Vec<Vec<u8>>,
 }
 
 impl Intersect {
-    pub fn new(keys: Vec<String>) -> Self {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
         Self { keys }
     }
 }
 
-impl Apply for Intersect {
+impl ApplyToStore for Intersect {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match intersect(store, &self.keys, usize::MAX) {
             SetOp::New(members) => Ok(Response::Set(members)),
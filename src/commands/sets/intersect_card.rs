@@ -2,12 +2,12 @@ use crate::commands::prelude::*;
 use crate::storage::{intersect, SetOp};
 
 struct IntersectCard {
-    from: Vec<String>,
+    from: Vec<Vec<u8>>,
     limit: usize,
 }
 
 impl IntersectCard {
-    pub fn new(from: Vec<String>) -> Self {
+    pub fn new(from: Vec<Vec<u8>>) -> Self {
         Self {
             from,
             limit: usize::MAX,
@@ -15,7 +15,7 @@ impl IntersectCard {
     }
 }
 
-impl Apply for IntersectCard {
+impl ApplyToStore for IntersectCard {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         let len = match intersect(store, &self.from, self.limit) {
             SetOp::New(members) => members.len(),
@@ -50,7 +50,7 @@ impl IntersectCardParser {
 impl TryParse for IntersectCardParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         let count = input.next_u64_min(1)? as usize;
-        let keys = input.next_strings("SINTERCARD", "key", count)?;
+        let keys = input.next_multiple("SINTERCARD", "key", count)?;
 
         Ok(Box::new(parse_options(
             "SINTERCARD",
@@ -2,17 +2,17 @@ use crate::commands::prelude::*;
 use crate::storage::{intersect, SetOp};
 
 struct IntersectStore {
-    from: Vec<String>,
-    to: String,
+    from: Vec<Vec<u8>>,
+    to: Vec<u8>,
 }
 
 impl IntersectStore {
-    pub fn new(from: Vec<String>, to: String) -> Self {
+    pub fn new(from: Vec<Vec<u8>>, to: Vec<u8>) -> Self {
         Self { from, to }
     }
 }
 
-impl Apply for IntersectStore {
+impl ApplyToStore for IntersectStore {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         let intersection = match intersect(store, &self.from, usize::MAX) {
             SetOp::New(members) => members,
@@ -25,6 +25,10 @@ impl Apply for IntersectStore {
         store.set(&self.to, Value::from(intersection));
         Ok(Response::Usize(len))
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct IntersectStoreParser {}
@@ -37,7 +41,7 @@ impl IntersectStoreParser {
 
 impl TryParse for IntersectStoreParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let to = input.next_string()?;
+        let to = input.next_bytes()?;
 
         if input.has_next() {
             Ok(Box::new(IntersectStore::new(input.rest()?, to)))
@@ -1,17 +1,17 @@
 use crate::commands::prelude::*;
 
 struct IsMember {
-    key: String,
-    value: String,
+    key: Vec<u8>,
+    value: Vec<u8>,
 }
 
 impl IsMember {
-    pub fn new(key: String, value: String) -> Self {
+    pub fn new(key: Vec<u8>, value: Vec<u8>) -> Self {
         Self { key, value }
     }
 }
 
-impl Apply for IsMember {
+impl ApplyToStore for IsMember {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::Set, &self.key) {
             IfKindResult::Matched(Value::Set(members)) => {
@@ -34,8 +34,8 @@ impl IsMemberParser {
 impl TryParse for IsMemberParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(IsMember::new(
-            input.next_string()?,
-            input.next_string()?,
+            input.next_bytes()?,
+            input.next_bytes()?,
         )))
     }
 }
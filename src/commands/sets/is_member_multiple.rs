@@ -3,34 +3,34 @@ use itertools::Itertools;
 use crate::commands::prelude::*;
 
 struct IsMemberMultiple {
-    key: String,
-    values: Vec<String>,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
 }
 
 impl IsMemberMultiple {
-    pub fn new(key: String, values: Vec<String>) -> Self {
+    pub fn new(key: Vec<u8>, values: Vec<Vec<u8>>) -> Self {
         Self { key, values }
     }
 }
 
-impl Apply for IsMemberMultiple {
+impl ApplyToStore for IsMemberMultiple {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::Set, &self.key) {
-            IfKindResult::Matched(Value::Set(members)) => Ok(Response::ValueList(
+            IfKindResult::Matched(Value::Set(members)) => Ok(Response::Array(
                 self.values
                     .iter()
                     .map(|value| {
                         if members.contains(value) {
-                            Value::from(1)
+                            Response::One
                         } else {
-                            Value::from(0)
+                            Response::Zero
                         }
                     })
                     .collect_vec(),
             )),
-            IfKindResult::NotSet => {
-                Ok(Response::ValueList(vec![Value::from(0); self.values.len()]))
-            }
+            IfKindResult::NotSet => Ok(Response::Array(
+                self.values.iter().map(|_| Response::Zero).collect_vec(),
+            )),
             _ => Err(Error::WrongType),
         }
     }
@@ -47,7 +47,7 @@ impl IsMemberMultipleParser {
 impl TryParse for IsMemberMultipleParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(IsMemberMultiple::new(
-            input.next_string()?,
+            input.next_bytes()?,
             input.rest()?,
         )))
     }
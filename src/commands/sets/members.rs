@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Members {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Members {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for Members {
+impl ApplyToStore for Members {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::Set, &self.key) {
             IfKindResult::Matched(value) if value.is_set() => Ok(Response::ValueRef(value)),
@@ -30,6 +30,6 @@ impl MembersParser {
 
 impl TryParse for MembersParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Members::new(input.next_string()?)))
+        Ok(Box::new(Members::new(input.next_bytes()?)))
     }
 }
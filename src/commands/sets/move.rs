@@ -1,18 +1,18 @@
 use crate::commands::prelude::*;
 
 struct Move {
-    from: String,
-    to: String,
-    value: String,
+    from: Vec<u8>,
+    to: Vec<u8>,
+    value: Vec<u8>,
 }
 
 impl Move {
-    pub fn new(from: String, to: String, value: String) -> Self {
+    pub fn new(from: Vec<u8>, to: Vec<u8>, value: Vec<u8>) -> Self {
         Self { from, to, value }
     }
 }
 
-impl Apply for Move {
+impl ApplyToStore for Move {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         if !match store.get_if_kind(Kind::Set, &self.from) {
             IfKindResult::Matched(Value::Set(members)) => {
@@ -35,7 +35,10 @@ impl Apply for Move {
                 }
             }
             IfKindResult::NotSet => {
-                store.set(&self.to, Value::set_from_vec(&[self.value.clone()]));
+                store.set(
+                    &self.to,
+                    Value::set_from_vec(std::slice::from_ref(&self.value)),
+                );
             }
             _ => return Err(Error::WrongType),
         };
@@ -48,6 +51,10 @@ impl Apply for Move {
         }
         Ok(Response::One)
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct MoveParser {}
@@ -61,9 +68,9 @@ impl MoveParser {
 impl TryParse for MoveParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(Move::new(
-            input.next_string()?,
-            input.next_string()?,
-            input.next_string()?,
+            input.next_bytes()?,
+            input.next_bytes()?,
+            input.next_bytes()?,
         )))
     }
 }
@@ -2,17 +2,15 @@ use crate::commands::prelude::*;
 use crate::storage::{pop_random_members, Random};
 
 struct Pop {
-    key: String,
+    key: Vec<u8>,
     count: usize,
 }
 
 impl Pop {
-    pub fn new(key: String, count: usize) -> Self {
+    pub fn new(key: Vec<u8>, count: usize) -> Self {
         Self { key, count }
     }
-}
 
-impl Apply for Pop {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match pop_random_members(store, &self.key, self.count) {
             Random::Single(member) => Ok(Response::BulkString(member.clone())),
@@ -24,6 +22,32 @@ impl Apply for Pop {
     }
 }
 
+impl Apply for Pop {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let propagated = context.propagated;
+        let response = self.apply(&mut context.databases[context.session.db()])?;
+
+        // replay removes the same members instead of picking new ones
+        let mut args = vec![b"SREM".to_vec(), self.key.clone()];
+        match &response {
+            Response::BulkString(member) => args.push(member.clone()),
+            Response::List(members) => args.extend(members.iter().cloned()),
+            _ => (),
+        }
+        *propagated = Some(if args.len() > 2 {
+            vec![args]
+        } else {
+            Vec::new()
+        });
+
+        Ok(response)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
 pub struct PopParser {}
 
 impl PopParser {
@@ -34,7 +58,7 @@ impl PopParser {
 
 impl TryParse for PopParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
 
         if input.has_next() {
             Ok(Box::new(Pop::new(key, input.next_u64()? as usize)))
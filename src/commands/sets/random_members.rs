@@ -2,18 +2,18 @@ use crate::commands::prelude::*;
 use crate::storage::{Random, random_members};
 
 struct RandomMembers {
-    key: String,
+    key: Vec<u8>,
     count: usize,
     dupes: bool,
 }
 
 impl RandomMembers {
-    pub fn new(key: String, count: usize, dupes: bool) -> Self {
+    pub fn new(key: Vec<u8>, count: usize, dupes: bool) -> Self {
         Self { key, count, dupes }
     }
 }
 
-impl Apply for RandomMembers {
+impl ApplyToStore for RandomMembers {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match random_members(store, &self.key, self.count, self.dupes) {
             Random::Single(member) => Ok(Response::BulkString(member.clone())),
@@ -35,7 +35,7 @@ impl RandomMembersParser {
 
 impl TryParse for RandomMembersParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
 
         if input.has_next() {
             let count = input.next_i64()?;
@@ -1,17 +1,17 @@
 use crate::commands::prelude::*;
 
 struct Remove {
-    key: String,
-    values: Vec<String>,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
 }
 
 impl Remove {
-    pub fn new(key: String, values: Vec<String>) -> Self {
+    pub fn new(key: Vec<u8>, values: Vec<Vec<u8>>) -> Self {
         Self { key, values }
     }
 }
 
-impl Apply for Remove {
+impl ApplyToStore for Remove {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::Set, &self.key) {
             IfKindResult::Matched(Value::Set(ref mut members)) => {
@@ -30,6 +30,10 @@ impl Apply for Remove {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct RemoveParser {}
@@ -42,6 +46,6 @@ impl RemoveParser {
 
 impl TryParse for RemoveParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Remove::new(input.next_string()?, input.rest()?)))
+        Ok(Box::new(Remove::new(input.next_bytes()?, input.rest()?)))
     }
 }
@@ -2,16 +2,16 @@ use crate::commands::prelude::*;
 use crate::storage::{SetOp, union};
 
 struct Union {
-    keys: Vec<String>,
+    keys: Vec<Vec<u8>>,
 }
 
 impl Union {
-    pub fn new(keys: Vec<String>) -> Self {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
         Self { keys }
     }
 }
 
-impl Apply for Union {
+impl ApplyToStore for Union {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match union(store, &self.keys, usize::MAX) {
             SetOp::New(members) => Ok(Response::Set(members)),
@@ -2,17 +2,17 @@ use crate::commands::prelude::*;
 use crate::storage::{SetOp, union};
 
 struct UnionStore {
-    from: Vec<String>,
-    to: String,
+    from: Vec<Vec<u8>>,
+    to: Vec<u8>,
 }
 
 impl UnionStore {
-    pub fn new(from: Vec<String>, to: String) -> Self {
+    pub fn new(from: Vec<Vec<u8>>, to: Vec<u8>) -> Self {
         Self { from, to }
     }
 }
 
-impl Apply for UnionStore {
+impl ApplyToStore for UnionStore {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         let union = match union(store, &self.from, usize::MAX) {
             SetOp::New(members) => members,
@@ -25,6 +25,10 @@ impl Apply for UnionStore {
         store.set(&self.to, Value::from(union));
         Ok(Response::Usize(len))
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct UnionStoreParser {}
@@ -37,7 +41,7 @@ impl UnionStoreParser {
 
 impl TryParse for UnionStoreParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let to = input.next_string()?;
+        let to = input.next_bytes()?;
 
         if input.has_next() {
             Ok(Box::new(UnionStore::new(input.rest()?, to)))
@@ -0,0 +1,49 @@
+use crate::network::Error;
+use crate::storage::{IfKindResult, Kind, Store, Value};
+
+pub use bounds::{parse_lex_bound, parse_score_bound};
+pub use combine::{Combine, store_result};
+
+pub mod add;
+pub mod blocking_pop;
+pub mod blocking_pop_multiple;
+pub mod card;
+pub mod count;
+pub mod diff;
+pub mod diff_store;
+pub mod increment_by;
+pub mod intersect;
+pub mod intersect_card;
+pub mod intersect_store;
+pub mod pop;
+pub mod pop_multiple;
+pub mod random_members;
+pub mod range;
+pub mod range_store;
+pub mod rank;
+pub mod remove;
+pub mod score;
+pub mod score_multiple;
+pub mod union;
+pub mod union_store;
+
+mod bounds;
+mod combine;
+
+/// Returns the first of the keys holding a sorted set that isn't empty,
+/// looking them up without counting them as changed.
+fn first_nonempty<'k>(
+    store: &mut Store,
+    keys: &'k [Vec<u8>],
+) -> Result<Option<&'k Vec<u8>>, Error> {
+    for key in keys {
+        match store.get_if_kind(Kind::SortedSet, key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) if !sorted_set.is_empty() => {
+                return Ok(Some(key));
+            }
+            IfKindResult::NotMatched => return Err(Error::WrongType),
+            _ => continue,
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,178 @@
+use crate::commands::prelude::*;
+use crate::storage::SortedSet;
+
+pub(super) struct Add {
+    key: Vec<u8>,
+    when: When,
+    compare: Compare,
+    changed: bool,
+    increment: bool,
+    members: Vec<(f64, Vec<u8>)>,
+}
+
+impl Add {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self {
+            key,
+            when: When::Always,
+            compare: Compare::Always,
+            changed: false,
+            increment: false,
+            members: Vec::new(),
+        }
+    }
+
+    /// Returns the command that adds the increment to the member's score.
+    pub fn increment(key: Vec<u8>, increment: f64, member: Vec<u8>) -> Self {
+        Self {
+            increment: true,
+            members: vec![(increment, member)],
+            ..Self::new(key)
+        }
+    }
+
+    /// Adds or updates the members in the set, returning the reply.
+    fn add<'a>(&self, sorted_set: &mut SortedSet) -> Result<Response<'a>, Error> {
+        let mut added = 0;
+        let mut changed = 0;
+        let mut result = None;
+
+        for (score, member) in &self.members {
+            match sorted_set.score(member) {
+                Some(old) => {
+                    if let When::NotExists = self.when {
+                        continue;
+                    }
+
+                    let score = if self.increment { old + score } else { *score };
+                    if score.is_nan() {
+                        return Err(Error::Raw(
+                            b"-ERR resulting score is not a number (NaN)\r\n",
+                        ));
+                    }
+                    let allow = match self.compare {
+                        Compare::Always => true,
+                        Compare::GreaterThan => score > old,
+                        Compare::LessThan => score < old,
+                    };
+                    if !allow {
+                        continue;
+                    }
+
+                    if score != old {
+                        sorted_set.insert(member.clone(), score);
+                        changed += 1;
+                    }
+                    result = Some(score);
+                }
+                None => {
+                    if let When::Exists = self.when {
+                        continue;
+                    }
+
+                    sorted_set.insert(member.clone(), *score);
+                    added += 1;
+                    changed += 1;
+                    result = Some(*score);
+                }
+            }
+        }
+
+        if self.increment {
+            Ok(result.map_or(Response::Null, Response::Double))
+        } else if self.changed {
+            Ok(Response::Usize(changed))
+        } else {
+            Ok(Response::Usize(added))
+        }
+    }
+}
+
+impl ApplyToStore for Add {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => self.add(sorted_set),
+            IfKindResult::NotSet => {
+                let mut sorted_set = SortedSet::new();
+                let response = self.add(&mut sorted_set)?;
+                if !sorted_set.is_empty() {
+                    store.set(&self.key, Value::from(sorted_set));
+                }
+                Ok(response)
+            }
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+enum When {
+    Always,
+    Exists,
+    NotExists,
+}
+
+enum Compare {
+    Always,
+    GreaterThan,
+    LessThan,
+}
+
+pub struct AddParser {}
+
+impl AddParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for AddParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let mut add = Add::new(input.next_bytes()?);
+        let mut when = Vec::new();
+        let mut compare = Vec::new();
+
+        // the options come before the scores and members
+        while let Some(token) = input.peek_token() {
+            match token.as_str() {
+                "NX" => when.push(When::NotExists),
+                "XX" => when.push(When::Exists),
+                "GT" => compare.push(Compare::GreaterThan),
+                "LT" => compare.push(Compare::LessThan),
+                "CH" => add.changed = true,
+                "INCR" => add.increment = true,
+                _ => break,
+            }
+            input.next()?;
+        }
+
+        if when.len() > 1 {
+            return Err(Error::Raw(
+                b"-ERR XX and NX options at the same time are not compatible\r\n",
+            ));
+        }
+        if compare.len() > 1 || (!compare.is_empty() && matches!(when[..], [When::NotExists])) {
+            return Err(Error::Raw(
+                b"-ERR GT, LT, and/or NX options at the same time are not compatible\r\n",
+            ));
+        }
+        if !input.has_next() || !input.len().is_multiple_of(2) {
+            return Err(Error::Syntax);
+        }
+        if add.increment && input.len() > 2 {
+            return Err(Error::Raw(
+                b"-ERR INCR option supports a single increment-element pair\r\n",
+            ));
+        }
+
+        add.when = when.pop().unwrap_or(When::Always);
+        add.compare = compare.pop().unwrap_or(Compare::Always);
+        while input.has_next() {
+            add.members.push((input.next_f64()?, input.next_bytes()?));
+        }
+        Ok(Box::new(add))
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use crate::commands::prelude::*;
+
+use super::first_nonempty;
+
+/// BlockingPop pops the member with the lowest or highest score
+/// from the first of the sorted sets that isn't empty,
+/// blocking until one of them is added to when they are all empty.
+struct BlockingPop {
+    keys: Vec<Vec<u8>>,
+    rev: bool,
+    timeout: Option<Duration>,
+}
+
+impl BlockingPop {
+    pub fn new(keys: Vec<Vec<u8>>, rev: bool, timeout: Option<Duration>) -> Self {
+        Self { keys, rev, timeout }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        // look before taking the sorted set to change so retries leave the keys alone
+        let Some(key) = first_nonempty(store, &self.keys)? else {
+            return Ok(Response::Block(self.timeout));
+        };
+        let IfKindResult::Matched(Value::SortedSet(sorted_set)) =
+            store.get_mut_if_kind(Kind::SortedSet, key)
+        else {
+            unreachable!("checked");
+        };
+
+        let (member, score) = sorted_set.pop(1, self.rev).pop().expect("not empty");
+        if sorted_set.is_empty() {
+            store.remove(key);
+        }
+        Ok(Response::Array(vec![
+            Response::BulkString(key.clone()),
+            Response::BulkString(member),
+            Response::Double(score),
+        ]))
+    }
+}
+
+impl Apply for BlockingPop {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let propagated = context.propagated;
+        let response = self.apply(&mut context.databases[context.session.db()])?;
+
+        // replay pops from the sorted set that was served without blocking
+        if let Response::Array(popped) = &response {
+            if let Some(Response::BulkString(key)) = popped.first() {
+                let command = if self.rev {
+                    b"ZPOPMAX".to_vec()
+                } else {
+                    b"ZPOPMIN".to_vec()
+                };
+                *propagated = Some(vec![vec![command, key.clone()]]);
+            }
+        }
+        Ok(response)
+    }
+
+    fn timeout<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        context.propagate_nothing();
+        Ok(Response::NullArray)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+
+    fn blocking_keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+}
+
+fn try_parse(command: &str, rev: bool, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+    let keys = input.next_multiple(command, "key", input.len().saturating_sub(1).max(1))?;
+    let timeout = input.next_timeout()?;
+
+    Ok(Box::new(BlockingPop::new(keys, rev, timeout)))
+}
+
+pub struct BlockingPopMinParser {}
+
+impl BlockingPopMinParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BlockingPopMinParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        try_parse("BZPOPMIN", false, input)
+    }
+}
+
+pub struct BlockingPopMaxParser {}
+
+impl BlockingPopMaxParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BlockingPopMaxParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        try_parse("BZPOPMAX", true, input)
+    }
+}
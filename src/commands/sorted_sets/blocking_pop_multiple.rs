@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use crate::commands::prelude::*;
+
+use super::pop_multiple::{PopMultiple, PopMultipleParser};
+
+/// BlockingPopMultiple pops members from the first of the sorted sets that isn't empty like ZMPOP,
+/// blocking until one of them is added to when they are all empty.
+struct BlockingPopMultiple {
+    inner: PopMultiple,
+    timeout: Option<Duration>,
+}
+
+impl BlockingPopMultiple {
+    pub fn new(inner: PopMultiple, timeout: Option<Duration>) -> Self {
+        Self { inner, timeout }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match self.inner.apply(store)? {
+            Response::NullArray => Ok(Response::Block(self.timeout)),
+            response => Ok(response),
+        }
+    }
+}
+
+impl Apply for BlockingPopMultiple {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let propagated = context.propagated;
+        let response = self.apply(&mut context.databases[context.session.db()])?;
+
+        // replay pops the same number of members from the sorted set that was served
+        if let Response::Array(popped) = &response {
+            if let [Response::BulkString(key), Response::Array(members)] = popped.as_slice() {
+                *propagated = Some(vec![vec![
+                    self.inner.pop_command(),
+                    key.clone(),
+                    members.len().to_string().into_bytes(),
+                ]]);
+            }
+        }
+        Ok(response)
+    }
+
+    fn timeout<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        context.propagate_nothing();
+        Ok(Response::NullArray)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+
+    fn blocking_keys(&self) -> &[Vec<u8>] {
+        self.inner.keys()
+    }
+}
+
+pub struct BlockingPopMultipleParser {
+    pop: PopMultipleParser,
+}
+
+impl BlockingPopMultipleParser {
+    pub fn new() -> Self {
+        Self {
+            pop: PopMultipleParser::new(),
+        }
+    }
+}
+
+impl TryParse for BlockingPopMultipleParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let timeout = input.next_timeout()?;
+        let inner = self.pop.try_parse_pop("BZMPOP", input)?;
+
+        Ok(Box::new(BlockingPopMultiple::new(inner, timeout)))
+    }
+}
@@ -0,0 +1,30 @@
+use crate::commands::prelude::*;
+use crate::parse::parse_f64;
+use crate::storage::{LexBound, ScoreBound};
+
+/// Parses a score that starts or ends a range, excluding it if it starts with `(`.
+pub fn parse_score_bound(bound: &[u8]) -> Result<ScoreBound, Error> {
+    let (score, exclusive) = match bound.strip_prefix(b"(") {
+        Some(score) => (score, true),
+        None => (bound, false),
+    };
+
+    Ok(ScoreBound {
+        score: parse_f64(score).map_err(|_| Error::Raw(b"-ERR min or max is not a float\r\n"))?,
+        exclusive,
+    })
+}
+
+/// Parses a member that starts or ends a range,
+/// which must start with `[` or `(` unless it's `-` or `+` for either end of the set.
+pub fn parse_lex_bound(bound: &[u8]) -> Result<LexBound, Error> {
+    match bound {
+        b"-" => Ok(LexBound::Min),
+        b"+" => Ok(LexBound::Max),
+        [b'[', member @ ..] => Ok(LexBound::Inclusive(member.to_vec())),
+        [b'(', member @ ..] => Ok(LexBound::Exclusive(member.to_vec())),
+        _ => Err(Error::Raw(
+            b"-ERR min or max not valid string range item\r\n",
+        )),
+    }
+}
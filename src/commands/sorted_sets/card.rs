@@ -0,0 +1,37 @@
+use crate::commands::prelude::*;
+
+struct Card {
+    key: Vec<u8>,
+}
+
+impl Card {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self { key }
+    }
+}
+
+impl ApplyToStore for Card {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => {
+                Ok(Response::Usize(sorted_set.len()))
+            }
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct CardParser {}
+
+impl CardParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for CardParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Card::new(input.next_bytes()?)))
+    }
+}
@@ -0,0 +1,159 @@
+use crate::commands::prelude::*;
+use crate::parse::parse_f64;
+use crate::storage::{Aggregate, SortedSet, SortedSetOp, zdiff, zinter, zunion};
+
+/// Combine holds the keys and options of the commands
+/// that combine sorted sets, or plain sets whose members score 1.
+pub struct Combine {
+    keys: Vec<Vec<u8>>,
+    weights: Vec<f64>,
+    aggregate: Aggregate,
+    with_scores: bool,
+}
+
+impl Combine {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
+        Self {
+            weights: vec![1.0; keys.len()],
+            keys,
+            aggregate: Aggregate::Sum,
+            with_scores: false,
+        }
+    }
+
+    /// Returns WEIGHTS and AGGREGATE if `weighted`, and WITHSCORES if `with_scores`.
+    pub fn options(weighted: bool, with_scores: bool) -> Options<Combine> {
+        let mut options: Options<Combine> = Vec::new();
+        if weighted {
+            options.push((vec!["WEIGHTS"], Combine::try_weights));
+            options.push((vec!["AGGREGATE"], Combine::try_aggregate));
+        }
+        if with_scores {
+            options.push((vec!["WITHSCORES"], Combine::try_with_scores));
+        }
+        options
+    }
+
+    fn try_weights(combine: &mut Combine, _: &str, input: &mut Input) -> Result<(), Error> {
+        for weight in combine.weights.iter_mut() {
+            *weight = parse_f64(input.next()?)
+                .map_err(|_| Error::Raw(b"-ERR weight value is not a float\r\n"))?;
+        }
+        Ok(())
+    }
+
+    fn try_aggregate(combine: &mut Combine, _: &str, input: &mut Input) -> Result<(), Error> {
+        combine.aggregate = match input.next_token()?.as_str() {
+            "SUM" => Aggregate::Sum,
+            "MIN" => Aggregate::Min,
+            "MAX" => Aggregate::Max,
+            _ => return Err(Error::Syntax),
+        };
+        Ok(())
+    }
+
+    fn try_with_scores(combine: &mut Combine, _: &str, _: &mut Input) -> Result<(), Error> {
+        combine.with_scores = true;
+        Ok(())
+    }
+
+    /// Parses the number of keys, the keys and the options.
+    pub fn try_parse(
+        command: &str,
+        options: &Options<Combine>,
+        input: &mut Input,
+    ) -> Result<Combine, Error> {
+        let count = input.next_u64()? as usize;
+        if count == 0 {
+            return Err(Error::String(format!(
+                "ERR at least 1 input key is needed for '{}' command",
+                command.to_lowercase()
+            )));
+        }
+        let keys = input.next_multiple(command, "key", count)?;
+
+        parse_options(command, options, input, Combine::new(keys))
+    }
+
+    pub fn diff(&self, store: &mut Store) -> SortedSetOp {
+        zdiff(store, &self.keys)
+    }
+
+    pub fn intersect(&self, store: &mut Store) -> SortedSetOp {
+        zinter(store, &self.keys, &self.weights, self.aggregate)
+    }
+
+    pub fn union(&self, store: &mut Store) -> SortedSetOp {
+        zunion(store, &self.keys, &self.weights, self.aggregate)
+    }
+
+    /// Replies with the members of the result from the lowest score.
+    pub fn reply<'a>(&self, result: SortedSetOp) -> Result<Response<'a>, Error> {
+        let sorted_set = match result {
+            SortedSetOp::New(sorted_set) => sorted_set,
+            SortedSetOp::Empty => return Ok(Response::EmptyList),
+            SortedSetOp::WrongType => return Err(Error::WrongType),
+        };
+
+        if self.with_scores {
+            Ok(Response::ScoredList(
+                sorted_set
+                    .iter()
+                    .map(|(member, score)| (member.clone(), score))
+                    .collect(),
+            ))
+        } else {
+            Ok(Response::List(
+                sorted_set
+                    .iter()
+                    .map(|(member, _)| member.clone())
+                    .collect(),
+            ))
+        }
+    }
+}
+
+/// Stores the result at the destination in place of any key already there, along with its TTL,
+/// removing it if the result is empty, and replies with the number of members stored.
+pub fn store_result<'a>(
+    store: &mut Store,
+    destination: &[u8],
+    result: SortedSetOp,
+) -> Result<Response<'a>, Error> {
+    let sorted_set = match result {
+        SortedSetOp::New(sorted_set) => sorted_set,
+        SortedSetOp::Empty => SortedSet::new(),
+        SortedSetOp::WrongType => return Err(Error::WrongType),
+    };
+
+    let len = sorted_set.len();
+    if sorted_set.is_empty() {
+        store.remove(destination);
+    } else {
+        store.set_entry(destination, Value::from(sorted_set), None);
+    }
+    Ok(Response::Usize(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+
+    use super::*;
+
+    #[test]
+    fn storing_replaces_the_destination_ttl() {
+        let mut store = Store::new();
+        store.set(b"destination", Value::Integer(1));
+        store.expire_at(b"destination", &(Utc::now() + TimeDelta::seconds(100)));
+
+        let sorted_set: SortedSet = [(b"member".to_vec(), 1.0)].into_iter().collect();
+        store_result(&mut store, b"destination", SortedSetOp::New(sorted_set)).unwrap();
+
+        assert!(matches!(
+            store.get(b"destination"),
+            Some(Value::SortedSet(_))
+        ));
+        assert_eq!(store.expires(b"destination"), None);
+    }
+}
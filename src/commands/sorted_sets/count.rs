@@ -0,0 +1,46 @@
+use crate::commands::prelude::*;
+use crate::storage::ScoreBound;
+
+use super::parse_score_bound;
+
+struct Count {
+    key: Vec<u8>,
+    min: ScoreBound,
+    max: ScoreBound,
+}
+
+impl Count {
+    pub fn new(key: Vec<u8>, min: ScoreBound, max: ScoreBound) -> Self {
+        Self { key, min, max }
+    }
+}
+
+impl ApplyToStore for Count {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => {
+                Ok(Response::Usize(sorted_set.count(&self.min, &self.max)))
+            }
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct CountParser {}
+
+impl CountParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for CountParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Count::new(
+            input.next_bytes()?,
+            parse_score_bound(input.next()?)?,
+            parse_score_bound(input.next()?)?,
+        )))
+    }
+}
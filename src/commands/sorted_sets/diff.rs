@@ -0,0 +1,39 @@
+use crate::commands::prelude::*;
+
+use super::Combine;
+
+struct Diff {
+    combine: Combine,
+}
+
+impl Diff {
+    pub fn new(combine: Combine) -> Self {
+        Self { combine }
+    }
+}
+
+impl ApplyToStore for Diff {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        self.combine.reply(self.combine.diff(store))
+    }
+}
+
+pub struct DiffParser {
+    options: Options<Combine>,
+}
+
+impl DiffParser {
+    pub fn new() -> Self {
+        Self {
+            options: Combine::options(false, true),
+        }
+    }
+}
+
+impl TryParse for DiffParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let combine = Combine::try_parse("ZDIFF", &self.options, input)?;
+
+        Ok(Box::new(Diff::new(combine)))
+    }
+}
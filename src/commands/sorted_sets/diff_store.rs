@@ -0,0 +1,49 @@
+use crate::commands::prelude::*;
+
+use super::{Combine, store_result};
+
+struct DiffStore {
+    destination: Vec<u8>,
+    combine: Combine,
+}
+
+impl DiffStore {
+    pub fn new(destination: Vec<u8>, combine: Combine) -> Self {
+        Self {
+            destination,
+            combine,
+        }
+    }
+}
+
+impl ApplyToStore for DiffStore {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let result = self.combine.diff(store);
+        store_result(store, &self.destination, result)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct DiffStoreParser {
+    options: Options<Combine>,
+}
+
+impl DiffStoreParser {
+    pub fn new() -> Self {
+        Self {
+            options: Combine::options(false, false),
+        }
+    }
+}
+
+impl TryParse for DiffStoreParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let destination = input.next_bytes()?;
+        let combine = Combine::try_parse("ZDIFFSTORE", &self.options, input)?;
+
+        Ok(Box::new(DiffStore::new(destination, combine)))
+    }
+}
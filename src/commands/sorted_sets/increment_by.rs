@@ -0,0 +1,21 @@
+use crate::commands::prelude::*;
+
+use super::add::Add;
+
+pub struct IncrementByParser {}
+
+impl IncrementByParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for IncrementByParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Add::increment(
+            input.next_bytes()?,
+            input.next_f64()?,
+            input.next_bytes()?,
+        )))
+    }
+}
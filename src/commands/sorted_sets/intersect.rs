@@ -0,0 +1,39 @@
+use crate::commands::prelude::*;
+
+use super::Combine;
+
+struct Intersect {
+    combine: Combine,
+}
+
+impl Intersect {
+    pub fn new(combine: Combine) -> Self {
+        Self { combine }
+    }
+}
+
+impl ApplyToStore for Intersect {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        self.combine.reply(self.combine.intersect(store))
+    }
+}
+
+pub struct IntersectParser {
+    options: Options<Combine>,
+}
+
+impl IntersectParser {
+    pub fn new() -> Self {
+        Self {
+            options: Combine::options(true, true),
+        }
+    }
+}
+
+impl TryParse for IntersectParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let combine = Combine::try_parse("ZINTER", &self.options, input)?;
+
+        Ok(Box::new(Intersect::new(combine)))
+    }
+}
@@ -0,0 +1,60 @@
+use crate::commands::prelude::*;
+use crate::storage::{SetOpCard, zinter_card};
+
+struct IntersectCard {
+    keys: Vec<Vec<u8>>,
+    limit: usize,
+}
+
+impl IntersectCard {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
+        Self {
+            keys,
+            limit: usize::MAX,
+        }
+    }
+}
+
+impl ApplyToStore for IntersectCard {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match zinter_card(store, &self.keys, self.limit) {
+            SetOpCard::Count(count) => Ok(Response::Usize(count)),
+            SetOpCard::Empty => Ok(Response::Usize(0)),
+            SetOpCard::WrongType => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct IntersectCardParser {
+    options: Options<IntersectCard>,
+}
+
+impl IntersectCardParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![(vec!["LIMIT"], IntersectCardParser::try_limit)],
+        }
+    }
+
+    fn try_limit(card: &mut IntersectCard, _: &str, input: &mut Input) -> Result<(), Error> {
+        let limit = input.next_u64()? as usize;
+        if limit > 0 {
+            card.limit = limit;
+        }
+        Ok(())
+    }
+}
+
+impl TryParse for IntersectCardParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let count = input.next_u64_min(1)? as usize;
+        let keys = input.next_multiple("ZINTERCARD", "key", count)?;
+
+        Ok(Box::new(parse_options(
+            "ZINTERCARD",
+            &self.options,
+            input,
+            IntersectCard::new(keys),
+        )?))
+    }
+}
@@ -0,0 +1,49 @@
+use crate::commands::prelude::*;
+
+use super::{Combine, store_result};
+
+struct IntersectStore {
+    destination: Vec<u8>,
+    combine: Combine,
+}
+
+impl IntersectStore {
+    pub fn new(destination: Vec<u8>, combine: Combine) -> Self {
+        Self {
+            destination,
+            combine,
+        }
+    }
+}
+
+impl ApplyToStore for IntersectStore {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let result = self.combine.intersect(store);
+        store_result(store, &self.destination, result)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct IntersectStoreParser {
+    options: Options<Combine>,
+}
+
+impl IntersectStoreParser {
+    pub fn new() -> Self {
+        Self {
+            options: Combine::options(true, false),
+        }
+    }
+}
+
+impl TryParse for IntersectStoreParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let destination = input.next_bytes()?;
+        let combine = Combine::try_parse("ZINTERSTORE", &self.options, input)?;
+
+        Ok(Box::new(IntersectStore::new(destination, combine)))
+    }
+}
@@ -0,0 +1,87 @@
+use crate::commands::prelude::*;
+
+struct Pop {
+    key: Vec<u8>,
+    count: Option<usize>,
+    rev: bool,
+}
+
+impl Pop {
+    pub fn new(key: Vec<u8>, count: Option<usize>, rev: bool) -> Self {
+        Self { key, count, rev }
+    }
+}
+
+impl ApplyToStore for Pop {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => {
+                let popped = sorted_set.pop(self.count.unwrap_or(1), self.rev);
+                if sorted_set.is_empty() {
+                    store.remove(&self.key);
+                }
+
+                if self.count.is_some() {
+                    return Ok(Response::ScoredList(popped));
+                }
+                match popped.into_iter().next() {
+                    Some((member, score)) => Ok(Response::Array(vec![
+                        Response::BulkString(member),
+                        Response::Double(score),
+                    ])),
+                    None => Ok(Response::EmptyList),
+                }
+            }
+            IfKindResult::NotSet => Ok(Response::EmptyList),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct PopMinParser {}
+
+impl PopMinParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PopMinParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        Ok(Box::new(Pop::new(key, try_count(input)?, false)))
+    }
+}
+
+pub struct PopMaxParser {}
+
+impl PopMaxParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PopMaxParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        Ok(Box::new(Pop::new(key, try_count(input)?, true)))
+    }
+}
+
+fn try_count(input: &mut Input) -> Result<Option<usize>, Error> {
+    if !input.has_next() {
+        return Ok(None);
+    }
+
+    let count = input.next_i64()?;
+    if count < 0 {
+        return Err(Error::Raw(
+            b"-ERR value is out of range, must be positive\r\n",
+        ));
+    }
+    Ok(Some(count as usize))
+}
@@ -0,0 +1,112 @@
+use crate::commands::prelude::*;
+
+use super::first_nonempty;
+
+pub(super) struct PopMultiple {
+    keys: Vec<Vec<u8>>,
+    rev: bool,
+    count: usize,
+}
+
+impl PopMultiple {
+    pub fn new(keys: Vec<Vec<u8>>, rev: bool) -> Self {
+        Self {
+            keys,
+            rev,
+            count: 1,
+        }
+    }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    /// Returns the command that pops from the same end of a single sorted set.
+    pub fn pop_command(&self) -> Vec<u8> {
+        if self.rev {
+            b"ZPOPMAX".to_vec()
+        } else {
+            b"ZPOPMIN".to_vec()
+        }
+    }
+}
+
+impl ApplyToStore for PopMultiple {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        // look before taking the sorted set to change so BZMPOP retries leave the keys alone
+        let Some(key) = first_nonempty(store, &self.keys)? else {
+            return Ok(Response::NullArray);
+        };
+        let IfKindResult::Matched(Value::SortedSet(sorted_set)) =
+            store.get_mut_if_kind(Kind::SortedSet, key)
+        else {
+            unreachable!("checked");
+        };
+
+        let popped = sorted_set.pop(self.count, self.rev);
+        if sorted_set.is_empty() {
+            store.remove(key);
+        }
+        Ok(Response::Array(vec![
+            Response::BulkString(key.clone()),
+            Response::Array(
+                popped
+                    .into_iter()
+                    .map(|(member, score)| {
+                        Response::Array(vec![Response::BulkString(member), Response::Double(score)])
+                    })
+                    .collect(),
+            ),
+        ]))
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct PopMultipleParser {
+    options: Options<PopMultiple>,
+}
+
+impl PopMultipleParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![(vec!["COUNT"], PopMultipleParser::try_count)],
+        }
+    }
+
+    fn try_count(pop: &mut PopMultiple, _token: &str, input: &mut Input) -> Result<(), Error> {
+        let count = input.next_usize()?;
+
+        if count == 0 {
+            return Err(Error::Raw(b"-ERR count should be greater than 0\r\n"));
+        }
+
+        pop.count = count;
+        Ok(())
+    }
+
+    /// Parses the keys, MIN or MAX and options shared with BZMPOP.
+    pub(super) fn try_parse_pop(
+        &self,
+        command: &str,
+        input: &mut Input,
+    ) -> Result<PopMultiple, Error> {
+        let key_count = input.next_u64_min(1)? as usize;
+        let keys = input.next_multiple(command, "key", key_count)?;
+        let rev = match input.next_token()?.as_str() {
+            "MIN" => false,
+            "MAX" => true,
+            _ => return Err(Error::Syntax),
+        };
+
+        parse_options(command, &self.options, input, PopMultiple::new(keys, rev))
+    }
+}
+
+impl TryParse for PopMultipleParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(self.try_parse_pop("ZMPOP", input)?))
+    }
+}
@@ -0,0 +1,119 @@
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::{Rng, thread_rng};
+
+use crate::commands::prelude::*;
+
+/// The most members to make room for up front when repeats are allowed.
+const MAX_PREALLOCATED: usize = 1024;
+
+struct RandomMembers {
+    key: Vec<u8>,
+    count: Option<i64>,
+    with_scores: bool,
+}
+
+impl RandomMembers {
+    pub fn new(key: Vec<u8>, count: Option<i64>, with_scores: bool) -> Self {
+        Self {
+            key,
+            count,
+            with_scores,
+        }
+    }
+}
+
+impl ApplyToStore for RandomMembers {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let sorted_set = match store.get_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) if !sorted_set.is_empty() => {
+                sorted_set
+            }
+            IfKindResult::Matched(Value::SortedSet(_)) | IfKindResult::NotSet => {
+                return match self.count {
+                    None => Ok(Response::Null),
+                    Some(_) => Ok(Response::EmptyList),
+                };
+            }
+            _ => return Err(Error::WrongType),
+        };
+        let mut rng = thread_rng();
+        // the skiplist finds the member at a rank without walking the ones before it
+        let mut pick = || {
+            let rank = rng.gen_range(0..sorted_set.len());
+            sorted_set
+                .iter_from(rank, false)
+                .next()
+                .expect("rank in range")
+        };
+
+        let count = match self.count {
+            None => return Ok(Response::BulkString(pick().0.clone())),
+            Some(0) => return Ok(Response::EmptyList),
+            Some(count) => count,
+        };
+
+        // a negative count may return the same member more than once
+        let chosen = if count < 0 {
+            let count = count.unsigned_abs() as usize;
+            let mut chosen = Vec::with_capacity(count.min(MAX_PREALLOCATED));
+            for _ in 0..count {
+                chosen.push(pick());
+            }
+            chosen
+        } else {
+            let mut pool = sorted_set.iter().collect_vec();
+            pool.shuffle(&mut thread_rng());
+            pool.truncate(count as usize);
+            pool
+        };
+
+        if self.with_scores {
+            Ok(Response::ScoredList(
+                chosen
+                    .into_iter()
+                    .map(|(member, score)| (member.clone(), score))
+                    .collect(),
+            ))
+        } else {
+            Ok(Response::List(
+                chosen
+                    .into_iter()
+                    .map(|(member, _)| member.clone())
+                    .collect(),
+            ))
+        }
+    }
+}
+
+pub struct RandomMembersParser {}
+
+impl RandomMembersParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for RandomMembersParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        if !input.has_next() {
+            return Ok(Box::new(RandomMembers::new(key, None, false)));
+        }
+
+        let count = input.next_i64()?;
+        if count < -(i64::MAX / 2) {
+            return Err(Error::Raw(b"-ERR value is out of range\r\n"));
+        }
+        let with_scores = match input.has_next() {
+            true if input.next_token()? == "WITHSCORES" => true,
+            true => return Err(Error::Syntax),
+            false => false,
+        };
+        if input.has_next() {
+            return Err(Error::Syntax);
+        }
+
+        Ok(Box::new(RandomMembers::new(key, Some(count), with_scores)))
+    }
+}
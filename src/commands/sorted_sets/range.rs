@@ -0,0 +1,193 @@
+use crate::commands::prelude::*;
+use crate::parse::parse_i64;
+use crate::storage::{LexBound, ScoreBound, SortedSet};
+
+use super::{parse_lex_bound, parse_score_bound};
+
+pub(super) struct Range {
+    key: Vec<u8>,
+    bounds: Bounds,
+    rev: bool,
+    limit: Option<(i64, i64)>,
+    with_scores: bool,
+}
+
+impl Range {
+    pub fn try_new(
+        key: Vec<u8>,
+        start: &[u8],
+        stop: &[u8],
+        options: RangeOptions,
+    ) -> Result<Self, Error> {
+        // the highest score or member comes first in reverse
+        let (min, max) = if options.rev {
+            (stop, start)
+        } else {
+            (start, stop)
+        };
+        let bounds = match options.by {
+            By::Rank if options.limit.is_some() => {
+                return Err(Error::Raw(
+                    b"-ERR syntax error, LIMIT is only supported in combination with either BYSCORE or BYLEX\r\n",
+                ))
+            }
+            By::Lex if options.with_scores => {
+                return Err(Error::Raw(
+                    b"-ERR syntax error, WITHSCORES not supported in combination with BYLEX\r\n",
+                ))
+            }
+            By::Rank => Bounds::Rank(parse_i64(start)?, parse_i64(stop)?),
+            By::Score => Bounds::Score(parse_score_bound(min)?, parse_score_bound(max)?),
+            By::Lex => Bounds::Lex(parse_lex_bound(min)?, parse_lex_bound(max)?),
+        };
+
+        Ok(Self {
+            key,
+            bounds,
+            rev: options.rev,
+            limit: options.limit,
+            with_scores: options.with_scores,
+        })
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Returns the members in the range in the order to reply with them.
+    pub fn members<'a>(&'a self, sorted_set: &'a SortedSet) -> Vec<(&'a Vec<u8>, f64)> {
+        let (offset, count) = match self.limit {
+            Some((offset, _)) if offset < 0 => return Vec::new(),
+            Some((offset, count)) if count >= 0 => (offset as usize, count as usize),
+            Some((offset, _)) => (offset as usize, usize::MAX),
+            None => (0, usize::MAX),
+        };
+
+        match &self.bounds {
+            Bounds::Rank(start, stop) => {
+                let range = clamp_range(sorted_set.len(), *start, *stop);
+                sorted_set
+                    .iter_from(range.start, self.rev)
+                    .take(range.len())
+                    .collect()
+            }
+            Bounds::Score(min, max) => sorted_set
+                .range_by_score(min, max, self.rev)
+                .skip(offset)
+                .take(count)
+                .collect(),
+            Bounds::Lex(min, max) => sorted_set
+                .range_by_lex(min, max, self.rev)
+                .skip(offset)
+                .take(count)
+                .collect(),
+        }
+    }
+}
+
+impl ApplyToStore for Range {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => {
+                let members = self.members(sorted_set).into_iter();
+                if self.with_scores {
+                    Ok(Response::ScoredList(
+                        members
+                            .map(|(member, score)| (member.clone(), score))
+                            .collect(),
+                    ))
+                } else {
+                    Ok(Response::List(
+                        members.map(|(member, _)| member.clone()).collect(),
+                    ))
+                }
+            }
+            IfKindResult::NotSet => Ok(Response::EmptyList),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+enum Bounds {
+    Rank(i64, i64),
+    Score(ScoreBound, ScoreBound),
+    Lex(LexBound, LexBound),
+}
+
+#[derive(Default)]
+enum By {
+    #[default]
+    Rank,
+    Score,
+    Lex,
+}
+
+#[derive(Default)]
+pub(super) struct RangeOptions {
+    by: By,
+    rev: bool,
+    limit: Option<(i64, i64)>,
+    with_scores: bool,
+}
+
+impl RangeOptions {
+    /// Returns the options of ZRANGE, leaving out WITHSCORES for ZRANGESTORE.
+    pub fn options(with_scores: bool) -> Options<RangeOptions> {
+        let mut options: Options<RangeOptions> = vec![
+            (vec!["BYSCORE", "BYLEX"], RangeOptions::try_by),
+            (vec!["REV"], RangeOptions::try_rev),
+            (vec!["LIMIT"], RangeOptions::try_limit),
+        ];
+        if with_scores {
+            options.push((vec!["WITHSCORES"], RangeOptions::try_with_scores));
+        }
+        options
+    }
+
+    fn try_by(options: &mut RangeOptions, token: &str, _: &mut Input) -> Result<(), Error> {
+        options.by = match token {
+            "BYSCORE" => By::Score,
+            "BYLEX" => By::Lex,
+            _ => panic!("unexpected token"),
+        };
+        Ok(())
+    }
+
+    fn try_rev(options: &mut RangeOptions, _: &str, _: &mut Input) -> Result<(), Error> {
+        options.rev = true;
+        Ok(())
+    }
+
+    fn try_limit(options: &mut RangeOptions, _: &str, input: &mut Input) -> Result<(), Error> {
+        options.limit = Some((input.next_i64()?, input.next_i64()?));
+        Ok(())
+    }
+
+    fn try_with_scores(options: &mut RangeOptions, _: &str, _: &mut Input) -> Result<(), Error> {
+        options.with_scores = true;
+        Ok(())
+    }
+}
+
+pub struct RangeParser {
+    options: Options<RangeOptions>,
+}
+
+impl RangeParser {
+    pub fn new() -> Self {
+        Self {
+            options: RangeOptions::options(true),
+        }
+    }
+}
+
+impl TryParse for RangeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let start = input.next_bytes()?;
+        let stop = input.next_bytes()?;
+        let options = parse_options("ZRANGE", &self.options, input, RangeOptions::default())?;
+
+        Ok(Box::new(Range::try_new(key, &start, &stop, options)?))
+    }
+}
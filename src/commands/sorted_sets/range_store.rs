@@ -0,0 +1,64 @@
+use crate::commands::prelude::*;
+use crate::storage::{SortedSet, SortedSetOp};
+
+use super::range::{Range, RangeOptions};
+use super::store_result;
+
+struct RangeStore {
+    destination: Vec<u8>,
+    range: Range,
+}
+
+impl RangeStore {
+    pub fn new(destination: Vec<u8>, range: Range) -> Self {
+        Self { destination, range }
+    }
+}
+
+impl ApplyToStore for RangeStore {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let members = match store.get_if_kind(Kind::SortedSet, self.range.key()) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => self
+                .range
+                .members(sorted_set)
+                .into_iter()
+                .map(|(member, score)| (member.clone(), score))
+                .collect::<SortedSet>(),
+            IfKindResult::NotSet => SortedSet::new(),
+            _ => return Err(Error::WrongType),
+        };
+
+        store_result(store, &self.destination, SortedSetOp::New(members))
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct RangeStoreParser {
+    options: Options<RangeOptions>,
+}
+
+impl RangeStoreParser {
+    pub fn new() -> Self {
+        Self {
+            options: RangeOptions::options(false),
+        }
+    }
+}
+
+impl TryParse for RangeStoreParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let destination = input.next_bytes()?;
+        let source = input.next_bytes()?;
+        let start = input.next_bytes()?;
+        let stop = input.next_bytes()?;
+        let options = parse_options("ZRANGESTORE", &self.options, input, RangeOptions::default())?;
+
+        Ok(Box::new(RangeStore::new(
+            destination,
+            Range::try_new(source, &start, &stop, options)?,
+        )))
+    }
+}
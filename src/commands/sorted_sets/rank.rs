@@ -0,0 +1,116 @@
+use crate::commands::prelude::*;
+
+struct Rank {
+    key: Vec<u8>,
+    member: Vec<u8>,
+    rev: bool,
+    with_score: bool,
+}
+
+impl Rank {
+    pub fn new(key: Vec<u8>, member: Vec<u8>, rev: bool) -> Self {
+        Self {
+            key,
+            member,
+            rev,
+            with_score: false,
+        }
+    }
+
+    fn missing<'a>(&self) -> Response<'a> {
+        if self.with_score {
+            Response::NullArray
+        } else {
+            Response::Null
+        }
+    }
+}
+
+impl ApplyToStore for Rank {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => {
+                let rank = if self.rev {
+                    sorted_set.rev_rank(&self.member)
+                } else {
+                    sorted_set.rank(&self.member)
+                };
+                let Some(rank) = rank else {
+                    return Ok(self.missing());
+                };
+
+                if self.with_score {
+                    Ok(Response::Array(vec![
+                        Response::Usize(rank),
+                        Response::Double(sorted_set.score(&self.member).expect("ranked")),
+                    ]))
+                } else {
+                    Ok(Response::Usize(rank))
+                }
+            }
+            IfKindResult::NotSet => Ok(self.missing()),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct RankParser {
+    options: Options<Rank>,
+}
+
+impl RankParser {
+    pub fn new() -> Self {
+        Self {
+            options: rank_options(),
+        }
+    }
+}
+
+impl TryParse for RankParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let member = input.next_bytes()?;
+
+        Ok(Box::new(parse_options(
+            "ZRANK",
+            &self.options,
+            input,
+            Rank::new(key, member, false),
+        )?))
+    }
+}
+
+pub struct RevRankParser {
+    options: Options<Rank>,
+}
+
+impl RevRankParser {
+    pub fn new() -> Self {
+        Self {
+            options: rank_options(),
+        }
+    }
+}
+
+impl TryParse for RevRankParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let member = input.next_bytes()?;
+
+        Ok(Box::new(parse_options(
+            "ZREVRANK",
+            &self.options,
+            input,
+            Rank::new(key, member, true),
+        )?))
+    }
+}
+
+fn rank_options() -> Options<Rank> {
+    vec![(vec!["WITHSCORE"], try_with_score)]
+}
+
+fn try_with_score(rank: &mut Rank, _token: &str, _input: &mut Input) -> Result<(), Error> {
+    rank.with_score = true;
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::commands::prelude::*;
+
+struct Remove {
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
+}
+
+impl Remove {
+    pub fn new(key: Vec<u8>, members: Vec<Vec<u8>>) -> Self {
+        Self { key, members }
+    }
+}
+
+impl ApplyToStore for Remove {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => {
+                let removed = self
+                    .members
+                    .iter()
+                    .filter(|member| sorted_set.remove(member).is_some())
+                    .count();
+                if sorted_set.is_empty() {
+                    store.remove(&self.key);
+                }
+                Ok(Response::Usize(removed))
+            }
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct RemoveParser {}
+
+impl RemoveParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for RemoveParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        if !input.has_next() {
+            return Err(Error::MissingArgument(
+                "ZREM".to_string(),
+                "member".to_string(),
+            ));
+        }
+
+        Ok(Box::new(Remove::new(key, input.rest()?)))
+    }
+}
@@ -0,0 +1,41 @@
+use crate::commands::prelude::*;
+
+struct Score {
+    key: Vec<u8>,
+    member: Vec<u8>,
+}
+
+impl Score {
+    pub fn new(key: Vec<u8>, member: Vec<u8>) -> Self {
+        Self { key, member }
+    }
+}
+
+impl ApplyToStore for Score {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => Ok(sorted_set
+                .score(&self.member)
+                .map_or(Response::Null, Response::Double)),
+            IfKindResult::NotSet => Ok(Response::Null),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct ScoreParser {}
+
+impl ScoreParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ScoreParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Score::new(
+            input.next_bytes()?,
+            input.next_bytes()?,
+        )))
+    }
+}
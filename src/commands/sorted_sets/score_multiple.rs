@@ -0,0 +1,55 @@
+use crate::commands::prelude::*;
+
+struct ScoreMultiple {
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
+}
+
+impl ScoreMultiple {
+    pub fn new(key: Vec<u8>, members: Vec<Vec<u8>>) -> Self {
+        Self { key, members }
+    }
+}
+
+impl ApplyToStore for ScoreMultiple {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::SortedSet, &self.key) {
+            IfKindResult::Matched(Value::SortedSet(sorted_set)) => Ok(Response::Array(
+                self.members
+                    .iter()
+                    .map(|member| {
+                        sorted_set
+                            .score(member)
+                            .map_or(Response::Null, Response::Double)
+                    })
+                    .collect(),
+            )),
+            IfKindResult::NotSet => Ok(Response::Array(
+                self.members.iter().map(|_| Response::Null).collect(),
+            )),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct ScoreMultipleParser {}
+
+impl ScoreMultipleParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ScoreMultipleParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        if !input.has_next() {
+            return Err(Error::MissingArgument(
+                "ZMSCORE".to_string(),
+                "member".to_string(),
+            ));
+        }
+
+        Ok(Box::new(ScoreMultiple::new(key, input.rest()?)))
+    }
+}
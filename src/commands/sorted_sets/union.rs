@@ -0,0 +1,39 @@
+use crate::commands::prelude::*;
+
+use super::Combine;
+
+struct Union {
+    combine: Combine,
+}
+
+impl Union {
+    pub fn new(combine: Combine) -> Self {
+        Self { combine }
+    }
+}
+
+impl ApplyToStore for Union {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        self.combine.reply(self.combine.union(store))
+    }
+}
+
+pub struct UnionParser {
+    options: Options<Combine>,
+}
+
+impl UnionParser {
+    pub fn new() -> Self {
+        Self {
+            options: Combine::options(true, true),
+        }
+    }
+}
+
+impl TryParse for UnionParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let combine = Combine::try_parse("ZUNION", &self.options, input)?;
+
+        Ok(Box::new(Union::new(combine)))
+    }
+}
@@ -0,0 +1,49 @@
+use crate::commands::prelude::*;
+
+use super::{Combine, store_result};
+
+struct UnionStore {
+    destination: Vec<u8>,
+    combine: Combine,
+}
+
+impl UnionStore {
+    pub fn new(destination: Vec<u8>, combine: Combine) -> Self {
+        Self {
+            destination,
+            combine,
+        }
+    }
+}
+
+impl ApplyToStore for UnionStore {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let result = self.combine.union(store);
+        store_result(store, &self.destination, result)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct UnionStoreParser {
+    options: Options<Combine>,
+}
+
+impl UnionStoreParser {
+    pub fn new() -> Self {
+        Self {
+            options: Combine::options(true, false),
+        }
+    }
+}
+
+impl TryParse for UnionStoreParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let destination = input.next_bytes()?;
+        let combine = Combine::try_parse("ZUNIONSTORE", &self.options, input)?;
+
+        Ok(Box::new(UnionStore::new(destination, combine)))
+    }
+}
@@ -0,0 +1,35 @@
+pub use entries::{entries_response, entry_response};
+pub use groups::{
+    ack_args, claim_args, key_required, no_group, no_group_for_key, parse_entries_read,
+    parse_group_id, stream_with_group,
+};
+pub use ids::{invalid_id, parse_id, parse_range_end, parse_range_start};
+pub use trimming::Trimming;
+
+pub mod ack;
+pub mod add;
+pub mod auto_claim;
+pub mod claim;
+pub mod del;
+pub mod group;
+pub mod group_create;
+pub mod group_create_consumer;
+pub mod group_del_consumer;
+pub mod group_destroy;
+pub mod group_set_id;
+pub mod info;
+pub mod info_consumers;
+pub mod info_groups;
+pub mod info_stream;
+pub mod len;
+pub mod pending;
+pub mod range;
+pub mod read;
+pub mod read_group;
+pub mod set_id;
+pub mod trim;
+
+mod entries;
+mod groups;
+mod ids;
+mod trimming;
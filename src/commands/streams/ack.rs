@@ -0,0 +1,59 @@
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::parse_id;
+
+struct Ack {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    ids: Vec<StreamId>,
+}
+
+impl Ack {
+    pub fn new(key: Vec<u8>, group: Vec<u8>, ids: Vec<StreamId>) -> Self {
+        Self { key, group, ids }
+    }
+}
+
+impl ApplyToStore for Ack {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => match stream.group_mut(&self.group) {
+                Some(group) => Ok(Response::Usize(
+                    self.ids.iter().filter(|id| group.ack(id)).count(),
+                )),
+                None => Ok(Response::Zero),
+            },
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct AckParser {}
+
+impl AckParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for AckParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let group = input.next_bytes()?;
+        if !input.has_next() {
+            return Err(Error::MissingArgument("XACK".to_string(), "id".to_string()));
+        }
+
+        let mut ids = Vec::with_capacity(input.len());
+        while input.has_next() {
+            ids.push(parse_id(input.next()?)?);
+        }
+        Ok(Box::new(Ack::new(key, group, ids)))
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::Utc;
+
+use crate::commands::prelude::*;
+use crate::storage::{Fields, Stream, StreamId};
+
+use super::{invalid_id, Trimming};
+
+struct Add {
+    key: Vec<u8>,
+    make_stream: bool,
+    trimming: Option<Trimming>,
+    id: AddId,
+    fields: Fields,
+}
+
+impl Add {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self {
+            key,
+            make_stream: true,
+            trimming: None,
+            id: AddId::Auto,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Appends the entry and trims the stream, returning the entry's ID,
+    /// or `None` if the stream doesn't exist and NOMKSTREAM was given.
+    fn add(&self, store: &mut Store) -> Result<Option<StreamId>, Error> {
+        let stream = match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream,
+            IfKindResult::NotSet if self.make_stream => {
+                let mut stream = Stream::new();
+                let id = self.add_to(&mut stream)?;
+                store.set(&self.key, Value::from(stream));
+                return Ok(Some(id));
+            }
+            IfKindResult::NotSet => return Ok(None),
+            _ => return Err(Error::WrongType),
+        };
+
+        self.add_to(stream).map(Some)
+    }
+
+    fn add_to(&self, stream: &mut Stream) -> Result<StreamId, Error> {
+        let id = match self.id {
+            AddId::Auto => stream.next_id(Utc::now().timestamp_millis() as u64),
+            AddId::Partial(ms) => stream.next_seq(ms),
+            AddId::Explicit(id) => Some(id).filter(|id| *id > stream.last_id()),
+        }
+        .ok_or(Error::Raw(
+            b"-ERR The ID specified in XADD is equal or smaller than the target stream top item\r\n",
+        ))?;
+
+        stream.add(id, self.fields.clone());
+        if let Some(trimming) = &self.trimming {
+            trimming.apply(stream);
+        }
+        Ok(id)
+    }
+
+    /// Returns the command that adds the same entry with the ID it was given.
+    fn propagated_args(&self, id: &StreamId) -> Vec<Vec<u8>> {
+        let mut args = vec![b"XADD".to_vec(), self.key.clone()];
+        if !self.make_stream {
+            args.push(b"NOMKSTREAM".to_vec());
+        }
+        if let Some(trimming) = &self.trimming {
+            args.extend(trimming.args());
+        }
+        args.push(id.to_bytes());
+        for (field, value) in &self.fields {
+            args.push(field.clone());
+            args.push(value.clone());
+        }
+        args
+    }
+}
+
+impl Apply for Add {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let db = context.session.db();
+        match self.add(&mut context.databases[db])? {
+            Some(id) => {
+                // replay with the ID that was generated
+                context.propagate(self.propagated_args(&id));
+                Ok(Response::BulkString(id.to_bytes()))
+            }
+            None => {
+                context.propagate_nothing();
+                Ok(Response::Null)
+            }
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+enum AddId {
+    Auto,
+    Partial(u64),
+    Explicit(StreamId),
+}
+
+/// Parses `*` to generate the whole ID, `ms-*` to generate the sequence number,
+/// or an explicit ID, which must not be 0-0.
+fn parse_add_id(id: &[u8]) -> Result<AddId, Error> {
+    match id {
+        b"*" => Ok(AddId::Auto),
+        [ms @ .., b'-', b'*'] => std::str::from_utf8(ms)
+            .ok()
+            .and_then(|ms| ms.parse().ok())
+            .map(AddId::Partial)
+            .ok_or_else(invalid_id),
+        _ => match StreamId::parse(id, 0).ok_or_else(invalid_id)? {
+            StreamId::MIN => Err(Error::Raw(
+                b"-ERR The ID specified in XADD must be greater than 0-0\r\n",
+            )),
+            id => Ok(AddId::Explicit(id)),
+        },
+    }
+}
+
+pub struct AddParser {}
+
+impl AddParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for AddParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let mut add = Add::new(input.next_bytes()?);
+
+        // the options come before the ID
+        while let Some(token) = input.peek_token() {
+            match token.as_str() {
+                "NOMKSTREAM" => {
+                    input.next()?;
+                    add.make_stream = false;
+                }
+                "MAXLEN" | "MINID" => {
+                    input.next()?;
+                    add.trimming = Some(Trimming::try_parse(&token, input)?);
+                }
+                _ => break,
+            }
+        }
+
+        add.id = parse_add_id(input.next()?)?;
+        if !input.has_next() || !input.len().is_multiple_of(2) {
+            return Err(Error::MissingArgument(
+                "XADD".to_string(),
+                "value".to_string(),
+            ));
+        }
+        while input.has_next() {
+            add.fields.push((input.next_bytes()?, input.next_bytes()?));
+        }
+        Ok(Box::new(add))
+    }
+}
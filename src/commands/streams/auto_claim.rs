@@ -0,0 +1,145 @@
+use chrono::Utc;
+
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::{ack_args, claim_args, entries_response, parse_range_start, stream_with_group};
+
+// the pending entries examined for each one that may be claimed
+const ATTEMPTS_PER_CLAIM: usize = 10;
+
+/// AutoClaim scans the group's pending entries from the start ID,
+/// giving those idle for at least the minimum time to the consumer
+/// and dropping those deleted from the stream.
+/// It replies with the ID to continue the scan from, or 0-0 when it's done,
+/// along with the claimed entries and the IDs of the deleted ones.
+struct AutoClaim {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    consumer: Vec<u8>,
+    min_idle: i64,
+    start: Option<StreamId>,
+    count: usize,
+    just_id: bool,
+}
+
+impl Apply for AutoClaim {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let db = context.session.db();
+        let stream = stream_with_group(&mut context.databases[db], &self.key, &self.group)?;
+        let now = Utc::now();
+
+        // one more than can be examined, to find where the next scan starts
+        let ids = match self.start {
+            Some(start) => stream
+                .group(&self.group)
+                .expect("checked")
+                .pending()
+                .range(start..)
+                .take(self.count * ATTEMPTS_PER_CLAIM + 1)
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+
+        let mut claimed = Vec::new();
+        let mut deleted = Vec::new();
+        let mut propagated = Vec::new();
+        let mut examined = 0;
+        for id in ids.iter().take(self.count * ATTEMPTS_PER_CLAIM) {
+            if claimed.len() == self.count {
+                break;
+            }
+            examined += 1;
+
+            let exists = stream.get(id).is_some();
+            let group = stream.group_mut(&self.group).expect("checked");
+            if !exists {
+                group.ack(id);
+                propagated.push(ack_args(&self.key, &self.group, id));
+                deleted.push(*id);
+                continue;
+            }
+            let entry = &group.pending()[id];
+            if (now - entry.delivered_at).num_milliseconds() < self.min_idle {
+                continue;
+            }
+
+            let delivery_count = entry.delivery_count + if self.just_id { 0 } else { 1 };
+            group.touch_consumer(&self.consumer, now, true);
+            group.deliver(*id, &self.consumer, now, delivery_count);
+            propagated.push(claim_args(&self.key, &self.group, id, &group.pending()[id]));
+            claimed.push(*id);
+        }
+
+        let next = ids.get(examined).copied().unwrap_or(StreamId::MIN);
+        let entries = if self.just_id {
+            Response::List(claimed.iter().map(|id| id.to_bytes()).collect())
+        } else {
+            entries_response(
+                claimed
+                    .iter()
+                    .map(|id| (id, stream.get(id).expect("claimed"))),
+            )
+        };
+
+        context.propagate_nothing();
+        for args in propagated {
+            context.propagate(args);
+        }
+        Ok(Response::Array(vec![
+            Response::BulkString(next.to_bytes()),
+            entries,
+            Response::List(deleted.iter().map(|id| id.to_bytes()).collect()),
+        ]))
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct AutoClaimParser {}
+
+impl AutoClaimParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for AutoClaimParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let group = input.next_bytes()?;
+        let consumer = input.next_bytes()?;
+        let min_idle = input.next_i64()?.max(0);
+        let start = parse_range_start(input.next()?)?;
+
+        let mut count = 100;
+        let mut just_id = false;
+        while input.has_next() {
+            match input.next_token()?.as_str() {
+                "COUNT" => {
+                    count = match input.next_i64()? {
+                        count if count >= 1 && count <= i64::MAX / ATTEMPTS_PER_CLAIM as i64 => {
+                            count as usize
+                        }
+                        _ => return Err(Error::Raw(b"-ERR COUNT must be > 0\r\n")),
+                    };
+                }
+                "JUSTID" => just_id = true,
+                _ => return Err(Error::Syntax),
+            }
+        }
+
+        Ok(Box::new(AutoClaim {
+            key,
+            group,
+            consumer,
+            min_idle,
+            start,
+            count,
+            just_id,
+        }))
+    }
+}
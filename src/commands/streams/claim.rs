@@ -0,0 +1,212 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::{ack_args, claim_args, entries_response, parse_id, stream_with_group};
+
+/// Claim gives pending entries idle for at least the minimum time to the consumer,
+/// creating pending entries for any with FORCE, and drops those deleted from the stream.
+struct Claim {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    consumer: Vec<u8>,
+    min_idle: i64,
+    ids: Vec<StreamId>,
+    delivered_at: Option<DateTime<Utc>>,
+    retry_count: Option<u64>,
+    force: bool,
+    just_id: bool,
+    last_id: Option<StreamId>,
+}
+
+impl Claim {
+    pub fn new(
+        key: Vec<u8>,
+        group: Vec<u8>,
+        consumer: Vec<u8>,
+        min_idle: i64,
+        ids: Vec<StreamId>,
+    ) -> Self {
+        Self {
+            key,
+            group,
+            consumer,
+            min_idle,
+            ids,
+            delivered_at: None,
+            retry_count: None,
+            force: false,
+            just_id: false,
+            last_id: None,
+        }
+    }
+}
+
+impl Apply for Claim {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let db = context.session.db();
+        let stream = stream_with_group(&mut context.databases[db], &self.key, &self.group)?;
+        let now = Utc::now();
+        // a delivery time in the future would make the entry idle for a negative time
+        let delivered_at = self.delivered_at.map_or(now, |at| at.min(now));
+
+        let mut propagated = Vec::new();
+        let group = stream.group_mut(&self.group).expect("checked");
+        if let Some(last_id) = self.last_id.filter(|id| *id > group.last_id) {
+            group.last_id = last_id;
+            propagated.push(vec![
+                b"XGROUP".to_vec(),
+                b"SETID".to_vec(),
+                self.key.clone(),
+                self.group.clone(),
+                last_id.to_bytes(),
+                b"ENTRIESREAD".to_vec(),
+                group
+                    .entries_read
+                    .map_or(-1, |n| n as i64)
+                    .to_string()
+                    .into_bytes(),
+            ]);
+        }
+
+        let mut claimed = Vec::new();
+        for id in &self.ids {
+            let exists = stream.get(id).is_some();
+            let group = stream.group_mut(&self.group).expect("checked");
+            let delivery_count = match group.pending().get(id) {
+                Some(_) if !exists => {
+                    group.ack(id);
+                    propagated.push(ack_args(&self.key, &self.group, id));
+                    continue;
+                }
+                Some(entry) => {
+                    if (now - entry.delivered_at).num_milliseconds() < self.min_idle {
+                        continue;
+                    }
+                    entry.delivery_count
+                }
+                // a forced entry counts as delivered once already
+                None if self.force && exists => 1,
+                None => continue,
+            };
+
+            let delivery_count = match self.retry_count {
+                Some(retry_count) => retry_count,
+                None if self.just_id => delivery_count,
+                None => delivery_count + 1,
+            };
+            group.touch_consumer(&self.consumer, now, true);
+            group.deliver(*id, &self.consumer, delivered_at, delivery_count);
+            propagated.push(claim_args(&self.key, &self.group, id, &group.pending()[id]));
+            claimed.push(*id);
+        }
+
+        let response = if self.just_id {
+            Response::List(claimed.iter().map(|id| id.to_bytes()).collect())
+        } else {
+            entries_response(
+                claimed
+                    .iter()
+                    .map(|id| (id, stream.get(id).expect("claimed"))),
+            )
+        };
+
+        context.propagate_nothing();
+        for args in propagated {
+            context.propagate(args);
+        }
+        Ok(response)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct ClaimParser {
+    options: Options<Claim>,
+}
+
+impl ClaimParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![
+                (vec!["IDLE"], ClaimParser::try_idle),
+                (vec!["TIME"], ClaimParser::try_time),
+                (vec!["RETRYCOUNT"], ClaimParser::try_retry_count),
+                (vec!["FORCE"], ClaimParser::try_force),
+                (vec!["JUSTID"], ClaimParser::try_just_id),
+                (vec!["LASTID"], ClaimParser::try_last_id),
+            ],
+        }
+    }
+
+    fn try_idle(claim: &mut Claim, _: &str, input: &mut Input) -> Result<(), Error> {
+        let idle = input.next_i64()?.max(0);
+        // an idle time reaching back before the epoch counts from the epoch, like TIME
+        let delivered_at = TimeDelta::try_milliseconds(idle)
+            .and_then(|idle| Utc::now().checked_sub_signed(idle))
+            .unwrap_or(DateTime::UNIX_EPOCH);
+        claim.delivered_at = Some(delivered_at.max(DateTime::UNIX_EPOCH));
+        Ok(())
+    }
+
+    fn try_time(claim: &mut Claim, _: &str, input: &mut Input) -> Result<(), Error> {
+        claim.delivered_at =
+            Some(DateTime::from_timestamp_millis(input.next_i64()?.max(0)).ok_or(Error::Integer)?);
+        Ok(())
+    }
+
+    fn try_retry_count(claim: &mut Claim, _: &str, input: &mut Input) -> Result<(), Error> {
+        claim.retry_count = Some(input.next_u64()?);
+        Ok(())
+    }
+
+    fn try_force(claim: &mut Claim, _: &str, _: &mut Input) -> Result<(), Error> {
+        claim.force = true;
+        Ok(())
+    }
+
+    fn try_just_id(claim: &mut Claim, _: &str, _: &mut Input) -> Result<(), Error> {
+        claim.just_id = true;
+        Ok(())
+    }
+
+    fn try_last_id(claim: &mut Claim, _: &str, input: &mut Input) -> Result<(), Error> {
+        claim.last_id = Some(parse_id(input.next()?)?);
+        Ok(())
+    }
+}
+
+impl TryParse for ClaimParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let group = input.next_bytes()?;
+        let consumer = input.next_bytes()?;
+        let min_idle = input.next_i64()?.max(0);
+
+        // the IDs run until the first argument that isn't one
+        let mut ids = Vec::new();
+        while let Some(id) = input
+            .peek_token()
+            .and_then(|token| StreamId::parse(token.as_bytes(), 0))
+        {
+            input.next()?;
+            ids.push(id);
+        }
+        if ids.is_empty() {
+            return Err(Error::MissingArgument(
+                "XCLAIM".to_string(),
+                "id".to_string(),
+            ));
+        }
+
+        Ok(Box::new(parse_options(
+            "XCLAIM",
+            &self.options,
+            input,
+            Claim::new(key, group, consumer, min_idle, ids),
+        )?))
+    }
+}
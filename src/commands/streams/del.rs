@@ -0,0 +1,54 @@
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::parse_id;
+
+struct Del {
+    key: Vec<u8>,
+    ids: Vec<StreamId>,
+}
+
+impl Del {
+    pub fn new(key: Vec<u8>, ids: Vec<StreamId>) -> Self {
+        Self { key, ids }
+    }
+}
+
+impl ApplyToStore for Del {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => Ok(Response::Usize(
+                self.ids.iter().filter(|id| stream.remove(id)).count(),
+            )),
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct DelParser {}
+
+impl DelParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for DelParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        if !input.has_next() {
+            return Err(Error::MissingArgument("XDEL".to_string(), "id".to_string()));
+        }
+
+        let mut ids = Vec::with_capacity(input.len());
+        while input.has_next() {
+            ids.push(parse_id(input.next()?)?);
+        }
+        Ok(Box::new(Del::new(key, ids)))
+    }
+}
@@ -0,0 +1,25 @@
+use crate::commands::prelude::*;
+use crate::storage::{Fields, StreamId};
+
+/// Replies with each entry as its ID and a flat list of its fields and values.
+pub fn entries_response<'a, 'e>(
+    entries: impl Iterator<Item = (&'e StreamId, &'e Fields)>,
+) -> Response<'a> {
+    Response::Array(
+        entries
+            .map(|(id, fields)| entry_response(id, fields))
+            .collect(),
+    )
+}
+
+pub fn entry_response<'a>(id: &StreamId, fields: &Fields) -> Response<'a> {
+    Response::Array(vec![
+        Response::BulkString(id.to_bytes()),
+        Response::List(
+            fields
+                .iter()
+                .flat_map(|(field, value)| [field.clone(), value.clone()])
+                .collect(),
+        ),
+    ])
+}
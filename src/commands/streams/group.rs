@@ -0,0 +1,42 @@
+use crate::commands::prelude::*;
+
+use super::group_create::GroupCreateParser;
+use super::group_create_consumer::GroupCreateConsumerParser;
+use super::group_del_consumer::GroupDelConsumerParser;
+use super::group_destroy::GroupDestroyParser;
+use super::group_set_id::GroupSetIdParser;
+
+pub struct GroupParser {
+    create: GroupCreateParser,
+    create_consumer: GroupCreateConsumerParser,
+    del_consumer: GroupDelConsumerParser,
+    destroy: GroupDestroyParser,
+    set_id: GroupSetIdParser,
+}
+
+impl GroupParser {
+    pub fn new() -> Self {
+        Self {
+            create: GroupCreateParser::new(),
+            create_consumer: GroupCreateConsumerParser::new(),
+            del_consumer: GroupDelConsumerParser::new(),
+            destroy: GroupDestroyParser::new(),
+            set_id: GroupSetIdParser::new(),
+        }
+    }
+}
+
+impl TryParse for GroupParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let subcommand = input.next_token()?;
+
+        match subcommand.as_str() {
+            "CREATE" => self.create.try_parse(input),
+            "CREATECONSUMER" => self.create_consumer.try_parse(input),
+            "DELCONSUMER" => self.del_consumer.try_parse(input),
+            "DESTROY" => self.destroy.try_parse(input),
+            "SETID" => self.set_id.try_parse(input),
+            _ => Err(Error::UnknownSubcommand("XGROUP".to_string(), subcommand)),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::commands::prelude::*;
+use crate::storage::{ConsumerGroup, Stream, StreamId};
+
+use super::{key_required, parse_entries_read, parse_group_id};
+
+struct GroupCreate {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    // `None` for `$`
+    id: Option<StreamId>,
+    make_stream: bool,
+    entries_read: Option<u64>,
+}
+
+impl GroupCreate {
+    pub fn new(key: Vec<u8>, group: Vec<u8>, id: Option<StreamId>) -> Self {
+        Self {
+            key,
+            group,
+            id,
+            make_stream: false,
+            entries_read: None,
+        }
+    }
+}
+
+impl ApplyToStore for GroupCreate {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let mut created = None;
+        let stream = match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream,
+            IfKindResult::NotSet if self.make_stream => created.insert(Stream::new()),
+            IfKindResult::NotSet => return Err(key_required()),
+            _ => return Err(Error::WrongType),
+        };
+
+        let last_id = self.id.unwrap_or_else(|| stream.last_id());
+        if !stream.create_group(&self.group, ConsumerGroup::new(last_id, self.entries_read)) {
+            return Err(Error::Raw(
+                b"-BUSYGROUP Consumer Group name already exists\r\n",
+            ));
+        }
+        if let Some(stream) = created {
+            store.set(&self.key, Value::from(stream));
+        }
+        Ok(Response::Ok)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct GroupCreateParser {
+    options: Options<GroupCreate>,
+}
+
+impl GroupCreateParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![
+                (vec!["MKSTREAM"], GroupCreateParser::try_make_stream),
+                (vec!["ENTRIESREAD"], GroupCreateParser::try_entries_read),
+            ],
+        }
+    }
+
+    fn try_make_stream(create: &mut GroupCreate, _: &str, _: &mut Input) -> Result<(), Error> {
+        create.make_stream = true;
+        Ok(())
+    }
+
+    fn try_entries_read(create: &mut GroupCreate, _: &str, input: &mut Input) -> Result<(), Error> {
+        create.entries_read = parse_entries_read(input)?;
+        Ok(())
+    }
+}
+
+impl TryParse for GroupCreateParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let group = input.next_bytes()?;
+        let id = parse_group_id(input.next()?)?;
+
+        Ok(Box::new(parse_options(
+            "XGROUP CREATE",
+            &self.options,
+            input,
+            GroupCreate::new(key, group, id),
+        )?))
+    }
+}
@@ -0,0 +1,54 @@
+use crate::commands::prelude::*;
+
+use super::{key_required, no_group_for_key};
+
+/// GroupDelConsumer removes the consumer and replies with the number of entries
+/// it had pending, which the group forgets.
+struct GroupDelConsumer {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    consumer: Vec<u8>,
+}
+
+impl ApplyToStore for GroupDelConsumer {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let stream = match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream,
+            IfKindResult::NotSet => return Err(key_required()),
+            _ => return Err(Error::WrongType),
+        };
+        let group = stream
+            .group_mut(&self.group)
+            .ok_or_else(|| no_group_for_key(&self.key, &self.group))?;
+
+        Ok(Response::Usize(
+            group.delete_consumer(&self.consumer).unwrap_or(0),
+        ))
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct GroupDelConsumerParser {}
+
+impl GroupDelConsumerParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for GroupDelConsumerParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let group = input.next_bytes()?;
+        let consumer = input.next_bytes()?;
+
+        Ok(Box::new(GroupDelConsumer {
+            key,
+            group,
+            consumer,
+        }))
+    }
+}
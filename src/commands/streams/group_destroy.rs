@@ -0,0 +1,41 @@
+use crate::commands::prelude::*;
+
+use super::key_required;
+
+struct GroupDestroy {
+    key: Vec<u8>,
+    group: Vec<u8>,
+}
+
+impl ApplyToStore for GroupDestroy {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => {
+                Ok(Response::Usize(stream.destroy_group(&self.group) as usize))
+            }
+            IfKindResult::NotSet => Err(key_required()),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct GroupDestroyParser {}
+
+impl GroupDestroyParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for GroupDestroyParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let group = input.next_bytes()?;
+
+        Ok(Box::new(GroupDestroy { key, group }))
+    }
+}
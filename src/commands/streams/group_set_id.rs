@@ -0,0 +1,77 @@
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::{key_required, no_group_for_key, parse_entries_read, parse_group_id};
+
+struct GroupSetId {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    // `None` for `$`
+    id: Option<StreamId>,
+    entries_read: Option<u64>,
+}
+
+impl GroupSetId {
+    pub fn new(key: Vec<u8>, group: Vec<u8>, id: Option<StreamId>) -> Self {
+        Self {
+            key,
+            group,
+            id,
+            entries_read: None,
+        }
+    }
+}
+
+impl ApplyToStore for GroupSetId {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let stream = match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream,
+            IfKindResult::NotSet => return Err(key_required()),
+            _ => return Err(Error::WrongType),
+        };
+
+        let last_id = self.id.unwrap_or_else(|| stream.last_id());
+        let group = stream
+            .group_mut(&self.group)
+            .ok_or_else(|| no_group_for_key(&self.key, &self.group))?;
+        group.last_id = last_id;
+        group.entries_read = self.entries_read;
+        Ok(Response::Ok)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct GroupSetIdParser {
+    options: Options<GroupSetId>,
+}
+
+impl GroupSetIdParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![(vec!["ENTRIESREAD"], GroupSetIdParser::try_entries_read)],
+        }
+    }
+
+    fn try_entries_read(set_id: &mut GroupSetId, _: &str, input: &mut Input) -> Result<(), Error> {
+        set_id.entries_read = parse_entries_read(input)?;
+        Ok(())
+    }
+}
+
+impl TryParse for GroupSetIdParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let group = input.next_bytes()?;
+        let id = parse_group_id(input.next()?)?;
+
+        Ok(Box::new(parse_options(
+            "XGROUP SETID",
+            &self.options,
+            input,
+            GroupSetId::new(key, group, id),
+        )?))
+    }
+}
@@ -0,0 +1,92 @@
+use crate::commands::prelude::*;
+use crate::storage::{PendingEntry, Stream, StreamId};
+
+use super::parse_id;
+
+/// Returns the stream at the key if it has the group.
+pub fn stream_with_group<'s>(
+    store: &'s mut Store,
+    key: &[u8],
+    group: &[u8],
+) -> Result<&'s mut Stream, Error> {
+    match store.get_mut_if_kind(Kind::Stream, key) {
+        IfKindResult::Matched(Value::Stream(stream)) if stream.group(group).is_some() => Ok(stream),
+        IfKindResult::Matched(_) | IfKindResult::NotSet => Err(no_group(key, group)),
+        _ => Err(Error::WrongType),
+    }
+}
+
+pub fn no_group(key: &[u8], group: &[u8]) -> Error {
+    Error::String(format!(
+        "NOGROUP No such key '{}' or consumer group '{}'",
+        String::from_utf8_lossy(key),
+        String::from_utf8_lossy(group)
+    ))
+}
+
+/// Returns the error XGROUP gives for a group the stream doesn't have.
+pub fn no_group_for_key(key: &[u8], group: &[u8]) -> Error {
+    Error::String(format!(
+        "NOGROUP No such consumer group '{}' for key name '{}'",
+        String::from_utf8_lossy(group),
+        String::from_utf8_lossy(key)
+    ))
+}
+
+pub fn key_required() -> Error {
+    Error::Raw(
+        b"-ERR The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically.\r\n",
+    )
+}
+
+/// Parses the ID a group starts reading after, where `$` is the stream's last ID.
+pub fn parse_group_id(id: &[u8]) -> Result<Option<StreamId>, Error> {
+    match id {
+        b"$" => Ok(None),
+        _ => parse_id(id).map(Some),
+    }
+}
+
+/// Parses the count of entries a group has read, where -1 means unknown.
+pub fn parse_entries_read(input: &mut Input) -> Result<Option<u64>, Error> {
+    match input.next_i64()? {
+        -1 => Ok(None),
+        n if n >= 0 => Ok(Some(n as u64)),
+        _ => Err(Error::Raw(
+            b"-ERR value for ENTRIESREAD must be positive or -1\r\n",
+        )),
+    }
+}
+
+/// Returns the command that gives the pending entry to its consumer
+/// with the same delivery time and count.
+pub fn claim_args(key: &[u8], group: &[u8], id: &StreamId, entry: &PendingEntry) -> Vec<Vec<u8>> {
+    vec![
+        b"XCLAIM".to_vec(),
+        key.to_vec(),
+        group.to_vec(),
+        entry.consumer.clone(),
+        b"0".to_vec(),
+        id.to_bytes(),
+        b"TIME".to_vec(),
+        entry
+            .delivered_at
+            .timestamp_millis()
+            .to_string()
+            .into_bytes(),
+        b"RETRYCOUNT".to_vec(),
+        entry.delivery_count.to_string().into_bytes(),
+        b"FORCE".to_vec(),
+        b"JUSTID".to_vec(),
+    ]
+}
+
+/// Returns the command that drops the pending entry from the group.
+pub fn ack_args(key: &[u8], group: &[u8], id: &StreamId) -> Vec<Vec<u8>> {
+    vec![
+        b"XACK".to_vec(),
+        key.to_vec(),
+        group.to_vec(),
+        id.to_bytes(),
+    ]
+}
@@ -0,0 +1,36 @@
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+/// Parses an ID given as `ms-seq`, or as `ms` for its first sequence number.
+pub fn parse_id(id: &[u8]) -> Result<StreamId, Error> {
+    StreamId::parse(id, 0).ok_or_else(invalid_id)
+}
+
+/// Parses the ID a range starts from, where `-` is the smallest ID
+/// and a leading `(` excludes the ID itself.
+/// Returns `None` if nothing can come after an excluded ID.
+pub fn parse_range_start(id: &[u8]) -> Result<Option<StreamId>, Error> {
+    match id {
+        b"-" => Ok(Some(StreamId::MIN)),
+        [b'(', id @ ..] => Ok(parse_id(id)?.next()),
+        _ => parse_id(id).map(Some),
+    }
+}
+
+/// Parses the ID a range ends at, where `+` is the largest ID,
+/// an `ms` on its own includes every sequence number in the millisecond,
+/// and a leading `(` excludes the ID itself.
+/// Returns `None` if nothing can come before an excluded ID.
+pub fn parse_range_end(id: &[u8]) -> Result<Option<StreamId>, Error> {
+    match id {
+        b"+" => Ok(Some(StreamId::MAX)),
+        [b'(', id @ ..] => Ok(StreamId::parse(id, u64::MAX).ok_or_else(invalid_id)?.prev()),
+        _ => StreamId::parse(id, u64::MAX)
+            .ok_or_else(invalid_id)
+            .map(Some),
+    }
+}
+
+pub fn invalid_id() -> Error {
+    Error::Raw(b"-ERR Invalid stream ID specified as stream command argument\r\n")
+}
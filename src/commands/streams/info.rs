@@ -0,0 +1,34 @@
+use crate::commands::prelude::*;
+
+use super::info_consumers::InfoConsumersParser;
+use super::info_groups::InfoGroupsParser;
+use super::info_stream::InfoStreamParser;
+
+pub struct InfoParser {
+    consumers: InfoConsumersParser,
+    groups: InfoGroupsParser,
+    stream: InfoStreamParser,
+}
+
+impl InfoParser {
+    pub fn new() -> Self {
+        Self {
+            consumers: InfoConsumersParser::new(),
+            groups: InfoGroupsParser::new(),
+            stream: InfoStreamParser::new(),
+        }
+    }
+}
+
+impl TryParse for InfoParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let subcommand = input.next_token()?;
+
+        match subcommand.as_str() {
+            "CONSUMERS" => self.consumers.try_parse(input),
+            "GROUPS" => self.groups.try_parse(input),
+            "STREAM" => self.stream.try_parse(input),
+            _ => Err(Error::UnknownSubcommand("XINFO".to_string(), subcommand)),
+        }
+    }
+}
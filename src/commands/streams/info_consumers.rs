@@ -0,0 +1,73 @@
+use chrono::Utc;
+
+use crate::commands::prelude::*;
+
+use super::no_group;
+
+/// InfoConsumers replies with each consumer in the group, the number of entries
+/// pending for it, and the milliseconds since it was last seen and last active,
+/// or -1 if it never was.
+struct InfoConsumers {
+    key: Vec<u8>,
+    group: Vec<u8>,
+}
+
+impl ApplyToStore for InfoConsumers {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let group = match store.get_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream.group(&self.group),
+            IfKindResult::NotSet => None,
+            _ => return Err(Error::WrongType),
+        }
+        .ok_or_else(|| no_group(&self.key, &self.group))?;
+        let now = Utc::now();
+
+        Ok(Response::Array(
+            group
+                .consumers()
+                .iter()
+                .map(|(name, consumer)| {
+                    Response::Map(vec![
+                        (
+                            Response::BulkStringRef(b"name"),
+                            Response::BulkString(name.clone()),
+                        ),
+                        (
+                            Response::BulkStringRef(b"pending"),
+                            Response::Usize(consumer.pending().len()),
+                        ),
+                        (
+                            Response::BulkStringRef(b"idle"),
+                            Response::I64((now - consumer.seen_at).num_milliseconds().max(0)),
+                        ),
+                        (
+                            Response::BulkStringRef(b"inactive"),
+                            Response::I64(
+                                consumer
+                                    .active_at
+                                    .map_or(-1, |at| (now - at).num_milliseconds().max(0)),
+                            ),
+                        ),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+}
+
+pub struct InfoConsumersParser {}
+
+impl InfoConsumersParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for InfoConsumersParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(InfoConsumers {
+            key: input.next_bytes()?,
+            group: input.next_bytes()?,
+        }))
+    }
+}
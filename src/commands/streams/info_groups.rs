@@ -0,0 +1,69 @@
+use crate::commands::prelude::*;
+
+struct InfoGroups {
+    key: Vec<u8>,
+}
+
+impl ApplyToStore for InfoGroups {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let stream = match store.get_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream,
+            IfKindResult::NotSet => return Err(Error::KeyNotFound),
+            _ => return Err(Error::WrongType),
+        };
+
+        Ok(Response::Array(
+            stream
+                .groups()
+                .map(|(name, group)| {
+                    Response::Map(vec![
+                        (
+                            Response::BulkStringRef(b"name"),
+                            Response::BulkString(name.clone()),
+                        ),
+                        (
+                            Response::BulkStringRef(b"consumers"),
+                            Response::Usize(group.consumers().len()),
+                        ),
+                        (
+                            Response::BulkStringRef(b"pending"),
+                            Response::Usize(group.pending().len()),
+                        ),
+                        (
+                            Response::BulkStringRef(b"last-delivered-id"),
+                            Response::BulkString(group.last_id.to_bytes()),
+                        ),
+                        (
+                            Response::BulkStringRef(b"entries-read"),
+                            group
+                                .entries_read
+                                .map_or(Response::Null, |n| Response::I64(n as i64)),
+                        ),
+                        (
+                            Response::BulkStringRef(b"lag"),
+                            stream
+                                .lag(group)
+                                .map_or(Response::Null, |n| Response::I64(n as i64)),
+                        ),
+                    ])
+                })
+                .collect(),
+        ))
+    }
+}
+
+pub struct InfoGroupsParser {}
+
+impl InfoGroupsParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for InfoGroupsParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(InfoGroups {
+            key: input.next_bytes()?,
+        }))
+    }
+}
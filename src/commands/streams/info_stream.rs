@@ -0,0 +1,225 @@
+use crate::commands::prelude::*;
+use crate::storage::{ConsumerGroup, Stream, StreamId};
+
+use super::{entries_response, entry_response};
+
+/// InfoStream replies with the stream's length, IDs and counters,
+/// and either its first and last entries and number of groups, or with FULL,
+/// up to COUNT entries and every group with its pending entries and consumers.
+struct InfoStream {
+    key: Vec<u8>,
+    // `Some` with FULL, where 0 means no limit
+    full: Option<usize>,
+}
+
+impl ApplyToStore for InfoStream {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let stream = match store.get_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream,
+            IfKindResult::NotSet => return Err(Error::KeyNotFound),
+            _ => return Err(Error::WrongType),
+        };
+
+        let mut info = vec![
+            (
+                Response::BulkStringRef(b"length"),
+                Response::Usize(stream.len()),
+            ),
+            (
+                Response::BulkStringRef(b"last-generated-id"),
+                Response::BulkString(stream.last_id().to_bytes()),
+            ),
+            (
+                Response::BulkStringRef(b"max-deleted-entry-id"),
+                Response::BulkString(stream.max_deleted_id().to_bytes()),
+            ),
+            (
+                Response::BulkStringRef(b"entries-added"),
+                Response::I64(stream.entries_added() as i64),
+            ),
+            (
+                Response::BulkStringRef(b"recorded-first-entry-id"),
+                Response::BulkString(
+                    stream
+                        .first_entry()
+                        .map_or(StreamId::MIN, |(id, _)| *id)
+                        .to_bytes(),
+                ),
+            ),
+        ];
+
+        let Some(count) = self.full else {
+            info.extend([
+                (
+                    Response::BulkStringRef(b"groups"),
+                    Response::Usize(stream.groups().count()),
+                ),
+                (
+                    Response::BulkStringRef(b"first-entry"),
+                    stream
+                        .first_entry()
+                        .map_or(Response::Null, |(id, fields)| entry_response(id, fields)),
+                ),
+                (
+                    Response::BulkStringRef(b"last-entry"),
+                    stream
+                        .last_entry()
+                        .map_or(Response::Null, |(id, fields)| entry_response(id, fields)),
+                ),
+            ]);
+            return Ok(Response::Map(info));
+        };
+
+        let count = if count == 0 { usize::MAX } else { count };
+        info.extend([
+            (
+                Response::BulkStringRef(b"entries"),
+                entries_response(stream.range(StreamId::MIN, StreamId::MAX).take(count)),
+            ),
+            (
+                Response::BulkStringRef(b"groups"),
+                Response::Array(
+                    stream
+                        .groups()
+                        .map(|(name, group)| group_response(stream, name, group, count))
+                        .collect(),
+                ),
+            ),
+        ]);
+        Ok(Response::Map(info))
+    }
+}
+
+/// Replies with the group's position, up to `count` of its pending entries,
+/// and its consumers with up to `count` of theirs.
+fn group_response<'a>(
+    stream: &Stream,
+    name: &[u8],
+    group: &ConsumerGroup,
+    count: usize,
+) -> Response<'a> {
+    let consumers = group
+        .consumers()
+        .iter()
+        .map(|(name, consumer)| {
+            Response::Map(vec![
+                (
+                    Response::BulkStringRef(b"name"),
+                    Response::BulkString(name.clone()),
+                ),
+                (
+                    Response::BulkStringRef(b"seen-time"),
+                    Response::I64(consumer.seen_at.timestamp_millis()),
+                ),
+                (
+                    Response::BulkStringRef(b"active-time"),
+                    Response::I64(consumer.active_at.map_or(-1, |at| at.timestamp_millis())),
+                ),
+                (
+                    Response::BulkStringRef(b"pel-count"),
+                    Response::Usize(consumer.pending().len()),
+                ),
+                (
+                    Response::BulkStringRef(b"pending"),
+                    Response::Array(
+                        consumer
+                            .pending()
+                            .iter()
+                            .take(count)
+                            .map(|id| {
+                                let entry = &group.pending()[id];
+                                Response::Array(vec![
+                                    Response::BulkString(id.to_bytes()),
+                                    Response::I64(entry.delivered_at.timestamp_millis()),
+                                    Response::I64(entry.delivery_count as i64),
+                                ])
+                            })
+                            .collect(),
+                    ),
+                ),
+            ])
+        })
+        .collect();
+
+    Response::Map(vec![
+        (
+            Response::BulkStringRef(b"name"),
+            Response::BulkString(name.to_vec()),
+        ),
+        (
+            Response::BulkStringRef(b"last-delivered-id"),
+            Response::BulkString(group.last_id.to_bytes()),
+        ),
+        (
+            Response::BulkStringRef(b"entries-read"),
+            group
+                .entries_read
+                .map_or(Response::Null, |n| Response::I64(n as i64)),
+        ),
+        (
+            Response::BulkStringRef(b"lag"),
+            stream
+                .lag(group)
+                .map_or(Response::Null, |n| Response::I64(n as i64)),
+        ),
+        (
+            Response::BulkStringRef(b"pel-count"),
+            Response::Usize(group.pending().len()),
+        ),
+        (
+            Response::BulkStringRef(b"pending"),
+            Response::Array(
+                group
+                    .pending()
+                    .iter()
+                    .take(count)
+                    .map(|(id, entry)| {
+                        Response::Array(vec![
+                            Response::BulkString(id.to_bytes()),
+                            Response::BulkString(entry.consumer.clone()),
+                            Response::I64(entry.delivered_at.timestamp_millis()),
+                            Response::I64(entry.delivery_count as i64),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        (
+            Response::BulkStringRef(b"consumers"),
+            Response::Array(consumers),
+        ),
+    ])
+}
+
+pub struct InfoStreamParser {}
+
+impl InfoStreamParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for InfoStreamParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+
+        let mut full = None;
+        if input.has_next() {
+            if input.next_token()? != "FULL" {
+                return Err(Error::Syntax);
+            }
+            full = Some(10);
+            if input.has_next() {
+                if input.next_token()? != "COUNT" {
+                    return Err(Error::Syntax);
+                }
+                full = Some(input.next_i64()?.max(0) as usize);
+            }
+        }
+        if input.has_next() {
+            return Err(Error::Syntax);
+        }
+
+        Ok(Box::new(InfoStream { key, full }))
+    }
+}
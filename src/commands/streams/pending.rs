@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+
+use crate::commands::prelude::*;
+use crate::storage::{ConsumerGroup, StreamId};
+
+use super::{no_group, parse_range_end, parse_range_start};
+
+/// Pending replies with a summary of the group's pending entries,
+/// or with the details of those in a range if one was given.
+struct Pending {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    range: Option<PendingRange>,
+}
+
+struct PendingRange {
+    min_idle: i64,
+    start: Option<StreamId>,
+    end: Option<StreamId>,
+    count: usize,
+    consumer: Option<Vec<u8>>,
+}
+
+impl ApplyToStore for Pending {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let group = match store.get_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream.group(&self.group),
+            IfKindResult::NotSet => None,
+            _ => return Err(Error::WrongType),
+        }
+        .ok_or_else(|| no_group(&self.key, &self.group))?;
+
+        match &self.range {
+            Some(range) => Ok(range.details(group, Utc::now())),
+            None => Ok(summary(group)),
+        }
+    }
+}
+
+/// Replies with the number of pending entries, the smallest and largest of their IDs,
+/// and the number pending for each consumer that has any.
+fn summary<'a>(group: &ConsumerGroup) -> Response<'a> {
+    let pending = group.pending();
+    let (Some((first, _)), Some((last, _))) = (pending.first_key_value(), pending.last_key_value())
+    else {
+        return Response::Array(vec![
+            Response::Zero,
+            Response::Null,
+            Response::Null,
+            Response::NullArray,
+        ]);
+    };
+
+    Response::Array(vec![
+        Response::Usize(pending.len()),
+        Response::BulkString(first.to_bytes()),
+        Response::BulkString(last.to_bytes()),
+        Response::Array(
+            group
+                .consumers()
+                .iter()
+                .filter(|(_, consumer)| !consumer.pending().is_empty())
+                .map(|(name, consumer)| {
+                    Response::Array(vec![
+                        Response::BulkString(name.clone()),
+                        Response::BulkString(consumer.pending().len().to_string().into_bytes()),
+                    ])
+                })
+                .collect(),
+        ),
+    ])
+}
+
+impl PendingRange {
+    /// Replies with the ID, consumer, idle time and delivery count of each entry in the range.
+    fn details<'a>(&self, group: &ConsumerGroup, now: DateTime<Utc>) -> Response<'a> {
+        let (Some(start), Some(end)) = (self.start, self.end) else {
+            return Response::EmptyList;
+        };
+        if start > end {
+            return Response::EmptyList;
+        }
+
+        let ids: Box<dyn Iterator<Item = &StreamId>> = match &self.consumer {
+            Some(consumer) => match group.consumer(consumer) {
+                Some(consumer) => Box::new(consumer.pending().range(start..=end)),
+                None => return Response::EmptyList,
+            },
+            None => Box::new(group.pending().range(start..=end).map(|(id, _)| id)),
+        };
+
+        Response::Array(
+            ids.map(|id| (id, &group.pending()[id]))
+                .map(|(id, entry)| (id, entry, (now - entry.delivered_at).num_milliseconds()))
+                .filter(|(_, _, idle)| *idle >= self.min_idle)
+                .take(self.count)
+                .map(|(id, entry, idle)| {
+                    Response::Array(vec![
+                        Response::BulkString(id.to_bytes()),
+                        Response::BulkString(entry.consumer.clone()),
+                        Response::I64(idle.max(0)),
+                        Response::I64(entry.delivery_count as i64),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+pub struct PendingParser {}
+
+impl PendingParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for PendingParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let group = input.next_bytes()?;
+        if !input.has_next() {
+            return Ok(Box::new(Pending {
+                key,
+                group,
+                range: None,
+            }));
+        }
+
+        let min_idle = match input.peek_token().as_deref() {
+            Some("IDLE") => {
+                input.next()?;
+                input.next_i64()?
+            }
+            _ => 0,
+        };
+        let start = parse_range_start(input.next()?)?;
+        let end = parse_range_end(input.next()?)?;
+        let count = input.next_i64()?.max(0) as usize;
+        let consumer = input.has_next().then(|| input.next_bytes()).transpose()?;
+        if input.has_next() {
+            return Err(Error::Syntax);
+        }
+
+        Ok(Box::new(Pending {
+            key,
+            group,
+            range: Some(PendingRange {
+                min_idle,
+                start,
+                end,
+                count,
+                consumer,
+            }),
+        }))
+    }
+}
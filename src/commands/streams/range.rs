@@ -0,0 +1,90 @@
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::{entries_response, parse_range_end, parse_range_start};
+
+struct Range {
+    key: Vec<u8>,
+    start: Option<StreamId>,
+    end: Option<StreamId>,
+    count: Option<usize>,
+    rev: bool,
+}
+
+impl ApplyToStore for Range {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let stream = match store.get_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream,
+            IfKindResult::NotSet => return Ok(Response::EmptyList),
+            _ => return Err(Error::WrongType),
+        };
+        let (Some(start), Some(end)) = (self.start, self.end) else {
+            return Ok(Response::EmptyList);
+        };
+
+        let count = self.count.unwrap_or(usize::MAX);
+        let entries = stream.range(start, end);
+        if self.rev {
+            Ok(entries_response(entries.rev().take(count)))
+        } else {
+            Ok(entries_response(entries.take(count)))
+        }
+    }
+}
+
+fn try_parse(rev: bool, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+    let key = input.next_bytes()?;
+    // XREVRANGE takes the end of the range first
+    let (start, end) = if rev {
+        let end = parse_range_end(input.next()?)?;
+        (parse_range_start(input.next()?)?, end)
+    } else {
+        let start = parse_range_start(input.next()?)?;
+        (start, parse_range_end(input.next()?)?)
+    };
+
+    let count = match input.has_next() {
+        true if input.next_token()? == "COUNT" => Some(input.next_i64()?.max(0) as usize),
+        true => return Err(Error::Syntax),
+        false => None,
+    };
+    if input.has_next() {
+        return Err(Error::Syntax);
+    }
+
+    Ok(Box::new(Range {
+        key,
+        start,
+        end,
+        count,
+        rev,
+    }))
+}
+
+pub struct RangeParser {}
+
+impl RangeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for RangeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        try_parse(false, input)
+    }
+}
+
+pub struct RevRangeParser {}
+
+impl RevRangeParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for RevRangeParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        try_parse(true, input)
+    }
+}
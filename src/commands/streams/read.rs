@@ -0,0 +1,182 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::{entries_response, parse_id};
+
+/// Read replies with the entries after the given IDs in each stream,
+/// blocking until one of the streams is added to if BLOCK was given and there are none.
+struct Read {
+    keys: Vec<Vec<u8>>,
+    ids: Vec<ReadId>,
+    count: Option<usize>,
+    block: Option<Option<Duration>>,
+    // where `$` and `+` put the start of each stream when the command first ran,
+    // so retries only see entries added since
+    starts: RefCell<Vec<Option<StreamId>>>,
+}
+
+impl Read {
+    pub fn new(keys: Vec<Vec<u8>>, ids: Vec<ReadId>) -> Self {
+        Self {
+            keys,
+            ids,
+            count: None,
+            block: None,
+            starts: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the first ID to read from each stream, or `None` if none can follow.
+    fn starts(&self, store: &mut Store) -> Result<Vec<Option<StreamId>>, Error> {
+        let mut starts = Vec::with_capacity(self.keys.len());
+
+        for (key, id) in self.keys.iter().zip(&self.ids) {
+            let stream = match store.get_if_kind(Kind::Stream, key) {
+                IfKindResult::Matched(Value::Stream(stream)) => Some(stream),
+                IfKindResult::NotSet => None,
+                _ => return Err(Error::WrongType),
+            };
+
+            starts.push(match id {
+                ReadId::After(id) => id.next(),
+                ReadId::Last => stream
+                    .map_or(StreamId::MIN, |stream| stream.last_id())
+                    .next(),
+                ReadId::LastEntry => match stream.and_then(|stream| stream.last_entry()) {
+                    Some((id, _)) => Some(*id),
+                    None => stream
+                        .map_or(StreamId::MIN, |stream| stream.last_id())
+                        .next(),
+                },
+            });
+        }
+
+        Ok(starts)
+    }
+
+    fn read<'a>(
+        &self,
+        store: &mut Store,
+        starts: &[Option<StreamId>],
+        protocol: Protocol,
+    ) -> Result<Response<'a>, Error> {
+        let mut streams = Vec::new();
+
+        for (key, start) in self.keys.iter().zip(starts) {
+            let stream = match store.get_if_kind(Kind::Stream, key) {
+                IfKindResult::Matched(Value::Stream(stream)) => stream,
+                IfKindResult::NotSet => continue,
+                _ => return Err(Error::WrongType),
+            };
+            let Some(start) = start else {
+                continue;
+            };
+
+            let mut entries = stream
+                .range(*start, StreamId::MAX)
+                .take(self.count.unwrap_or(usize::MAX))
+                .peekable();
+            if entries.peek().is_some() {
+                streams.push((Response::BulkString(key.clone()), entries_response(entries)));
+            }
+        }
+
+        if streams.is_empty() {
+            return match self.block {
+                Some(timeout) => Ok(Response::Block(timeout)),
+                None => Ok(Response::NullArray),
+            };
+        }
+        Ok(match protocol {
+            Protocol::Resp2 => Response::Array(
+                streams
+                    .into_iter()
+                    .map(|(key, entries)| Response::Array(vec![key, entries]))
+                    .collect(),
+            ),
+            Protocol::Resp3 => Response::Map(streams),
+        })
+    }
+}
+
+impl Apply for Read {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let protocol = context.session.protocol();
+        let store = context.store();
+        let starts = self.starts(store)?;
+
+        let response = self.read(store, &starts, protocol);
+        *self.starts.borrow_mut() = starts;
+        response
+    }
+
+    fn retry<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let protocol = context.session.protocol();
+        self.read(context.store(), &self.starts.borrow(), protocol)
+    }
+
+    fn blocking_keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+}
+
+enum ReadId {
+    After(StreamId),
+    /// `$`, for entries added after the command runs
+    Last,
+    /// `+`, for the last entry and those added after it
+    LastEntry,
+}
+
+pub struct ReadParser {}
+
+impl ReadParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ReadParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let mut count = None;
+        let mut block = None;
+
+        loop {
+            match input.next_token()?.as_str() {
+                "COUNT" => count = Some(input.next_i64()?).filter(|count| *count > 0),
+                "BLOCK" => {
+                    let ms = input.next_i64()?;
+                    if ms < 0 {
+                        return Err(Error::Raw(b"-ERR timeout is negative\r\n"));
+                    }
+                    block = Some((ms > 0).then(|| Duration::from_millis(ms as u64)));
+                }
+                "STREAMS" => break,
+                _ => return Err(Error::Syntax),
+            }
+        }
+
+        if !input.has_next() || !input.len().is_multiple_of(2) {
+            return Err(Error::Raw(
+                b"-ERR Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified.\r\n",
+            ));
+        }
+        let keys = input.next_multiple("XREAD", "key", input.len() / 2)?;
+        let mut ids = Vec::with_capacity(keys.len());
+        while input.has_next() {
+            ids.push(match input.next()? {
+                b"$" => ReadId::Last,
+                b"+" => ReadId::LastEntry,
+                id => ReadId::After(parse_id(id)?),
+            });
+        }
+
+        let mut read = Read::new(keys, ids);
+        read.count = count.map(|count| count as usize);
+        read.block = block;
+        Ok(Box::new(read))
+    }
+}
@@ -0,0 +1,277 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::{claim_args, entries_response, entry_response, parse_id, stream_with_group};
+
+/// ReadGroup delivers entries to a consumer in a group.
+///
+/// `>` delivers the entries the group hasn't seen, adding them to the consumer's
+/// pending entries unless NOACK was given, and blocks if BLOCK was given and there are none.
+/// Any other ID replies with the consumer's pending entries after it.
+struct ReadGroup {
+    group: Vec<u8>,
+    consumer: Vec<u8>,
+    keys: Vec<Vec<u8>>,
+    // `None` for `>`
+    ids: Vec<Option<StreamId>>,
+    count: Option<usize>,
+    block: Option<Option<Duration>>,
+    no_ack: bool,
+    // the streams where the consumer was created by a run that blocked,
+    // which the run that finally replies must propagate
+    created: RefCell<Vec<Vec<u8>>>,
+}
+
+impl ReadGroup {
+    pub fn new(group: Vec<u8>, consumer: Vec<u8>) -> Self {
+        Self {
+            group,
+            consumer,
+            keys: Vec::new(),
+            ids: Vec::new(),
+            count: None,
+            block: None,
+            no_ack: false,
+            created: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn read<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let protocol = context.session.protocol();
+        let db = context.session.db();
+        let store = &mut context.databases[db];
+        let now = Utc::now();
+
+        // check every stream before changing any
+        for key in &self.keys {
+            match store.get_if_kind(Kind::Stream, key) {
+                IfKindResult::Matched(Value::Stream(stream))
+                    if stream.group(&self.group).is_some() => {}
+                IfKindResult::Matched(_) | IfKindResult::NotSet => {
+                    return Err(Error::String(format!(
+                        "NOGROUP No such key '{}' or consumer group '{}' in XREADGROUP with GROUP option",
+                        String::from_utf8_lossy(key),
+                        String::from_utf8_lossy(&self.group)
+                    )));
+                }
+                _ => return Err(Error::WrongType),
+            }
+        }
+
+        let count = self.count.unwrap_or(usize::MAX);
+        let mut streams = Vec::new();
+        let mut propagated = Vec::new();
+
+        for (key, id) in self.keys.iter().zip(&self.ids) {
+            let stream = stream_with_group(store, key, &self.group)?;
+            let group = stream.group_mut(&self.group).expect("checked");
+            if group.create_consumer(&self.consumer, now) {
+                self.created.borrow_mut().push(key.clone());
+            }
+
+            let Some(after) = id else {
+                let ids = match group.last_id.next() {
+                    Some(start) => stream
+                        .range(start, StreamId::MAX)
+                        .take(count)
+                        .map(|(id, _)| *id)
+                        .collect::<Vec<_>>(),
+                    None => Vec::new(),
+                };
+                for id in &ids {
+                    stream.advance_group(&self.group, *id);
+                }
+
+                let group = stream.group_mut(&self.group).expect("checked");
+                group.touch_consumer(&self.consumer, now, !ids.is_empty());
+                if ids.is_empty() {
+                    continue;
+                }
+                if !self.no_ack {
+                    for id in &ids {
+                        group.deliver(*id, &self.consumer, now, 1);
+                        let entry = &group.pending()[id];
+                        propagated.push(claim_args(key, &self.group, id, entry));
+                    }
+                }
+                propagated.push(vec![
+                    b"XGROUP".to_vec(),
+                    b"SETID".to_vec(),
+                    key.clone(),
+                    self.group.clone(),
+                    group.last_id.to_bytes(),
+                    b"ENTRIESREAD".to_vec(),
+                    group
+                        .entries_read
+                        .map_or(-1, |n| n as i64)
+                        .to_string()
+                        .into_bytes(),
+                ]);
+
+                streams.push((
+                    Response::BulkString(key.clone()),
+                    entries_response(
+                        ids.iter()
+                            .map(|id| (id, stream.get(id).expect("just read"))),
+                    ),
+                ));
+                continue;
+            };
+
+            let pending = match (group.consumer(&self.consumer), after.next()) {
+                (Some(consumer), Some(start)) => consumer
+                    .pending()
+                    .range(start..)
+                    .take(count)
+                    .copied()
+                    .collect::<Vec<_>>(),
+                _ => Vec::new(),
+            };
+            group.touch_consumer(&self.consumer, now, !pending.is_empty());
+
+            let mut entries = Vec::with_capacity(pending.len());
+            for id in &pending {
+                // entries deleted from the stream stay pending until acknowledged or claimed
+                let Some(fields) = stream.get(id) else {
+                    entries.push(Response::Array(vec![
+                        Response::BulkString(id.to_bytes()),
+                        Response::NullArray,
+                    ]));
+                    continue;
+                };
+                entries.push(entry_response(id, fields));
+                stream
+                    .group_mut(&self.group)
+                    .expect("checked")
+                    .redeliver(id, now);
+            }
+            streams.push((Response::BulkString(key.clone()), Response::Array(entries)));
+        }
+
+        if streams.is_empty() {
+            if let Some(timeout) = self.block {
+                if self.ids.iter().all(Option::is_none) {
+                    return Ok(Response::Block(timeout));
+                }
+            }
+        }
+
+        // replay the changes to the group rather than the read
+        context.propagate_nothing();
+        self.propagate_created(&mut context);
+        for args in propagated {
+            context.propagate(args);
+        }
+
+        if streams.is_empty() {
+            return Ok(Response::NullArray);
+        }
+        Ok(match protocol {
+            Protocol::Resp2 => Response::Array(
+                streams
+                    .into_iter()
+                    .map(|(key, entries)| Response::Array(vec![key, entries]))
+                    .collect(),
+            ),
+            Protocol::Resp3 => Response::Map(streams),
+        })
+    }
+
+    fn propagate_created(&self, context: &mut Context) {
+        for key in self.created.borrow_mut().drain(..) {
+            context.propagate(vec![
+                b"XGROUP".to_vec(),
+                b"CREATECONSUMER".to_vec(),
+                key,
+                self.group.clone(),
+                self.consumer.clone(),
+            ]);
+        }
+    }
+}
+
+impl Apply for ReadGroup {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        self.read(context)
+    }
+
+    fn timeout<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        context.propagate_nothing();
+        self.propagate_created(&mut context);
+        Ok(Response::NullArray)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+
+    fn blocking_keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+}
+
+pub struct ReadGroupParser {}
+
+impl ReadGroupParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ReadGroupParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let mut group = None;
+        let mut count = None;
+        let mut block = None;
+        let mut no_ack = false;
+
+        loop {
+            match input.next_token()?.as_str() {
+                "GROUP" => group = Some((input.next_bytes()?, input.next_bytes()?)),
+                "COUNT" => count = Some(input.next_i64()?).filter(|count| *count > 0),
+                "BLOCK" => {
+                    let ms = input.next_i64()?;
+                    if ms < 0 {
+                        return Err(Error::Raw(b"-ERR timeout is negative\r\n"));
+                    }
+                    block = Some((ms > 0).then(|| Duration::from_millis(ms as u64)));
+                }
+                "NOACK" => no_ack = true,
+                "STREAMS" => break,
+                _ => return Err(Error::Syntax),
+            }
+        }
+
+        let Some((group, consumer)) = group else {
+            return Err(Error::Raw(b"-ERR Missing GROUP option for XREADGROUP\r\n"));
+        };
+        if !input.has_next() || !input.len().is_multiple_of(2) {
+            return Err(Error::Raw(
+                b"-ERR Unbalanced 'xreadgroup' list of streams: for each stream key an ID or '>' must be specified.\r\n",
+            ));
+        }
+
+        let mut read = ReadGroup::new(group, consumer);
+        read.keys = input.next_multiple("XREADGROUP", "key", input.len() / 2)?;
+        while input.has_next() {
+            read.ids.push(match input.next()? {
+                b">" => None,
+                b"$" => {
+                    return Err(Error::Raw(
+                        b"-ERR The $ ID is meaningless in the context of XREADGROUP: you want to read the history of this consumer by specifying a proper ID, or use the > ID to get new messages. The $ ID would just return an empty result set.\r\n",
+                    ));
+                }
+                id => Some(parse_id(id)?),
+            });
+        }
+        read.count = count.map(|count| count as usize);
+        read.block = block;
+        read.no_ack = no_ack;
+        Ok(Box::new(read))
+    }
+}
@@ -0,0 +1,100 @@
+use crate::commands::prelude::*;
+use crate::storage::StreamId;
+
+use super::parse_id;
+
+struct SetId {
+    key: Vec<u8>,
+    last_id: StreamId,
+    entries_added: Option<u64>,
+    max_deleted_id: Option<StreamId>,
+}
+
+impl SetId {
+    pub fn new(key: Vec<u8>, last_id: StreamId) -> Self {
+        Self {
+            key,
+            last_id,
+            entries_added: None,
+            max_deleted_id: None,
+        }
+    }
+}
+
+impl ApplyToStore for SetId {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let stream = match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => stream,
+            IfKindResult::NotSet => return Err(Error::KeyNotFound),
+            _ => return Err(Error::WrongType),
+        };
+
+        if self.max_deleted_id.is_some_and(|id| id > self.last_id) {
+            return Err(Error::Raw(
+                b"-ERR The ID specified in XSETID is smaller than the provided max_deleted_entry_id\r\n",
+            ));
+        }
+        if self
+            .entries_added
+            .is_some_and(|entries_added| entries_added < stream.len() as u64)
+        {
+            return Err(Error::Raw(
+                b"-ERR The entries_added specified in XSETID is smaller than the target stream length\r\n",
+            ));
+        }
+        if stream
+            .last_entry()
+            .is_some_and(|(id, _)| *id > self.last_id)
+        {
+            return Err(Error::Raw(
+                b"-ERR The ID specified in XSETID is smaller than the target stream top item\r\n",
+            ));
+        }
+
+        stream.set_last_id(self.last_id, self.entries_added, self.max_deleted_id);
+        Ok(Response::Ok)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct SetIdParser {
+    options: Options<SetId>,
+}
+
+impl SetIdParser {
+    pub fn new() -> Self {
+        Self {
+            options: vec![
+                (vec!["ENTRIESADDED"], SetIdParser::try_entries_added),
+                (vec!["MAXDELETEDID"], SetIdParser::try_max_deleted_id),
+            ],
+        }
+    }
+
+    fn try_entries_added(set_id: &mut SetId, _: &str, input: &mut Input) -> Result<(), Error> {
+        set_id.entries_added = Some(input.next_u64()?);
+        Ok(())
+    }
+
+    fn try_max_deleted_id(set_id: &mut SetId, _: &str, input: &mut Input) -> Result<(), Error> {
+        set_id.max_deleted_id = Some(parse_id(input.next()?)?);
+        Ok(())
+    }
+}
+
+impl TryParse for SetIdParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let last_id = parse_id(input.next()?)?;
+
+        Ok(Box::new(parse_options(
+            "XSETID",
+            &self.options,
+            input,
+            SetId::new(key, last_id),
+        )?))
+    }
+}
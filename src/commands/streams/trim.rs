@@ -0,0 +1,53 @@
+use crate::commands::prelude::*;
+
+use super::Trimming;
+
+struct Trim {
+    key: Vec<u8>,
+    trimming: Trimming,
+}
+
+impl Trim {
+    pub fn new(key: Vec<u8>, trimming: Trimming) -> Self {
+        Self { key, trimming }
+    }
+}
+
+impl ApplyToStore for Trim {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::Stream, &self.key) {
+            IfKindResult::Matched(Value::Stream(stream)) => {
+                Ok(Response::Usize(self.trimming.apply(stream)))
+            }
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct TrimParser {}
+
+impl TrimParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for TrimParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let trimming = match input.next_token()?.as_str() {
+            token @ ("MAXLEN" | "MINID") => Trimming::try_parse(token, input)?,
+            _ => return Err(Error::Syntax),
+        };
+        if input.has_next() {
+            return Err(Error::Syntax);
+        }
+
+        Ok(Box::new(Trim::new(key, trimming)))
+    }
+}
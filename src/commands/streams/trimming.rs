@@ -0,0 +1,94 @@
+use crate::commands::prelude::*;
+use crate::storage::{Stream, Trim};
+
+use super::parse_id;
+
+// approximate trimming removes at most a hundred nodes unless told otherwise
+const DEFAULT_LIMIT: usize = 10_000;
+
+/// Trimming is the MAXLEN or MINID option of XADD and XTRIM.
+pub struct Trimming {
+    trim: Trim,
+    approximate: bool,
+    limit: Option<usize>,
+}
+
+impl Trimming {
+    /// Parses the rest of the option after its MAXLEN or MINID token.
+    pub fn try_parse(token: &str, input: &mut Input) -> Result<Self, Error> {
+        let approximate = match input.peek_token().as_deref() {
+            Some("~") => true,
+            Some("=") => false,
+            _ => return Self::try_parse_threshold(token, false, input),
+        };
+        input.next()?;
+
+        Self::try_parse_threshold(token, approximate, input)
+    }
+
+    fn try_parse_threshold(
+        token: &str,
+        approximate: bool,
+        input: &mut Input,
+    ) -> Result<Self, Error> {
+        let trim = match token {
+            "MAXLEN" => match input.next_i64()? {
+                max_len if max_len < 0 => {
+                    return Err(Error::Raw(b"-ERR The MAXLEN argument must be >= 0.\r\n"))
+                }
+                max_len => Trim::MaxLen(max_len as usize),
+            },
+            "MINID" => Trim::MinId(parse_id(input.next()?)?),
+            _ => panic!("unexpected token"),
+        };
+
+        let limit = match input.peek_token().as_deref() {
+            Some("LIMIT") => {
+                input.next()?;
+                let limit = input.next_i64()?;
+                if limit < 0 {
+                    return Err(Error::Raw(b"-ERR The LIMIT argument must be >= 0.\r\n"));
+                }
+                if !approximate {
+                    return Err(Error::Raw(
+                        b"-ERR syntax error, LIMIT cannot be used without the special ~ option\r\n",
+                    ));
+                }
+                Some(limit as usize)
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            trim,
+            approximate,
+            limit,
+        })
+    }
+
+    /// Trims the stream and returns the number of entries removed.
+    pub fn apply(&self, stream: &mut Stream) -> usize {
+        let limit = match self.limit {
+            Some(0) => usize::MAX,
+            Some(limit) => limit,
+            None => DEFAULT_LIMIT,
+        };
+        stream.trim(self.trim, self.approximate, limit)
+    }
+
+    /// Returns the option as arguments to propagate.
+    pub fn args(&self) -> Vec<Vec<u8>> {
+        let mut args = match self.trim {
+            Trim::MaxLen(max_len) => vec![b"MAXLEN".to_vec(), max_len.to_string().into_bytes()],
+            Trim::MinId(min_id) => vec![b"MINID".to_vec(), min_id.to_bytes()],
+        };
+        if self.approximate {
+            args.insert(1, b"~".to_vec());
+        }
+        if let Some(limit) = self.limit {
+            args.push(b"LIMIT".to_vec());
+            args.push(limit.to_string().into_bytes());
+        }
+        args
+    }
+}
@@ -1,9 +1,20 @@
+pub use bits::{bit_range, count_ones, find_bit, get_bit, parse_offset, BitUnit};
+
 pub mod append;
+pub mod bit_count;
+pub mod bit_op;
+pub mod bit_position;
 pub mod get;
+pub mod get_bit;
 pub mod get_del;
 pub mod get_ex;
 pub mod get_range;
+pub mod increment_by;
+pub mod increment_by_float;
 pub mod set;
+pub mod set_bit;
 pub mod set_multiple;
 pub mod set_multiple_if_not_set;
 pub mod str_len;
+
+mod bits;
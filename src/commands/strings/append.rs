@@ -1,30 +1,35 @@
 use crate::commands::prelude::*;
 
 struct Append {
-    key: String,
-    value: String,
+    key: Vec<u8>,
+    value: Vec<u8>,
 }
 
 impl Append {
-    pub fn new(key: String, value: String) -> Self {
+    pub fn new(key: Vec<u8>, value: Vec<u8>) -> Self {
         Self { key, value }
     }
 }
 
-impl Apply for Append {
+impl ApplyToStore for Append {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_mut_if_kind(Kind::String, &self.key) {
-            IfKindResult::Matched(Value::String(ref mut s)) => {
-                s.push_str(&self.value);
+            IfKindResult::Matched(value) => {
+                let s = value.expect_string_mut();
+                s.extend_from_slice(&self.value);
                 Ok(Response::Usize(s.len()))
             }
             IfKindResult::NotSet => {
-                store.set(&self.key, Value::from(self.value.clone()));
+                store.set(&self.key, Value::string(self.value.clone()));
                 Ok(Response::Usize(self.value.len()))
             }
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct AppendParser {}
@@ -38,8 +43,8 @@ impl AppendParser {
 impl TryParse for AppendParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(Append::new(
-            input.next_string()?,
-            input.next_string()?,
+            input.next_bytes()?,
+            input.next_bytes()?,
         )))
     }
 }
@@ -0,0 +1,52 @@
+use crate::commands::prelude::*;
+
+use super::{bit_range, count_ones, BitUnit};
+
+struct BitCount {
+    key: Vec<u8>,
+    range: Option<(i64, i64, BitUnit)>,
+}
+
+impl BitCount {
+    pub fn new(key: Vec<u8>, range: Option<(i64, i64, BitUnit)>) -> Self {
+        Self { key, range }
+    }
+}
+
+impl ApplyToStore for BitCount {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::String, &self.key) {
+            IfKindResult::Matched(value) => {
+                let bytes = value.expect_string();
+                let (start, end, unit) = self.range.unwrap_or((0, -1, BitUnit::Byte));
+                let range = bit_range(bytes.len(), start, end, unit);
+                Ok(Response::Usize(count_ones(&bytes, range)))
+            }
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct BitCountParser {}
+
+impl BitCountParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BitCountParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let range = if input.has_next() {
+            let start = input.next_i64()?;
+            let end = input.next_i64()?;
+            Some((start, end, BitUnit::parse(input)?))
+        } else {
+            None
+        };
+
+        Ok(Box::new(BitCount::new(key, range)))
+    }
+}
@@ -0,0 +1,127 @@
+use crate::commands::prelude::*;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Operation {
+    And,
+    Or,
+    Xor,
+    Not,
+    /// the bits of the first key that are set in none of the others
+    Diff,
+    /// the bits set in exactly one key
+    One,
+}
+
+/// BitOp combines strings bit by bit into the destination. Missing keys and the ends of shorter
+/// strings count as zero bytes, and an empty result deletes the destination.
+struct BitOp {
+    operation: Operation,
+    destination: Vec<u8>,
+    keys: Vec<Vec<u8>>,
+}
+
+impl BitOp {
+    pub fn new(operation: Operation, destination: Vec<u8>, keys: Vec<Vec<u8>>) -> Self {
+        Self {
+            operation,
+            destination,
+            keys,
+        }
+    }
+
+    fn combine(&self, sources: &[Vec<u8>]) -> Vec<u8> {
+        let len = sources.iter().map(Vec::len).max().unwrap_or(0);
+        let byte = |source: &Vec<u8>, i: usize| source.get(i).copied().unwrap_or(0);
+
+        (0..len)
+            .map(|i| {
+                let mut bytes = sources.iter().map(|source| byte(source, i));
+                match self.operation {
+                    Operation::And => bytes.fold(0xFF, |result, byte| result & byte),
+                    Operation::Or => bytes.fold(0, |result, byte| result | byte),
+                    Operation::Xor => bytes.fold(0, |result, byte| result ^ byte),
+                    Operation::Not => !byte(&sources[0], i),
+                    Operation::Diff => {
+                        let first = bytes.next().unwrap_or(0);
+                        first & !bytes.fold(0, |result, byte| result | byte)
+                    }
+                    Operation::One => {
+                        let mut once = 0;
+                        let mut many = 0;
+                        for byte in bytes {
+                            many |= once & byte;
+                            once = (once ^ byte) & !many;
+                        }
+                        once
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+impl ApplyToStore for BitOp {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let mut sources = Vec::with_capacity(self.keys.len());
+        for key in &self.keys {
+            match store.get_if_kind(Kind::String, key) {
+                IfKindResult::Matched(value) => sources.push(value.expect_string().into_owned()),
+                IfKindResult::NotSet => sources.push(Vec::new()),
+                _ => return Err(Error::WrongType),
+            }
+        }
+
+        let result = self.combine(&sources);
+        let len = result.len();
+        if result.is_empty() {
+            store.remove(&self.destination);
+        } else {
+            // the result replaces the destination along with its TTL
+            store.set_entry(&self.destination, Value::String(result), None);
+        }
+
+        Ok(Response::Usize(len))
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct BitOpParser {}
+
+impl BitOpParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BitOpParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let operation = match input.next_token()?.as_str() {
+            "AND" => Operation::And,
+            "OR" => Operation::Or,
+            "XOR" => Operation::Xor,
+            "NOT" => Operation::Not,
+            "DIFF" => Operation::Diff,
+            "ONE" => Operation::One,
+            _ => return Err(Error::Syntax),
+        };
+        let destination = input.next_bytes()?;
+        let keys = input.rest()?;
+
+        match operation {
+            _ if keys.is_empty() => Err(Error::MissingArgument(
+                "BITOP".to_string(),
+                "keys".to_string(),
+            )),
+            Operation::Not if keys.len() > 1 => Err(Error::Raw(
+                b"-ERR BITOP NOT must be called with a single source key.\r\n",
+            )),
+            Operation::Diff if keys.len() < 2 => Err(Error::Raw(
+                b"-ERR BITOP DIFF must be called with at least two source keys.\r\n",
+            )),
+            _ => Ok(Box::new(BitOp::new(operation, destination, keys))),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::commands::prelude::*;
+
+use super::{bit_range, find_bit, BitUnit};
+
+/// BitPosition finds the first bit set to 0 or 1. Without an end, a string is treated as padded
+/// with zeros on the right, so a search for 0 in a string of ones finds the bit past its end.
+struct BitPosition {
+    key: Vec<u8>,
+    bit: bool,
+    start: i64,
+    end: Option<i64>,
+    unit: BitUnit,
+}
+
+impl BitPosition {
+    pub fn new(key: Vec<u8>, bit: bool, start: i64, end: Option<i64>, unit: BitUnit) -> Self {
+        Self {
+            key,
+            bit,
+            start,
+            end,
+            unit,
+        }
+    }
+}
+
+impl ApplyToStore for BitPosition {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::String, &self.key) {
+            IfKindResult::Matched(value) => {
+                let bytes = value.expect_string();
+                let range = bit_range(bytes.len(), self.start, self.end.unwrap_or(-1), self.unit);
+                if range.is_empty() {
+                    return Ok(Response::I64(-1));
+                }
+
+                let end = range.end;
+                match find_bit(&bytes, self.bit, range) {
+                    Some(position) => Ok(Response::Usize(position)),
+                    None if !self.bit && self.end.is_none() => Ok(Response::Usize(end)),
+                    None => Ok(Response::I64(-1)),
+                }
+            }
+            IfKindResult::NotSet if self.bit => Ok(Response::I64(-1)),
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct BitPositionParser {}
+
+impl BitPositionParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for BitPositionParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let bit = match input.next()? {
+            b"0" => false,
+            b"1" => true,
+            _ => return Err(Error::Raw(b"-ERR The bit argument must be 1 or 0.\r\n")),
+        };
+
+        let start = if input.has_next() {
+            input.next_i64()?
+        } else {
+            0
+        };
+        let end = if input.has_next() {
+            Some(input.next_i64()?)
+        } else {
+            None
+        };
+
+        Ok(Box::new(BitPosition::new(
+            key,
+            bit,
+            start,
+            end,
+            BitUnit::parse(input)?,
+        )))
+    }
+}
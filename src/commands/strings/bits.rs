@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+use crate::commands::prelude::*;
+
+/// The largest bit offset, which keeps a bitmap within the 512MB limit on strings.
+const MAX_OFFSET: u64 = (512 * 1024 * 1024 * 8) - 1;
+
+/// Whether the start and end of a BITCOUNT or BITPOS range count bytes or bits.
+#[derive(Clone, Copy)]
+pub enum BitUnit {
+    Byte,
+    Bit,
+}
+
+impl BitUnit {
+    /// Reads an optional BYTE or BIT, defaulting to BYTE.
+    pub fn parse(input: &mut Input) -> Result<Self, Error> {
+        if !input.has_next() {
+            return Ok(BitUnit::Byte);
+        }
+        match input.next_token()?.as_str() {
+            "BYTE" => Ok(BitUnit::Byte),
+            "BIT" => Ok(BitUnit::Bit),
+            _ => Err(Error::Syntax),
+        }
+    }
+}
+
+pub fn parse_offset(input: &mut Input) -> Result<usize, Error> {
+    match input.next_u64() {
+        Ok(offset) if offset <= MAX_OFFSET => Ok(offset as usize),
+        _ => Err(Error::Raw(
+            b"-ERR bit offset is not an integer or out of range\r\n",
+        )),
+    }
+}
+
+/// Returns the bits between start and end inclusive, counting back from the end of a string
+/// `len` bytes long when negative.
+pub fn bit_range(len: usize, start: i64, end: i64, unit: BitUnit) -> Range<usize> {
+    match unit {
+        BitUnit::Byte => {
+            let range = clamp_range(len, start, end);
+            range.start * 8..range.end * 8
+        }
+        BitUnit::Bit => clamp_range(len * 8, start, end),
+    }
+}
+
+pub fn get_bit(bytes: &[u8], offset: usize) -> bool {
+    bytes
+        .get(offset / 8)
+        .is_some_and(|byte| byte & (0x80 >> (offset % 8)) != 0)
+}
+
+/// Returns the bytes the range touches with the bits outside the range cleared, along with the
+/// index of the first byte.
+fn masked_bytes(
+    bytes: &[u8],
+    range: Range<usize>,
+    invert: bool,
+) -> impl Iterator<Item = (usize, u8)> + '_ {
+    let first = range.start / 8;
+    let last = range.end.saturating_sub(1) / 8;
+    let bytes = if range.is_empty() {
+        &bytes[0..0]
+    } else {
+        &bytes[first..=last]
+    };
+
+    bytes.iter().enumerate().map(move |(i, &byte)| {
+        let index = first + i;
+        let mut byte = if invert { !byte } else { byte };
+        if index == first {
+            byte &= 0xFF >> (range.start % 8);
+        }
+        if index == last {
+            byte &= 0xFF << (7 - (range.end - 1) % 8);
+        }
+        (index, byte)
+    })
+}
+
+pub fn count_ones(bytes: &[u8], range: Range<usize>) -> usize {
+    masked_bytes(bytes, range, false)
+        .map(|(_, byte)| byte.count_ones() as usize)
+        .sum()
+}
+
+/// Returns the offset of the first bit in the range that is set to `bit`.
+pub fn find_bit(bytes: &[u8], bit: bool, range: Range<usize>) -> Option<usize> {
+    masked_bytes(bytes, range, !bit)
+        .find(|&(_, byte)| byte != 0)
+        .map(|(index, byte)| index * 8 + byte.leading_zeros() as usize)
+}
@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct Get {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Get {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for Get {
+impl ApplyToStore for Get {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::String, &self.key) {
             IfKindResult::Matched(value) if value.is_string() => Ok(Response::ValueRef(value)),
@@ -30,6 +30,6 @@ impl GetParser {
 
 impl TryParse for GetParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(Get::new(input.next_string()?)))
+        Ok(Box::new(Get::new(input.next_bytes()?)))
     }
 }
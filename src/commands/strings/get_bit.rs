@@ -0,0 +1,44 @@
+use crate::commands::prelude::*;
+
+use super::{get_bit, parse_offset};
+
+struct GetBit {
+    key: Vec<u8>,
+    offset: usize,
+}
+
+impl GetBit {
+    pub fn new(key: Vec<u8>, offset: usize) -> Self {
+        Self { key, offset }
+    }
+}
+
+impl ApplyToStore for GetBit {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_if_kind(Kind::String, &self.key) {
+            IfKindResult::Matched(value) => Ok(Response::int_from_bool(get_bit(
+                &value.expect_string(),
+                self.offset,
+            ))),
+            IfKindResult::NotSet => Ok(Response::Zero),
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+pub struct GetBitParser {}
+
+impl GetBitParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for GetBitParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(GetBit::new(
+            input.next_bytes()?,
+            parse_offset(input)?,
+        )))
+    }
+}
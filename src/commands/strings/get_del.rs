@@ -1,16 +1,16 @@
 use crate::commands::prelude::*;
 
 struct GetDel {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl GetDel {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for GetDel {
+impl ApplyToStore for GetDel {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_and_remove_if_kind(Kind::String, &self.key) {
             IfKindResult::Matched(value) if value.is_string() => Ok(Response::Value(value)),
@@ -18,6 +18,10 @@ impl Apply for GetDel {
             _ => Err(Error::WrongType),
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct GetDelParser {}
@@ -30,6 +34,6 @@ impl GetDelParser {
 
 impl TryParse for GetDelParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(GetDel::new(input.next_string()?)))
+        Ok(Box::new(GetDel::new(input.next_bytes()?)))
     }
 }
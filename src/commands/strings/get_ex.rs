@@ -4,24 +4,22 @@ use crate::commands::prelude::*;
 use crate::parse::Expiration;
 
 struct GetEx {
-    key: String,
+    key: Vec<u8>,
     expire: Expiration,
 }
 
 impl GetEx {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self {
             key,
             expire: Expiration::Keep,
         }
     }
-}
 
-impl Apply for GetEx {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::String, &self.key) {
-            IfKindResult::Matched(Value::String(s)) => {
-                let result = Ok(Response::BulkString(s.clone()));
+            IfKindResult::Matched(value) => {
+                let result = Ok(Response::BulkString(value.expect_string().into_owned()));
 
                 match self.expire {
                     Expiration::Keep => (),
@@ -45,6 +43,26 @@ impl Apply for GetEx {
     }
 }
 
+impl Apply for GetEx {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        match self.expire {
+            Expiration::Keep => context.propagate_nothing(),
+            Expiration::Never => context.propagate(vec![b"PERSIST".to_vec(), self.key.clone()]),
+            Expiration::At(at) => context.propagate(vec![
+                b"PEXPIREAT".to_vec(),
+                self.key.clone(),
+                at.timestamp_millis().to_string().into_bytes(),
+            ]),
+        }
+
+        self.apply(context.store())
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
 pub struct GetExParser {
     options: Options<GetEx>,
 }
@@ -67,7 +85,7 @@ impl GetExParser {
 
 impl TryParse for GetExParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
+        let key = input.next_bytes()?;
 
         Ok(Box::new(parse_options(
             "GETEX",
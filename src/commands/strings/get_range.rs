@@ -1,27 +1,33 @@
+use std::borrow::Cow;
+
 use crate::commands::prelude::*;
 
 struct GetRange {
-    key: String,
+    key: Vec<u8>,
     start: i64,
     end: i64,
 }
 
 impl GetRange {
-    pub fn new(key: String, start: i64, end: i64) -> Self {
+    pub fn new(key: Vec<u8>, start: i64, end: i64) -> Self {
         Self { key, start, end }
     }
 }
 
-impl Apply for GetRange {
+impl ApplyToStore for GetRange {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::String, &self.key) {
-            IfKindResult::Matched(Value::String(s)) => {
+            IfKindResult::Matched(value) => {
+                let s = value.expect_string();
                 let range = clamp_range(s.len(), self.start, self.end);
 
                 if range.is_empty() {
                     Ok(Response::EmptyBulkString)
                 } else {
-                    Ok(Response::BulkStringRef(&s[range]))
+                    match s {
+                        Cow::Borrowed(s) => Ok(Response::BulkStringRef(&s[range])),
+                        Cow::Owned(s) => Ok(Response::BulkString(s[range].to_vec())),
+                    }
                 }
             }
             IfKindResult::NotSet => Ok(Response::Null),
@@ -41,7 +47,7 @@ impl GetRangeParser {
 impl TryParse for GetRangeParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         Ok(Box::new(GetRange::new(
-            input.next_string()?,
+            input.next_bytes()?,
             input.next_i64()?,
             input.next_i64()?,
         )))
@@ -0,0 +1,106 @@
+use crate::commands::prelude::*;
+
+/// IncrementBy adds to the integer a string holds, starting from 0 if the key doesn't exist,
+/// and keeps the key's expiration.
+struct IncrementBy {
+    key: Vec<u8>,
+    increment: i64,
+}
+
+impl IncrementBy {
+    pub fn new(key: Vec<u8>, increment: i64) -> Self {
+        Self { key, increment }
+    }
+}
+
+impl ApplyToStore for IncrementBy {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::String, &self.key) {
+            IfKindResult::Matched(value) => {
+                let result = value
+                    .as_integer()
+                    .ok_or(Error::Integer)?
+                    .checked_add(self.increment)
+                    .ok_or(Error::Raw(
+                        b"-ERR increment or decrement would overflow\r\n",
+                    ))?;
+                *value = Value::Integer(result);
+                Ok(Response::I64(result))
+            }
+            IfKindResult::NotSet => {
+                store.set(&self.key, Value::Integer(self.increment));
+                Ok(Response::I64(self.increment))
+            }
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct IncrementParser {}
+
+impl IncrementParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for IncrementParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(IncrementBy::new(input.next_bytes()?, 1)))
+    }
+}
+
+pub struct DecrementParser {}
+
+impl DecrementParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for DecrementParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(IncrementBy::new(input.next_bytes()?, -1)))
+    }
+}
+
+pub struct IncrementByParser {}
+
+impl IncrementByParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for IncrementByParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let increment = input.next_i64()?;
+
+        Ok(Box::new(IncrementBy::new(key, increment)))
+    }
+}
+
+pub struct DecrementByParser {}
+
+impl DecrementByParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for DecrementByParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let decrement = input
+            .next_i64()?
+            .checked_neg()
+            .ok_or(Error::Raw(b"-ERR decrement would overflow\r\n"))?;
+
+        Ok(Box::new(IncrementBy::new(key, decrement)))
+    }
+}
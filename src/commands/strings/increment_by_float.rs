@@ -0,0 +1,88 @@
+use crate::commands::prelude::*;
+use crate::parse::parse_f64;
+
+/// IncrementByFloat adds to the number a string holds, starting from 0 if the key doesn't exist,
+/// and keeps the key's expiration.
+/// The result is written without an exponent and with no trailing zeros.
+struct IncrementByFloat {
+    key: Vec<u8>,
+    increment: f64,
+}
+
+impl IncrementByFloat {
+    pub fn new(key: Vec<u8>, increment: f64) -> Self {
+        Self { key, increment }
+    }
+
+    fn increment(&self, value: Option<&Value>) -> Result<Vec<u8>, Error> {
+        let value = match value {
+            Some(Value::Integer(i)) => *i as f64,
+            Some(value) => parse_f64(&value.expect_string())?,
+            None => 0.0,
+        };
+
+        let value = value + self.increment;
+        if value.is_finite() {
+            Ok(value.to_string().into_bytes())
+        } else {
+            Err(Error::Raw(
+                b"-ERR increment would produce NaN or Infinity\r\n",
+            ))
+        }
+    }
+
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        match store.get_mut_if_kind(Kind::String, &self.key) {
+            IfKindResult::Matched(value) => {
+                let result = self.increment(Some(&*value))?;
+                *value = Value::string(result.clone());
+                Ok(Response::BulkString(result))
+            }
+            IfKindResult::NotSet => {
+                let result = self.increment(None)?;
+                store.set(&self.key, Value::string(result.clone()));
+                Ok(Response::BulkString(result))
+            }
+            _ => Err(Error::WrongType),
+        }
+    }
+}
+
+impl Apply for IncrementByFloat {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let propagated = context.propagated;
+        let response = self.apply(&mut context.databases[context.session.db()])?;
+
+        // replay sets the same value instead of repeating the rounding
+        if let Response::BulkString(value) = &response {
+            *propagated = Some(vec![vec![
+                b"SET".to_vec(),
+                self.key.clone(),
+                value.clone(),
+                b"KEEPTTL".to_vec(),
+            ]]);
+        }
+        Ok(response)
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct IncrementByFloatParser {}
+
+impl IncrementByFloatParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for IncrementByFloatParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let increment = input.next_f64()?;
+
+        Ok(Box::new(IncrementByFloat::new(key, increment)))
+    }
+}
@@ -4,15 +4,15 @@ use crate::commands::prelude::*;
 use crate::parse::Expiration;
 
 struct Set {
-    key: String,
-    value: String,
+    key: Vec<u8>,
+    value: Vec<u8>,
     when: When,
     get: bool,
     expire: Expiration,
 }
 
 impl Set {
-    pub fn new(key: String, value: String) -> Self {
+    pub fn new(key: Vec<u8>, value: Vec<u8>) -> Self {
         Self {
             key,
             value,
@@ -21,9 +21,7 @@ impl Set {
             expire: Expiration::Never,
         }
     }
-}
 
-impl Apply for Set {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match self.when {
             When::Exists => {
@@ -40,8 +38,9 @@ impl Apply for Set {
         }
 
         let previous = if self.get {
-            match store.get_and_remove_if_kind(Kind::String, &self.key) {
-                IfKindResult::Matched(value) if value.is_string() => Some(value),
+            // read rather than remove the old value so KEEPTTL keeps its expiration
+            match store.get_if_kind(Kind::String, &self.key) {
+                IfKindResult::Matched(value) if value.is_string() => Some(value.clone()),
                 IfKindResult::NotSet => None,
                 _ => return Err(Error::WrongType),
             }
@@ -67,7 +66,7 @@ impl Apply for Set {
         };
 
         if !removed {
-            store.set(&self.key, Value::from(self.value.clone()));
+            store.set(&self.key, Value::string(self.value.clone()));
         }
         match previous {
             Some(value) => Ok(Response::Value(value)),
@@ -77,6 +76,32 @@ impl Apply for Set {
     }
 }
 
+impl Apply for Set {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        if let Expiration::At(at) = self.expire {
+            // replay with the same deadline no matter when
+            let mut args = vec![b"SET".to_vec(), self.key.clone(), self.value.clone()];
+            match self.when {
+                When::Exists => args.push(b"XX".to_vec()),
+                When::NotExists => args.push(b"NX".to_vec()),
+                When::Always => (),
+            }
+            if self.get {
+                args.push(b"GET".to_vec());
+            }
+            args.push(b"PXAT".to_vec());
+            args.push(at.timestamp_millis().to_string().into_bytes());
+            context.propagate(args);
+        }
+
+        self.apply(context.store())
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
 pub struct SetParser {
     options: Options<Set>,
 }
@@ -117,8 +142,8 @@ impl SetParser {
 
 impl TryParse for SetParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        let key = input.next_string()?;
-        let value = input.next_string()?;
+        let key = input.next_bytes()?;
+        let value = input.next_bytes()?;
 
         Ok(Box::new(parse_options(
             "SET",
@@ -0,0 +1,79 @@
+use crate::commands::prelude::*;
+
+use super::{get_bit, parse_offset};
+
+/// SetBit sets or clears a bit, padding the string with zero bytes to reach the offset.
+struct SetBit {
+    key: Vec<u8>,
+    offset: usize,
+    bit: bool,
+}
+
+impl SetBit {
+    pub fn new(key: Vec<u8>, offset: usize, bit: bool) -> Self {
+        Self { key, offset, bit }
+    }
+
+    fn set(&self, bytes: &mut Vec<u8>) -> bool {
+        let index = self.offset / 8;
+        let mask = 0x80 >> (self.offset % 8);
+        if bytes.len() <= index {
+            bytes.resize(index + 1, 0);
+        }
+
+        let previous = get_bit(bytes, self.offset);
+        if self.bit {
+            bytes[index] |= mask;
+        } else {
+            bytes[index] &= !mask;
+        }
+        previous
+    }
+}
+
+impl ApplyToStore for SetBit {
+    fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
+        let previous = match store.get_mut_if_kind(Kind::String, &self.key) {
+            IfKindResult::Matched(value) => self.set(value.expect_string_mut()),
+            IfKindResult::NotSet => {
+                let mut bytes = Vec::new();
+                self.set(&mut bytes);
+                store.set(&self.key, Value::String(bytes));
+                false
+            }
+            _ => return Err(Error::WrongType),
+        };
+
+        Ok(Response::int_from_bool(previous))
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+}
+
+pub struct SetBitParser {}
+
+impl SetBitParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for SetBitParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        let key = input.next_bytes()?;
+        let offset = parse_offset(input)?;
+        let bit = match input.next()? {
+            b"0" => false,
+            b"1" => true,
+            _ => {
+                return Err(Error::Raw(
+                    b"-ERR bit is not an integer or out of range\r\n",
+                ))
+            }
+        };
+
+        Ok(Box::new(SetBit::new(key, offset, bit)))
+    }
+}
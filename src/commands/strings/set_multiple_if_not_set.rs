@@ -1,18 +1,18 @@
 use crate::commands::prelude::*;
 
 struct SetMultipleIfNotSet {
-    key_value_pairs: Vec<String>,
+    key_value_pairs: Vec<Vec<u8>>,
 }
 
 impl SetMultipleIfNotSet {
-    pub fn new(key_value_pairs: Vec<String>) -> Self {
+    pub fn new(key_value_pairs: Vec<Vec<u8>>) -> Self {
         Self { key_value_pairs }
     }
 }
 
-impl Apply for SetMultipleIfNotSet {
+impl ApplyToStore for SetMultipleIfNotSet {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
-        if self.key_value_pairs.len() % 2 != 0 {
+        if !self.key_value_pairs.len().is_multiple_of(2) {
             Err(Error::Syntax)
         } else {
             for i in (0..self.key_value_pairs.len()).step_by(2) {
@@ -23,12 +23,16 @@ impl Apply for SetMultipleIfNotSet {
             for i in (0..self.key_value_pairs.len()).step_by(2) {
                 store.set(
                     &self.key_value_pairs[i],
-                    Value::from(self.key_value_pairs[i + 1].clone()),
+                    Value::string(self.key_value_pairs[i + 1].clone()),
                 );
             }
             Ok(Response::One)
         }
     }
+
+    fn is_write(&self) -> bool {
+        true
+    }
 }
 
 pub struct SetMultipleIfNotSetParser {}
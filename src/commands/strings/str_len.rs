@@ -1,19 +1,19 @@
 use crate::commands::prelude::*;
 
 struct StrLen {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl StrLen {
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Self { key }
     }
 }
 
-impl Apply for StrLen {
+impl ApplyToStore for StrLen {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error> {
         match store.get_if_kind(Kind::String, &self.key) {
-            IfKindResult::Matched(Value::String(s)) => Ok(Response::Usize(s.len())),
+            IfKindResult::Matched(value) => Ok(Response::Usize(value.expect_string().len())),
             IfKindResult::NotSet => Ok(Response::Zero),
             _ => Err(Error::WrongType),
         }
@@ -30,6 +30,6 @@ impl StrLenParser {
 
 impl TryParse for StrLenParser {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
-        Ok(Box::new(StrLen::new(input.next_string()?)))
+        Ok(Box::new(StrLen::new(input.next_bytes()?)))
     }
 }
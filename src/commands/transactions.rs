@@ -0,0 +1,5 @@
+pub mod discard;
+pub mod exec;
+pub mod multi;
+pub mod unwatch;
+pub mod watch;
@@ -0,0 +1,38 @@
+use crate::commands::prelude::*;
+
+struct Discard {}
+
+impl Discard {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for Discard {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        if context.session.take_transaction().is_none() {
+            return Err(Error::Raw(b"-ERR DISCARD without MULTI\r\n"));
+        }
+
+        context.unwatch();
+        Ok(Response::Ok)
+    }
+
+    fn is_transaction(&self) -> bool {
+        true
+    }
+}
+
+pub struct DiscardParser {}
+
+impl DiscardParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for DiscardParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Discard::new()))
+    }
+}
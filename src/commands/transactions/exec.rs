@@ -0,0 +1,49 @@
+use crate::commands::prelude::*;
+
+struct Exec {}
+
+impl Exec {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for Exec {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let aborted = match context.session.transaction_mut() {
+            Some(transaction) => transaction.is_aborted(),
+            None => return Err(Error::Raw(b"-ERR EXEC without MULTI\r\n")),
+        };
+        let changed = context.unwatch();
+
+        if aborted {
+            context.session.take_transaction();
+            return Err(Error::Raw(
+                b"-EXECABORT Transaction discarded because of previous errors.\r\n",
+            ));
+        }
+        if changed {
+            context.session.take_transaction();
+            return Ok(Response::NullArray);
+        }
+        Ok(Response::Exec)
+    }
+
+    fn is_transaction(&self) -> bool {
+        true
+    }
+}
+
+pub struct ExecParser {}
+
+impl ExecParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for ExecParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Exec::new()))
+    }
+}
@@ -0,0 +1,38 @@
+use crate::commands::prelude::*;
+
+struct Multi {}
+
+impl Multi {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for Multi {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        if context.session.in_multi() {
+            return Err(Error::Raw(b"-ERR MULTI calls can not be nested\r\n"));
+        }
+
+        context.session.start_multi();
+        Ok(Response::Ok)
+    }
+
+    fn is_transaction(&self) -> bool {
+        true
+    }
+}
+
+pub struct MultiParser {}
+
+impl MultiParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for MultiParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Multi::new()))
+    }
+}
@@ -0,0 +1,30 @@
+use crate::commands::prelude::*;
+
+struct Unwatch {}
+
+impl Unwatch {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Apply for Unwatch {
+    fn execute<'a>(&self, mut context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        context.unwatch();
+        Ok(Response::Ok)
+    }
+}
+
+pub struct UnwatchParser {}
+
+impl UnwatchParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for UnwatchParser {
+    fn try_parse(&self, _input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        Ok(Box::new(Unwatch::new()))
+    }
+}
@@ -0,0 +1,55 @@
+use crate::commands::prelude::*;
+
+struct Watch {
+    keys: Vec<Vec<u8>>,
+}
+
+impl Watch {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
+        Self { keys }
+    }
+}
+
+impl Apply for Watch {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        let session = context.session;
+        if session.in_multi() {
+            return Err(Error::Raw(b"-ERR WATCH inside MULTI is not allowed\r\n"));
+        }
+
+        let db = session.db();
+        let store = &mut context.databases[db];
+        for key in &self.keys {
+            let version = store.version(key);
+            if session.watch(db, key, version) {
+                store.watch(key);
+            }
+        }
+        Ok(Response::Ok)
+    }
+
+    fn is_transaction(&self) -> bool {
+        true
+    }
+}
+
+pub struct WatchParser {}
+
+impl WatchParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TryParse for WatchParser {
+    fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
+        if input.has_next() {
+            Ok(Box::new(Watch::new(input.rest()?)))
+        } else {
+            Err(Error::MissingArgument(
+                "WATCH".to_string(),
+                "key".to_string(),
+            ))
+        }
+    }
+}
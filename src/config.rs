@@ -0,0 +1,569 @@
+use std::env;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::PathBuf;
+
+use itertools::Itertools;
+
+use crate::log;
+use crate::log::Level;
+use crate::network::VERSION;
+use crate::parse::split_args;
+use crate::persistence::Fsync;
+
+/// Config holds the server parameters read from the config file
+/// and command-line arguments at startup.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub file: Option<PathBuf>,
+    pub bind: Vec<IpAddr>,
+    pub port: u16,
+    pub dir: PathBuf,
+    pub db_filename: String,
+    pub log_level: Level,
+    pub max_clients: usize,
+    pub timeout: u64,
+    pub databases: usize,
+    /// snapshot after the number of seconds if at least the number of keys changed
+    pub save: Vec<(u64, u64)>,
+    pub append_only: bool,
+    pub append_filename: String,
+    pub append_fsync: Fsync,
+    /// the host and port of the leader to replicate
+    pub replica_of: Option<(String, u16)>,
+    pub replica_read_only: bool,
+    /// the bytes at the end of the replication stream kept for replicas to continue from
+    pub repl_backlog_size: usize,
+}
+
+/// Param describes a single configuration parameter
+/// for the config file, command line, and CONFIG command.
+pub struct Param {
+    pub name: &'static str,
+    get: fn(&Config) -> String,
+    set: fn(&mut Config, &str) -> Result<(), String>,
+    /// accepts multiple arguments in the config file, joined by spaces
+    multiple: bool,
+    /// may be changed while the server is running
+    mutable: bool,
+}
+
+pub const PARAMS: [Param; 15] = [
+    Param {
+        name: "bind",
+        get: |config| config.bind.iter().join(" "),
+        set: Config::set_bind,
+        multiple: true,
+        mutable: false,
+    },
+    Param {
+        name: "port",
+        get: |config| config.port.to_string(),
+        set: Config::set_port,
+        multiple: false,
+        mutable: false,
+    },
+    Param {
+        name: "dir",
+        get: |config| config.dir.display().to_string(),
+        set: Config::set_dir,
+        multiple: false,
+        mutable: true,
+    },
+    Param {
+        name: "dbfilename",
+        get: |config| config.db_filename.clone(),
+        set: Config::set_db_filename,
+        multiple: false,
+        mutable: true,
+    },
+    Param {
+        name: "loglevel",
+        get: |config| config.log_level.as_str().to_string(),
+        set: Config::set_log_level,
+        multiple: false,
+        mutable: true,
+    },
+    Param {
+        name: "maxclients",
+        get: |config| config.max_clients.to_string(),
+        set: Config::set_max_clients,
+        multiple: false,
+        mutable: true,
+    },
+    Param {
+        name: "timeout",
+        get: |config| config.timeout.to_string(),
+        set: Config::set_timeout,
+        multiple: false,
+        mutable: true,
+    },
+    Param {
+        name: "databases",
+        get: |config| config.databases.to_string(),
+        set: Config::set_databases,
+        multiple: false,
+        mutable: false,
+    },
+    Param {
+        name: "save",
+        get: |config| {
+            config
+                .save
+                .iter()
+                .map(|(seconds, changes)| format!("{} {}", seconds, changes))
+                .join(" ")
+        },
+        set: Config::set_save,
+        multiple: true,
+        mutable: true,
+    },
+    Param {
+        name: "appendonly",
+        get: |config| yes_no(config.append_only),
+        set: Config::set_append_only,
+        multiple: false,
+        mutable: true,
+    },
+    Param {
+        name: "appendfilename",
+        get: |config| config.append_filename.clone(),
+        set: Config::set_append_filename,
+        multiple: false,
+        mutable: false,
+    },
+    Param {
+        name: "appendfsync",
+        get: |config| config.append_fsync.as_str().to_string(),
+        set: Config::set_append_fsync,
+        multiple: false,
+        mutable: true,
+    },
+    Param {
+        name: "replicaof",
+        get: |config| match &config.replica_of {
+            Some((host, port)) => format!("{} {}", host, port),
+            None => String::new(),
+        },
+        set: Config::set_replica_of,
+        multiple: true,
+        mutable: false,
+    },
+    Param {
+        name: "replica-read-only",
+        get: |config| yes_no(config.replica_read_only),
+        set: Config::set_replica_read_only,
+        multiple: false,
+        mutable: true,
+    },
+    Param {
+        name: "repl-backlog-size",
+        get: |config| config.repl_backlog_size.to_string(),
+        set: Config::set_repl_backlog_size,
+        multiple: false,
+        mutable: true,
+    },
+];
+
+const REWRITE_SIGNATURE: &str = "# Generated by CONFIG REWRITE";
+
+impl Config {
+    pub fn new() -> Self {
+        Self {
+            file: None,
+            bind: vec![IpAddr::V4(Ipv4Addr::LOCALHOST)],
+            port: 6379,
+            dir: PathBuf::from("."),
+            db_filename: "dump.rdb".to_string(),
+            log_level: Level::Notice,
+            max_clients: 10_000,
+            timeout: 0,
+            databases: 16,
+            save: vec![(3600, 1), (300, 100), (60, 10_000)],
+            append_only: false,
+            append_filename: "appendonly.aof".to_string(),
+            append_fsync: Fsync::EverySec,
+            replica_of: None,
+            replica_read_only: true,
+            repl_backlog_size: 1024 * 1024,
+        }
+    }
+
+    /// Builds the config from the arguments passed to the server
+    /// in the form `[config-file] [--name value ...]...`,
+    /// where the arguments override the directives in the file.
+    pub fn try_from_args(args: Vec<String>) -> Result<Self, String> {
+        let mut config = Config::new();
+        let mut args = args.into_iter().peekable();
+
+        if let Some(file) = args.next_if(|arg| !arg.starts_with("--")) {
+            let contents = fs::read_to_string(&file).map_err(|err| {
+                format!("Fatal error, can't open config file '{}': {}", file, err)
+            })?;
+
+            config.load(&contents)?;
+            config.file = Some(fs::canonicalize(&file).unwrap_or(PathBuf::from(file)));
+        }
+
+        let mut lines = Vec::new();
+        for arg in args {
+            match arg.strip_prefix("--") {
+                Some(name) => lines.push(vec![name.to_string()]),
+                None => match lines.last_mut() {
+                    Some(line) => line.push(arg),
+                    None => return Err(format!("Invalid argument '{}'", arg)),
+                },
+            }
+        }
+        for line in lines {
+            config
+                .set(&line[0], &line[1..])
+                .map_err(|err| fatal_error("command line", &line.join(" "), &err))?;
+        }
+
+        Ok(config)
+    }
+
+    /// Applies each directive in the contents of a redis.conf-style file.
+    pub fn load(&mut self, contents: &str) -> Result<(), String> {
+        for (number, line) in contents.lines().enumerate() {
+            let location = format!("line {}", number + 1);
+            let args = match parse_line(line) {
+                Some(args) => args.map_err(|err| fatal_error(&location, line, &err))?,
+                None => continue,
+            };
+
+            self.set(&args[0], &args[1..])
+                .map_err(|err| fatal_error(&location, line, &err))?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets a parameter from a config file directive or command-line argument.
+    pub fn set(&mut self, name: &str, args: &[String]) -> Result<(), String> {
+        match find_param(name) {
+            Some(param) if param.multiple || args.len() == 1 => (param.set)(self, &args.join(" ")),
+            _ => Err("Bad directive or wrong number of arguments".to_string()),
+        }
+    }
+
+    /// Sets a parameter while the server is running.
+    /// Call `apply` once all parameters have been set.
+    pub fn set_live(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match find_param(name) {
+            Some(param) if param.mutable => (param.set)(self, value),
+            Some(_) => Err("can't set immutable config".to_string()),
+            None => Err("Unknown option".to_string()),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<String> {
+        find_param(name).map(|param| (param.get)(self))
+    }
+
+    /// Puts the parameters that affect the whole process into effect.
+    pub fn apply(&mut self) -> Result<(), String> {
+        env::set_current_dir(&self.dir)
+            .map_err(|err| format!("Can't chdir to '{}': {}", self.dir.display(), err))?;
+        if let Ok(dir) = env::current_dir() {
+            self.dir = dir;
+        }
+        log::set_level(self.log_level);
+        Ok(())
+    }
+
+    /// Writes the current parameters back to the config file,
+    /// replacing the first directive for each parameter in place,
+    /// dropping any repeats, and appending those that differ from the defaults.
+    pub fn rewrite(&self) -> Result<(), String> {
+        let file = match &self.file {
+            Some(file) => file,
+            None => return Err("The server is running without a config file".to_string()),
+        };
+        let contents = fs::read_to_string(file).unwrap_or_default();
+        let defaults = Config::new();
+        let mut written = Vec::new();
+        let mut lines = Vec::new();
+
+        for line in contents.lines() {
+            if line == REWRITE_SIGNATURE {
+                continue;
+            }
+
+            let param = match parse_line(line) {
+                Some(Ok(args)) => find_param(&args[0]),
+                _ => None,
+            };
+            match param {
+                Some(param) if written.contains(&param.name) => (),
+                Some(param) => {
+                    written.push(param.name);
+                    lines.push(self.directive(param));
+                }
+                None => lines.push(line.to_string()),
+            }
+        }
+
+        let mut signed = false;
+        for param in PARAMS.iter() {
+            if written.contains(&param.name) || (param.get)(self) == (param.get)(&defaults) {
+                continue;
+            }
+            if !signed {
+                lines.push(REWRITE_SIGNATURE.to_string());
+                signed = true;
+            }
+            lines.push(self.directive(param));
+        }
+
+        let temp = file.with_extension("rewrite.tmp");
+        fs::write(&temp, lines.join("\n") + "\n")
+            .and_then(|_| fs::rename(&temp, file))
+            .map_err(|err| format!("Rewriting config file: {}", err))
+    }
+
+    fn directive(&self, param: &Param) -> String {
+        let value = (param.get)(self);
+
+        if param.multiple && !value.is_empty() {
+            format!("{} {}", param.name, value)
+        } else {
+            format!("{} {}", param.name, quote(&value))
+        }
+    }
+
+    fn set_bind(&mut self, value: &str) -> Result<(), String> {
+        let bind = value
+            .split_whitespace()
+            .map(|arg| match arg.trim_start_matches('-') {
+                "*" => Ok(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+                "::*" => Ok(IpAddr::V6(Ipv6Addr::UNSPECIFIED)),
+                addr => addr
+                    .parse()
+                    .map_err(|_| format!("Invalid bind address '{}'", arg)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if bind.is_empty() {
+            return Err("Wrong number of arguments".to_string());
+        }
+
+        self.bind = bind;
+        Ok(())
+    }
+
+    fn set_port(&mut self, value: &str) -> Result<(), String> {
+        self.port = value.parse().map_err(|_| "Invalid port".to_string())?;
+        Ok(())
+    }
+
+    fn set_dir(&mut self, value: &str) -> Result<(), String> {
+        self.dir = PathBuf::from(value);
+        Ok(())
+    }
+
+    fn set_db_filename(&mut self, value: &str) -> Result<(), String> {
+        if value.contains('/') || value.contains('\\') {
+            return Err("dbfilename can't be a path, just a filename".to_string());
+        }
+
+        self.db_filename = value.to_string();
+        Ok(())
+    }
+
+    fn set_log_level(&mut self, value: &str) -> Result<(), String> {
+        self.log_level = Level::try_parse(value)
+            .ok_or("Invalid log level. Must be one of debug, verbose, notice, warning, nothing")?;
+        Ok(())
+    }
+
+    fn set_max_clients(&mut self, value: &str) -> Result<(), String> {
+        let max_clients = parse_u64(value)? as usize;
+        if max_clients == 0 {
+            return Err("maxclients must be at least 1".to_string());
+        }
+
+        self.max_clients = max_clients;
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, value: &str) -> Result<(), String> {
+        self.timeout = parse_u64(value)?;
+        Ok(())
+    }
+
+    fn set_databases(&mut self, value: &str) -> Result<(), String> {
+        let databases = parse_u64(value)? as usize;
+        if databases == 0 {
+            return Err("Invalid number of databases".to_string());
+        }
+
+        self.databases = databases;
+        Ok(())
+    }
+
+    fn set_save(&mut self, value: &str) -> Result<(), String> {
+        let args = value.split_whitespace().collect::<Vec<_>>();
+        if !args.len().is_multiple_of(2) {
+            return Err("Invalid save parameters".to_string());
+        }
+
+        self.save = args
+            .chunks(2)
+            .map(|pair| Ok((parse_u64(pair[0])?, parse_u64(pair[1])?)))
+            .collect::<Result<_, String>>()
+            .map_err(|_| "Invalid save parameters".to_string())?;
+        Ok(())
+    }
+
+    fn set_append_only(&mut self, value: &str) -> Result<(), String> {
+        self.append_only = parse_yes_no(value)?;
+        Ok(())
+    }
+
+    fn set_append_filename(&mut self, value: &str) -> Result<(), String> {
+        if value.contains('/') || value.contains('\\') {
+            return Err("appendfilename can't be a path, just a filename".to_string());
+        }
+
+        self.append_filename = value.to_string();
+        Ok(())
+    }
+
+    fn set_append_fsync(&mut self, value: &str) -> Result<(), String> {
+        self.append_fsync = Fsync::try_parse(value)
+            .ok_or("argument(s) must be one of the following: always, everysec, no")?;
+        Ok(())
+    }
+
+    fn set_replica_of(&mut self, value: &str) -> Result<(), String> {
+        let args = value.split_whitespace().collect::<Vec<_>>();
+
+        self.replica_of = match args[..] {
+            [] => None,
+            [host, port] => Some((
+                host.to_string(),
+                port.parse()
+                    .map_err(|_| "Invalid master port".to_string())?,
+            )),
+            _ => return Err("Wrong number of arguments".to_string()),
+        };
+        Ok(())
+    }
+
+    fn set_replica_read_only(&mut self, value: &str) -> Result<(), String> {
+        self.replica_read_only = parse_yes_no(value)?;
+        Ok(())
+    }
+
+    fn set_repl_backlog_size(&mut self, value: &str) -> Result<(), String> {
+        let size = parse_memory(value)? as usize;
+        if size == 0 {
+            return Err("argument must be between 1 and 9223372036854775807 inclusive".to_string());
+        }
+
+        self.repl_backlog_size = size;
+        Ok(())
+    }
+}
+
+fn find_param(name: &str) -> Option<&'static Param> {
+    PARAMS
+        .iter()
+        .find(|param| param.name.eq_ignore_ascii_case(name))
+}
+
+/// Splits a config file line into its directive and arguments,
+/// returning `None` for blank lines and comments.
+fn parse_line(line: &str) -> Option<Result<Vec<String>, String>> {
+    let args = match split_args(line.trim().as_bytes()) {
+        Ok(args) => args,
+        Err(_) => return Some(Err("Unbalanced quotes in configuration line".to_string())),
+    };
+
+    if args.is_empty() || args[0].starts_with(b"#") {
+        None
+    } else {
+        Some(Ok(args
+            .iter()
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect()))
+    }
+}
+
+fn parse_u64(value: &str) -> Result<u64, String> {
+    value
+        .parse()
+        .map_err(|_| "argument couldn't be parsed into an integer".to_string())
+}
+
+/// Parses a number of bytes with an optional unit like `100kb` or `1g`.
+fn parse_memory(value: &str) -> Result<u64, String> {
+    let lower = value.to_lowercase();
+    let split = lower
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(lower.len());
+    let (number, unit) = lower.split_at(split);
+    let multiplier = match unit {
+        "" | "b" => 1,
+        "k" => 1000,
+        "kb" => 1024,
+        "m" => 1000 * 1000,
+        "mb" => 1024 * 1024,
+        "g" => 1000 * 1000 * 1000,
+        "gb" => 1024 * 1024 * 1024,
+        _ => return Err("argument must be a memory value".to_string()),
+    };
+
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .ok_or_else(|| "argument must be a memory value".to_string())
+}
+
+fn parse_yes_no(value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "yes" => Ok(true),
+        "no" => Ok(false),
+        _ => Err("argument must be 'yes' or 'no'".to_string()),
+    }
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+/// Wraps the value in double quotes when needed to read it back as a single argument.
+fn quote(value: &str) -> String {
+    if !value.is_empty()
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '\'' || c == '\\')
+    {
+        return value.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn fatal_error(location: &str, line: &str, reason: &str) -> String {
+    format!(
+        "\n*** FATAL CONFIG FILE ERROR (Redis {}) ***\nReading the configuration file, at {}\n>>> '{}'\n{}",
+        VERSION, location, line, reason
+    )
+}
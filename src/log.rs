@@ -0,0 +1,71 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Level orders log messages from most to least verbose
+/// using the names of the `loglevel` directive.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Debug,
+    Verbose,
+    Notice,
+    Warning,
+    Nothing,
+}
+
+const NAMES: [(&str, Level); 5] = [
+    ("debug", Level::Debug),
+    ("verbose", Level::Verbose),
+    ("notice", Level::Notice),
+    ("warning", Level::Warning),
+    ("nothing", Level::Nothing),
+];
+
+impl Level {
+    pub fn try_parse(name: &str) -> Option<Self> {
+        NAMES
+            .iter()
+            .find(|(level, _)| level.eq_ignore_ascii_case(name))
+            .map(|(_, level)| *level)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        NAMES
+            .iter()
+            .find(|(_, level)| level == self)
+            .map(|(name, _)| *name)
+            .expect("named level")
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Notice as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 >= LEVEL.load(Ordering::Relaxed)
+}
+
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            println!($($arg)*);
+        }
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+macro_rules! verbose {
+    ($($arg:tt)*) => { log!($crate::log::Level::Verbose, $($arg)*) };
+}
+
+macro_rules! notice {
+    ($($arg:tt)*) => { log!($crate::log::Level::Notice, $($arg)*) };
+}
+
+macro_rules! warning {
+    ($($arg:tt)*) => { log!($crate::log::Level::Warning, $($arg)*) };
+}
@@ -6,18 +6,53 @@
     unused_imports
 )]
 
-use std::io;
+use std::{env, io, process};
 
-use crate::network::Server;
+use crate::config::Config;
+use crate::network::{Server, VERSION};
+
+#[macro_use]
+mod log;
 
 mod commands;
+mod config;
 mod network;
 mod parse;
+mod persistence;
 mod resp;
 mod storage;
 
 fn main() -> io::Result<()> {
-    let addr = "127.0.0.1:6379".parse().expect("unable to parse address");
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("-v" | "--version") => {
+            println!("Redis server v={}", VERSION);
+            return Ok(());
+        }
+        Some("-h" | "--help") => {
+            println!("Usage: ./redis-server [/path/to/redis.conf] [options]");
+            println!("Examples:");
+            println!("       ./redis-server /etc/redis/6379.conf");
+            println!("       ./redis-server --port 7777");
+            println!("       ./redis-server /etc/myredis.conf --bind 0.0.0.0 --port 7777");
+            return Ok(());
+        }
+        _ => (),
+    }
+
+    let mut config = match Config::try_from_args(args) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    if let Err(err) = config.apply() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
 
-    Server::new()?.start(addr)
+    Server::new(config)?.start()
 }
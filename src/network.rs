@@ -1,14 +1,28 @@
 use std::io;
 
 pub use client::Client;
+pub use context::Context;
 pub use error::Error;
 pub use response::*;
-pub use server::Server;
+pub use server::{Server, VERSION};
+pub use handshake::{Handshake, Resync};
+pub use pubsub::{Message, PubSub};
+pub use replication::{LinkState, Replica, Replication};
+pub use session::{Peer, Protocol, Session};
+pub use stats::Stats;
+pub use transaction::Transaction;
 
 mod client;
+mod context;
 mod error;
+mod handshake;
+mod pubsub;
+mod replication;
 mod response;
 mod server;
+mod session;
+mod stats;
+mod transaction;
 
 fn would_block(err: &io::Error) -> bool {
     err.kind() == io::ErrorKind::WouldBlock
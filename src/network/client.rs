@@ -2,58 +2,118 @@ use std::collections::HashSet;
 use std::hash::Hasher;
 use std::io;
 use std::io::{Read, Write};
-use std::str::from_utf8;
+use std::time::Instant;
 
 use mio::{Interest, Registry, Token};
 use mio::event::Source;
 use mio::net::TcpStream;
 
-use crate::parse::Parser;
-use crate::storage::{Store, Value};
+use crate::config::Config;
+use crate::parse::{Apply, Parser};
+use crate::persistence::{Aof, Snapshots};
+use crate::storage::{format_score, Hash, Store, Stream, StreamId, Value};
 
-use super::{interrupted, Response, would_block};
+use super::{
+    Context, interrupted, Peer, Protocol, PubSub, Replication, Response, Session, Stats,
+    Transaction, would_block,
+};
 use super::error::Error;
 
+/// The command a blocked client runs again until it responds.
+struct Blocked {
+    command: Box<dyn Apply>,
+    args: Option<Vec<Vec<u8>>>,
+    deadline: Option<Instant>,
+    /// the database, key and version of each key the command waits for
+    keys: Vec<(usize, Vec<u8>, u64)>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Run {
+    Execute,
+    Retry,
+    Timeout,
+}
+
 pub struct Client {
     token: Token,
     stream: TcpStream,
+    session: Session,
+    last_interaction: Instant,
     incoming_end: usize,
     incoming: Vec<u8>,
     outgoing: Vec<u8>,
+    blocked: Option<Blocked>,
 }
 
 impl Client {
     pub fn new(token: Token, stream: TcpStream) -> Self {
+        let mut session = Session::new(token.0);
+        session.set_ip(stream.peer_addr().ok().map(|addr| addr.ip().to_string()));
+
         Self {
             token,
             stream,
+            session,
+            last_interaction: Instant::now(),
             incoming: vec![0; 1024],
             incoming_end: 0,
             outgoing: Vec::with_capacity(4 * 1024),
+            blocked: None,
         }
     }
 
+    /// Creates the client that applies the stream of commands from the leader,
+    /// starting with the part received along with the reply to PSYNC
+    /// in the database the stream last selected.
+    pub fn from_leader(token: Token, stream: TcpStream, received: Vec<u8>, db: usize) -> Self {
+        let mut client = Client::new(token, stream);
+
+        client.session.set_peer(Peer::Leader);
+        client.session.set_db(db);
+        client.incoming_end = received.len();
+        client.incoming = received;
+        client.incoming.resize(client.incoming_end + 1024, 0);
+        client
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
     pub fn start(&mut self, registry: &Registry) -> io::Result<()> {
         self.stream
             .register(registry, self.token, Interest::READABLE)
     }
 
+    pub fn idle_secs(&self) -> u64 {
+        self.last_interaction.elapsed().as_secs()
+    }
+
+    pub fn close(&mut self, registry: &Registry) -> io::Result<()> {
+        // the peer may have already closed its end
+        let _ = self.stream.shutdown(std::net::Shutdown::Both);
+        self.stream.deregister(registry)
+    }
+
     pub fn receive(&mut self, registry: &Registry) -> io::Result<bool> {
+        self.last_interaction = Instant::now();
         loop {
-            println!(
+            debug!(
                 "reading up to {} bytes",
                 self.incoming.len() - self.incoming_end
             );
             match self.stream.read(&mut self.incoming[self.incoming_end..]) {
                 Ok(0) => {
-                    println!("connection closed");
+                    debug!("connection closed");
 
-                    self.stream.shutdown(std::net::Shutdown::Both)?;
+                    // the peer may have reset the connection instead
+                    let _ = self.stream.shutdown(std::net::Shutdown::Both);
                     self.stream.deregister(registry)?;
                     return Ok(true);
                 }
                 Ok(n) => {
-                    println!("read {} bytes", n);
+                    debug!("read {} bytes", n);
 
                     self.incoming_end += n;
                     if self.incoming_end == self.incoming.len() {
@@ -61,54 +121,127 @@ impl Client {
                     }
                 }
                 Err(ref err) if would_block(err) => {
-                    println!(
+                    debug!(
                         "read {}",
-                        from_utf8(&self.incoming[..self.incoming_end]).unwrap()
+                        String::from_utf8_lossy(&self.incoming[..self.incoming_end])
                     );
 
                     return Ok(false);
                 }
                 Err(ref err) if interrupted(err) => {
-                    println!("interrupted");
+                    debug!("interrupted");
                 }
                 Err(err) => return Err(err),
             };
         }
     }
 
+    /// Runs the commands received so far until the client blocks,
+    /// or with `pause_when_ready` until one changes a watched key
+    /// so the clients blocked on it can be served before the next command.
+    /// Returns `true` if it paused.
     pub fn run_commands(
         &mut self,
         parser: &Parser,
-        store: &mut Store,
+        config: &mut Config,
+        stats: &mut Stats,
+        snapshots: &mut Snapshots,
+        aof: &mut Aof,
+        replication: &mut Replication,
+        pubsub: &mut PubSub,
+        databases: &mut [Store],
         registry: &Registry,
-    ) -> io::Result<()> {
+        pause_when_ready: bool,
+    ) -> io::Result<bool> {
         let mut index = 0;
+        let mut paused = false;
+        let from_leader = self.session.peer() == Peer::Leader;
 
-        while index < self.incoming_end {
+        while index < self.incoming_end && self.blocked.is_none() {
             match parser.try_next_input(&self.incoming[index..self.incoming_end]) {
-                Ok(Some((input, len))) => {
+                Ok(Some((mut input, len))) => {
                     index += len;
-                    match parser.try_parse_command(input) {
-                        Ok(command) => match command.apply(store) {
-                            Ok(response) => {
-                                self.write_response(&response)?;
+                    let proceed = match parser.try_parse_command(&mut input) {
+                        Ok(command)
+                            if command.is_write()
+                                && !from_leader
+                                && config.replica_of.is_some()
+                                && config.replica_read_only =>
+                        {
+                            self.abort_transaction();
+                            self.write_error(&Error::ReadOnly)?;
+                            false
+                        }
+                        Ok(command)
+                            if self.session.is_subscribed()
+                                && !self.is_resp3()
+                                && !command.is_subscriber() =>
+                        {
+                            self.write_error(&Error::String(format!(
+                                "ERR Can't execute '{}': only (P|S)SUBSCRIBE / (P|S)UNSUBSCRIBE / PING / QUIT / RESET are allowed in this context",
+                                input.command()
+                            )))?;
+                            false
+                        }
+                        Ok(command) if self.session.in_multi() && !command.is_transaction() => {
+                            let args = (command.is_write()
+                                && (aof.is_logging() || replication.is_feeding()))
+                            .then(|| input.to_vec());
+                            if let Some(transaction) = self.session.transaction_mut() {
+                                transaction.queue(command, args);
                             }
-                            Err(error) => {
-                                self.write_error(&error)?;
-                                break;
+                            if !from_leader {
+                                self.write(b"+QUEUED\r\n")?;
                             }
-                        },
+                            true
+                        }
+                        Ok(command) => {
+                            stats.total_commands_processed += 1;
+                            let args = (command.is_write()
+                                && (aof.is_logging() || replication.is_feeding()))
+                            .then(|| input.to_vec());
+                            self.run_command(
+                                command,
+                                args,
+                                Run::Execute,
+                                None,
+                                config,
+                                stats,
+                                snapshots,
+                                aof,
+                                replication,
+                                pubsub,
+                                databases,
+                            )?
+                        }
+                        Err(error) if from_leader => {
+                            warning!("unknown command from the master: {:?}", error);
+                            true
+                        }
                         Err(error) => {
+                            self.abort_transaction();
                             self.write_error(&error)?;
-                            break;
+                            false
                         }
+                    };
+                    if from_leader {
+                        replication.processed(&self.incoming[index - len..index]);
+                    }
+                    if !proceed {
+                        break;
+                    }
+                    if pause_when_ready && take_ready(databases) {
+                        paused = true;
+                        break;
                     }
                 }
                 Ok(None) => {
                     break;
                 }
                 Err(error) => {
+                    // there's no way to find the start of the next command
                     self.write_error(&error)?;
+                    index = self.incoming_end;
                     break;
                 }
             }
@@ -123,33 +256,319 @@ impl Client {
             Interest::READABLE | Interest::WRITABLE,
         )?;
 
+        Ok(paused)
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        self.blocked.is_some()
+    }
+
+    /// Returns when the blocked command times out, if ever.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.blocked.as_ref().and_then(|blocked| blocked.deadline)
+    }
+
+    /// Stops waiting for the keys of the blocked command, if any, once the client disconnects.
+    pub fn unblock(&mut self, databases: &mut [Store]) {
+        if let Some(blocked) = self.blocked.take() {
+            unwatch_keys(&blocked.keys, databases);
+        }
+    }
+
+    /// Runs the blocked command again, or its timeout if it has passed,
+    /// skipping the retry while none of the keys it waits for changed.
+    /// Returns `true` if the client is no longer blocked.
+    pub fn retry_blocked(
+        &mut self,
+        config: &mut Config,
+        stats: &mut Stats,
+        snapshots: &mut Snapshots,
+        aof: &mut Aof,
+        replication: &mut Replication,
+        pubsub: &mut PubSub,
+        databases: &mut [Store],
+        registry: &Registry,
+    ) -> io::Result<bool> {
+        let blocked = match self.blocked.take() {
+            Some(blocked) => blocked,
+            None => return Ok(true),
+        };
+        let run = if blocked
+            .deadline
+            .is_some_and(|deadline| deadline <= Instant::now())
+        {
+            Run::Timeout
+        } else {
+            Run::Retry
+        };
+        if run == Run::Retry
+            && !blocked.keys.is_empty()
+            && blocked
+                .keys
+                .iter()
+                .all(|(db, key, version)| databases[*db].version(key) == *version)
+        {
+            // none of the keys changed, so the command would block again
+            self.blocked = Some(blocked);
+            return Ok(false);
+        }
+
+        unwatch_keys(&blocked.keys, databases);
+        self.run_command(
+            blocked.command,
+            blocked.args,
+            run,
+            blocked.deadline,
+            config,
+            stats,
+            snapshots,
+            aof,
+            replication,
+            pubsub,
+            databases,
+        )?;
+        if self.blocked.is_some() {
+            return Ok(false);
+        }
+
+        // the reply counts as activity so the client isn't closed as idle before it's sent
+        self.last_interaction = Instant::now();
+        self.stream.reregister(
+            registry,
+            self.token,
+            Interest::READABLE | Interest::WRITABLE,
+        )?;
+        Ok(true)
+    }
+
+    /// Runs the command, logging and replicating it if it changed the databases,
+    /// and writes the response unless the client blocks.
+    /// Returns `false` if the command failed and the rest of the input must be skipped.
+    fn run_command(
+        &mut self,
+        command: Box<dyn Apply>,
+        args: Option<Vec<Vec<u8>>>,
+        run: Run,
+        deadline: Option<Instant>,
+        config: &mut Config,
+        stats: &mut Stats,
+        snapshots: &mut Snapshots,
+        aof: &mut Aof,
+        replication: &mut Replication,
+        pubsub: &mut PubSub,
+        databases: &mut [Store],
+    ) -> io::Result<bool> {
+        // a replica applies the commands from its leader without replying
+        let from_leader = self.session.peer() == Peer::Leader;
+        let db = self.session.db();
+        let mut propagated = None;
+        let context = Context::new(
+            &mut self.session,
+            config,
+            stats,
+            snapshots,
+            aof,
+            replication,
+            pubsub,
+            databases,
+            &mut propagated,
+        );
+        let result = match run {
+            Run::Execute => command.execute(context),
+            Run::Retry => command.retry(context),
+            Run::Timeout => command.timeout(context),
+        };
+
+        match result {
+            Ok(Response::Exec) => {
+                self.run_transaction(
+                    config,
+                    stats,
+                    snapshots,
+                    aof,
+                    replication,
+                    pubsub,
+                    databases,
+                )?;
+                Ok(true)
+            }
+            Ok(Response::Block(timeout)) => {
+                let deadline = match run {
+                    // a timeout too far out to represent is as good as waiting forever
+                    Run::Execute => timeout.and_then(|timeout| Instant::now().checked_add(timeout)),
+                    _ => deadline,
+                };
+                let keys = command
+                    .blocking_keys()
+                    .iter()
+                    .map(|key| (db, key.clone(), databases[db].watch(key)))
+                    .collect();
+                self.blocked = Some(Blocked {
+                    command,
+                    args,
+                    deadline,
+                    keys,
+                });
+                Ok(true)
+            }
+            Ok(response) => {
+                if let Some(args) = args {
+                    let commands = propagated.unwrap_or_else(|| vec![args]);
+                    aof.append(db, &commands, config.append_fsync);
+                    replication.feed(db, &commands);
+                }
+                self.session.set_repl_offset(replication.offset());
+                if !from_leader {
+                    self.write_response(&response)?;
+                }
+                Ok(true)
+            }
+            Err(error) if from_leader => {
+                debug!("error applying the stream from the master: {:?}", error);
+                Ok(true)
+            }
+            Err(error) => {
+                self.write_error(&error)?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// Runs the commands queued since MULTI one after the other, replying with an array
+    /// of their responses and wrapping their writes in MULTI and EXEC for the AOF and replicas.
+    fn run_transaction(
+        &mut self,
+        config: &mut Config,
+        stats: &mut Stats,
+        snapshots: &mut Snapshots,
+        aof: &mut Aof,
+        replication: &mut Replication,
+        pubsub: &mut PubSub,
+        databases: &mut [Store],
+    ) -> io::Result<()> {
+        let commands = self
+            .session
+            .take_transaction()
+            .map(Transaction::into_commands)
+            .unwrap_or_default();
+        let writes = commands.iter().any(|(_, args)| args.is_some());
+
+        if self.session.peer() != Peer::Leader {
+            self.write_string(format!("*{}\r\n", commands.len()))?;
+        }
+        if writes {
+            let multi = [vec![b"MULTI".to_vec()]];
+            aof.append(self.session.db(), &multi, config.append_fsync);
+            replication.feed(self.session.db(), &multi);
+        }
+        for (command, args) in commands {
+            self.run_command(
+                command,
+                args,
+                Run::Execute,
+                None,
+                config,
+                stats,
+                snapshots,
+                aof,
+                replication,
+                pubsub,
+                databases,
+            )?;
+            // a transaction can't wait, so a blocking command times out at once
+            if let Some(blocked) = self.blocked.take() {
+                unwatch_keys(&blocked.keys, databases);
+                self.run_command(
+                    blocked.command,
+                    blocked.args,
+                    Run::Timeout,
+                    None,
+                    config,
+                    stats,
+                    snapshots,
+                    aof,
+                    replication,
+                    pubsub,
+                    databases,
+                )?;
+            }
+        }
+        if writes {
+            let exec = [vec![b"EXEC".to_vec()]];
+            aof.append(self.session.db(), &exec, config.append_fsync);
+            replication.feed(self.session.db(), &exec);
+        }
         Ok(())
     }
 
+    /// Makes EXEC discard the transaction, if any, after a command failed to queue.
+    fn abort_transaction(&mut self) {
+        if let Some(transaction) = self.session.transaction_mut() {
+            transaction.abort();
+        }
+    }
+
     pub fn write(&mut self, data: &[u8]) -> io::Result<()> {
         self.outgoing.extend_from_slice(data);
         Ok(())
     }
 
+    /// Sends data that isn't a reply to a command, such as the stream to a replica.
+    pub fn push(&mut self, data: &[u8], registry: &Registry) -> io::Result<()> {
+        self.write(data)?;
+        self.stream.reregister(
+            registry,
+            self.token,
+            Interest::READABLE | Interest::WRITABLE,
+        )
+    }
+
+    /// Sends a response that isn't a reply to a command, such as a message to a subscriber.
+    pub fn push_response(&mut self, response: &Response, registry: &Registry) -> io::Result<()> {
+        self.write_response(response)?;
+        self.stream.reregister(
+            registry,
+            self.token,
+            Interest::READABLE | Interest::WRITABLE,
+        )
+    }
+
+    fn is_resp3(&self) -> bool {
+        self.session.protocol() == Protocol::Resp3
+    }
+
     pub fn write_response(&mut self, response: &Response) -> io::Result<()> {
         match response {
-            Response::Null => self.write(b"_\r\n"),
+            Response::Null if self.is_resp3() => self.write(b"_\r\n"),
+            Response::Null => self.write(b"$-1\r\n"),
+            Response::NullArray if self.is_resp3() => self.write(b"_\r\n"),
+            Response::NullArray => self.write(b"*-1\r\n"),
             Response::Ok => self.write(b"+OK\r\n"),
             Response::Zero => self.write(b":0\r\n"),
             Response::One => self.write(b":1\r\n"),
-            Response::False => self.write(b"#f\r\n"),
-            Response::True => self.write(b"#t\r\n"),
+            Response::False if self.is_resp3() => self.write(b"#f\r\n"),
+            Response::False => self.write(b":0\r\n"),
+            Response::True if self.is_resp3() => self.write(b"#t\r\n"),
+            Response::True => self.write(b":1\r\n"),
             Response::EmptyBulkString => self.write(b"$0\r\n\r\n"),
             Response::EmptyList => self.write(b"*0\r\n"),
-            Response::EmptyMap => self.write(b"%0\r\n"),
-            Response::EmptySet => self.write(b"~0\r\n"),
+            Response::EmptyMap if self.is_resp3() => self.write(b"%0\r\n"),
+            Response::EmptySet if self.is_resp3() => self.write(b"~0\r\n"),
+            Response::EmptyMap | Response::EmptySet => self.write(b"*0\r\n"),
             Response::I64(value) => self.write_integer(*value),
             Response::Usize(value) => self.write_usize(*value),
+            Response::Double(value) => self.write_double(*value),
             Response::Raw(data) => self.write(data),
+            Response::RawBytes(data) => self.write(data),
+            Response::Block(_) | Response::Exec | Response::NoReply => Ok(()),
             Response::SimpleString(s) => self.write_simple_string(s),
             Response::BulkString(s) => self.write_bulk_string(s),
             Response::BulkStringRef(s) => self.write_bulk_string(s),
             Response::List(list) => self.write_list(list),
+            Response::ScoredList(list) => self.write_scored_list(
+                list.len(),
+                list.iter().map(|(member, score)| (member, *score)),
+            ),
             Response::ListRef(list) => self.write_list(list),
             Response::Set(set) => self.write_set(set),
             Response::Value(value) => self.write_value(value),
@@ -161,14 +580,53 @@ impl Client {
                 }
                 Ok(())
             }
+            Response::Map(pairs) => {
+                if self.is_resp3() {
+                    self.write_string(format!("%{}\r\n", pairs.len()))?;
+                } else {
+                    self.write_string(format!("*{}\r\n", 2 * pairs.len()))?;
+                }
+                for (key, value) in pairs {
+                    self.write_response(key)?;
+                    self.write_response(value)?;
+                }
+                Ok(())
+            }
+            Response::Array(items) => {
+                self.write_string(format!("*{}\r\n", items.len()))?;
+                for item in items {
+                    self.write_response(item)?;
+                }
+                Ok(())
+            }
+            Response::Push(items) => {
+                if self.is_resp3() {
+                    self.write_string(format!(">{}\r\n", items.len()))?;
+                } else {
+                    self.write_string(format!("*{}\r\n", items.len()))?;
+                }
+                for item in items {
+                    self.write_response(item)?;
+                }
+                Ok(())
+            }
+            Response::Multiple(responses) => {
+                for response in responses {
+                    self.write_response(response)?;
+                }
+                Ok(())
+            }
         }
     }
 
     pub fn write_value(&mut self, value: &Value) -> io::Result<()> {
         match value {
+            Value::Hash(fields) => self.write_hash(fields),
             Value::List(values) => self.write_list(values),
-            Value::Integer(i) => self.write_integer(*i),
+            Value::Integer(i) => self.write_bulk_string(i.to_string().as_bytes()),
             Value::Set(members) => self.write_set(members),
+            Value::SortedSet(members) => self.write_scored_list(members.len(), members.iter()),
+            Value::Stream(stream) => self.write_stream(stream),
             Value::String(s) => self.write_bulk_string(s),
         }
     }
@@ -176,11 +634,15 @@ impl Client {
     pub fn write_error(&mut self, error: &Error) -> io::Result<()> {
         match error {
             Error::Raw(data) => self.write(data),
-            Error::String(s) => self.write_simple_string(s),
+            Error::String(s) => self.write_string(format!("-{}\r\n", s)),
             Error::Protocol => self.write(b"-broken protocol\r\n"),
             Error::UnknownCommand(command) => {
                 self.write_string(format!("-unknown command '{}'\r\n", command))
             }
+            Error::UnknownSubcommand(command, subcommand) => self.write_string(format!(
+                "-ERR unknown subcommand '{}'. Try {} HELP.\r\n",
+                subcommand, command
+            )),
             Error::UnknownOption(command, option) => self.write_string(format!(
                 "-unknown option '{}' for command '{}'\r\n",
                 option, command
@@ -197,6 +659,10 @@ impl Client {
             Error::Integer => self.write(b"-value is not an integer or out of range\r\n"),
             Error::ExpireTime => self.write(b"-invalid expire time\r\n"),
             Error::KeyNotFound => self.write(b"-no such key\r\n"),
+            Error::DbIndex => self.write(b"-ERR DB index is out of range\r\n"),
+            Error::ReadOnly => {
+                self.write(b"-READONLY You can't write against a read only replica.\r\n")
+            }
             Error::WrongType => self
                 .write(b"-WRONGTYPE Operation against a key holding the wrong kind of value\r\n"),
         }
@@ -210,8 +676,10 @@ impl Client {
         self.write_string(format!("+{}\r\n", value))
     }
 
-    pub fn write_bulk_string(&mut self, value: &str) -> io::Result<()> {
-        self.write_string(format!("${}\r\n{}\r\n", value.len(), value))
+    pub fn write_bulk_string(&mut self, value: &[u8]) -> io::Result<()> {
+        self.write_string(format!("${}\r\n", value.len()))?;
+        self.write(value)?;
+        self.write(b"\r\n")
     }
 
     pub fn write_integer(&mut self, value: i64) -> io::Result<()> {
@@ -222,7 +690,7 @@ impl Client {
         self.write_string(format!(":{}\r\n", value))
     }
 
-    pub fn write_list(&mut self, values: &[String]) -> io::Result<()> {
+    pub fn write_list(&mut self, values: &[Vec<u8>]) -> io::Result<()> {
         self.write_string(format!("*{}\r\n", values.len()))?;
         for value in values {
             self.write_bulk_string(value)?;
@@ -230,8 +698,67 @@ impl Client {
         Ok(())
     }
 
-    pub fn write_set(&mut self, members: &HashSet<String>) -> io::Result<()> {
-        self.write_string(format!("~{}\r\n", members.len()))?;
+    pub fn write_double(&mut self, value: f64) -> io::Result<()> {
+        if self.is_resp3() {
+            self.write_string(format!(",{}\r\n", format_score(value)))
+        } else {
+            self.write_bulk_string(format_score(value).as_bytes())
+        }
+    }
+
+    pub fn write_scored_list<'m>(
+        &mut self,
+        len: usize,
+        members: impl Iterator<Item = (&'m Vec<u8>, f64)>,
+    ) -> io::Result<()> {
+        if self.is_resp3() {
+            self.write_string(format!("*{}\r\n", len))?;
+        } else {
+            self.write_string(format!("*{}\r\n", 2 * len))?;
+        }
+        for (member, score) in members {
+            if self.is_resp3() {
+                self.write(b"*2\r\n")?;
+            }
+            self.write_bulk_string(member)?;
+            self.write_double(score)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_stream(&mut self, stream: &Stream) -> io::Result<()> {
+        self.write_string(format!("*{}\r\n", stream.len()))?;
+        for (id, fields) in stream.range(StreamId::MIN, StreamId::MAX) {
+            self.write(b"*2\r\n")?;
+            self.write_bulk_string(id.to_string().as_bytes())?;
+            self.write_string(format!("*{}\r\n", 2 * fields.len()))?;
+            for (field, value) in fields {
+                self.write_bulk_string(field)?;
+                self.write_bulk_string(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write_hash(&mut self, fields: &Hash) -> io::Result<()> {
+        if self.is_resp3() {
+            self.write_string(format!("%{}\r\n", fields.len()))?;
+        } else {
+            self.write_string(format!("*{}\r\n", 2 * fields.len()))?;
+        }
+        for (field, value) in fields.iter() {
+            self.write_bulk_string(field)?;
+            self.write_bulk_string(value)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_set(&mut self, members: &HashSet<Vec<u8>>) -> io::Result<()> {
+        if self.is_resp3() {
+            self.write_string(format!("~{}\r\n", members.len()))?;
+        } else {
+            self.write_string(format!("*{}\r\n", members.len()))?;
+        }
         for member in members {
             self.write_bulk_string(member)?;
         }
@@ -244,24 +771,27 @@ impl Client {
         let mut bytes_left = bytes_total;
 
         while bytes_left > 0 {
-            println!("writing up to {} bytes", bytes_left);
+            debug!("writing up to {} bytes", bytes_left);
             match self.stream.write(&self.outgoing[bytes_sent..bytes_total]) {
                 Ok(0) => {
-                    println!("connection closed");
+                    debug!("connection closed");
 
                     self.stream.deregister(registry)?;
                     return Ok(true);
                 }
                 Ok(n) => {
-                    println!("wrote {} bytes", n);
+                    debug!("wrote {} bytes", n);
 
                     bytes_sent += n;
                     bytes_left -= n;
                 }
                 Err(ref err) if would_block(err) => {
-                    println!("wrote {}", from_utf8(&self.outgoing[..bytes_sent]).unwrap());
+                    debug!(
+                        "wrote {}",
+                        String::from_utf8_lossy(&self.outgoing[..bytes_sent])
+                    );
                     if bytes_left > 0 {
-                        println!("did not write {} bytes", bytes_left);
+                        debug!("did not write {} bytes", bytes_left);
                         self.outgoing.copy_within(bytes_sent..bytes_total, 0);
                         self.outgoing.truncate(bytes_left);
                     }
@@ -269,13 +799,13 @@ impl Client {
                     return Ok(false);
                 }
                 Err(ref err) if interrupted(err) => {
-                    println!("interrupted");
+                    debug!("interrupted");
                 }
                 Err(err) => return Err(err),
             };
         }
 
-        println!("wrote {}", from_utf8(&self.outgoing).unwrap());
+        debug!("wrote {}", String::from_utf8_lossy(&self.outgoing));
         self.outgoing.truncate(0);
 
         // done writing
@@ -285,3 +815,18 @@ impl Client {
         Ok(false)
     }
 }
+
+/// Returns `true` if a watched key changed in any of the databases, clearing them all.
+fn take_ready(databases: &mut [Store]) -> bool {
+    let mut ready = false;
+    for store in databases {
+        ready |= store.take_ready();
+    }
+    ready
+}
+
+fn unwatch_keys(keys: &[(usize, Vec<u8>, u64)], databases: &mut [Store]) {
+    for (db, key, _) in keys {
+        databases[*db].unwatch(key);
+    }
+}
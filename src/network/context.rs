@@ -0,0 +1,83 @@
+use crate::config::Config;
+use crate::persistence::{Aof, Snapshots};
+use crate::storage::Store;
+
+use super::{Error, PubSub, Replication, Session, Stats};
+
+/// Context gives a command access to the connection that sent it
+/// and the server state in addition to the databases it operates on.
+pub struct Context<'a, 's> {
+    pub session: &'s mut Session,
+    pub config: &'s mut Config,
+    pub stats: &'s mut Stats,
+    pub snapshots: &'s mut Snapshots,
+    pub aof: &'s mut Aof,
+    pub replication: &'s mut Replication,
+    pub pubsub: &'s mut PubSub,
+    pub databases: &'a mut [Store],
+    /// the commands to append to the AOF and replication stream instead of the one being executed
+    pub propagated: &'s mut Option<Vec<Vec<Vec<u8>>>>,
+}
+
+impl<'a, 's> Context<'a, 's> {
+    pub fn new(
+        session: &'s mut Session,
+        config: &'s mut Config,
+        stats: &'s mut Stats,
+        snapshots: &'s mut Snapshots,
+        aof: &'s mut Aof,
+        replication: &'s mut Replication,
+        pubsub: &'s mut PubSub,
+        databases: &'a mut [Store],
+        propagated: &'s mut Option<Vec<Vec<Vec<u8>>>>,
+    ) -> Self {
+        Self {
+            session,
+            config,
+            stats,
+            snapshots,
+            aof,
+            replication,
+            pubsub,
+            databases,
+            propagated,
+        }
+    }
+
+    /// Returns the database selected by the connection.
+    pub fn store(self) -> &'a mut Store {
+        &mut self.databases[self.session.db()]
+    }
+
+    /// Returns the index of the database if it exists.
+    pub fn check_db(&self, db: i64) -> Result<usize, Error> {
+        if db >= 0 && (db as usize) < self.databases.len() {
+            Ok(db as usize)
+        } else {
+            Err(Error::DbIndex)
+        }
+    }
+
+    /// Appends the command to those written to the AOF and replicas in place of the one being executed.
+    /// Call `propagate_nothing` for a command that made no changes.
+    pub fn propagate(&mut self, args: Vec<Vec<u8>>) {
+        self.propagated.get_or_insert_with(Vec::new).push(args);
+    }
+
+    pub fn propagate_nothing(&mut self) {
+        self.propagated.get_or_insert_with(Vec::new);
+    }
+
+    /// Stops watching the connection's keys,
+    /// returning `true` if any changed since they were watched.
+    pub fn unwatch(&mut self) -> bool {
+        let mut changed = false;
+
+        for (db, key, version) in self.session.take_watched() {
+            let store = &mut self.databases[db];
+            changed |= store.version(&key) != version;
+            store.unwatch(&key);
+        }
+        changed
+    }
+}
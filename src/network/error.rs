@@ -4,6 +4,7 @@ pub enum Error {
     String(String),
     Protocol,
     UnknownCommand(String),
+    UnknownSubcommand(String, String),
     UnknownOption(String, String),
     DuplicateOption(String, String),
     MissingArgument(String, String),
@@ -11,5 +12,7 @@ pub enum Error {
     Integer,
     ExpireTime,
     KeyNotFound,
+    DbIndex,
+    ReadOnly,
     WrongType,
 }
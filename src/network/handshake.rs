@@ -0,0 +1,286 @@
+use std::io;
+use std::io::{Read, Write};
+use std::mem;
+use std::net::SocketAddr;
+
+use mio::{Interest, Registry, Token};
+use mio::event::{Event, Source};
+use mio::net::TcpStream;
+
+use crate::persistence::encode_command;
+
+use super::{interrupted, would_block};
+
+/// The reply to PSYNC and the data that follows it,
+/// where `stream` holds the start of the commands sent after the reply.
+pub enum Resync {
+    Full {
+        id: String,
+        offset: u64,
+        snapshot: Vec<u8>,
+        stream: Vec<u8>,
+    },
+    /// continues the stream, which the leader may have given a new id
+    Partial { id: Option<String>, stream: Vec<u8> },
+}
+
+enum State {
+    Connecting,
+    Ping,
+    ListeningPort,
+    Capa,
+    Psync,
+    Bulk { id: String, offset: u64 },
+    Snapshot { id: String, offset: u64, len: usize },
+}
+
+/// Handshake is a replica's connection to its leader until the full resync completes,
+/// after which it becomes a client that applies the stream of commands.
+pub struct Handshake {
+    token: Token,
+    stream: TcpStream,
+    state: State,
+    incoming: Vec<u8>,
+    /// the port this server accepts clients on to tell the leader
+    port: u16,
+    /// the stream id and offset of the next byte to ask the leader to continue from
+    psync: (String, u64),
+}
+
+impl Handshake {
+    pub fn connect(
+        addr: SocketAddr,
+        token: Token,
+        port: u16,
+        psync: (String, u64),
+        registry: &Registry,
+    ) -> io::Result<Self> {
+        let mut stream = TcpStream::connect(addr)?;
+
+        stream.register(registry, token, Interest::READABLE | Interest::WRITABLE)?;
+        Ok(Self {
+            token,
+            stream,
+            state: State::Connecting,
+            incoming: Vec::new(),
+            port,
+            psync,
+        })
+    }
+
+    pub fn token(&self) -> Token {
+        self.token
+    }
+
+    /// Returns `true` once the connection is established and the handshake is underway.
+    pub fn is_connected(&self) -> bool {
+        !matches!(self.state, State::Connecting)
+    }
+
+    pub fn close(mut self, registry: &Registry) -> io::Result<()> {
+        let _ = self.stream.shutdown(std::net::Shutdown::Both);
+        self.stream.deregister(registry)
+    }
+
+    /// Returns the connection and its token to continue reading the stream.
+    pub fn into_stream(mut self, registry: &Registry) -> io::Result<(Token, TcpStream)> {
+        self.stream.deregister(registry)?;
+        Ok((self.token, self.stream))
+    }
+
+    /// Advances the handshake as the leader replies,
+    /// returning how to resync once the leader decides.
+    pub fn handle(&mut self, event: &Event, registry: &Registry) -> io::Result<Option<Resync>> {
+        if let State::Connecting = self.state {
+            if let Some(err) = self.stream.take_error()? {
+                return Err(err);
+            }
+            match self.stream.peer_addr() {
+                Ok(_) => (),
+                Err(err) if err.kind() == io::ErrorKind::NotConnected => return Ok(None),
+                Err(err) => return Err(err),
+            }
+
+            self.stream
+                .reregister(registry, self.token, Interest::READABLE)?;
+            self.send(&["PING"])?;
+            self.state = State::Ping;
+        }
+
+        if event.is_readable() {
+            self.receive()?;
+        }
+
+        loop {
+            match mem::replace(&mut self.state, State::Connecting) {
+                State::Connecting => unreachable!("connected above"),
+                State::Ping => match self.next_line() {
+                    Some(line) if line.starts_with('-') => {
+                        return Err(error(format!(
+                            "Error reply to PING from master: '{}'",
+                            line
+                        )));
+                    }
+                    Some(_) => {
+                        let port = self.port.to_string();
+                        self.send(&["REPLCONF", "listening-port", &port])?;
+                        self.state = State::ListeningPort;
+                    }
+                    None => {
+                        self.state = State::Ping;
+                        return Ok(None);
+                    }
+                },
+                State::ListeningPort => match self.next_line() {
+                    Some(line) => {
+                        if line.starts_with('-') {
+                            notice!(
+                                "(Non critical) Master does not understand REPLCONF listening-port: {}",
+                                line
+                            );
+                        }
+                        self.send(&["REPLCONF", "capa", "psync2"])?;
+                        self.state = State::Capa;
+                    }
+                    None => {
+                        self.state = State::ListeningPort;
+                        return Ok(None);
+                    }
+                },
+                State::Capa => match self.next_line() {
+                    Some(line) => {
+                        if line.starts_with('-') {
+                            notice!(
+                                "(Non critical) Master does not understand REPLCONF capa: {}",
+                                line
+                            );
+                        }
+                        let (id, offset) = self.psync.clone();
+                        self.send(&["PSYNC", &id, &offset.to_string()])?;
+                        self.state = State::Psync;
+                    }
+                    None => {
+                        self.state = State::Capa;
+                        return Ok(None);
+                    }
+                },
+                State::Psync => match self.next_line() {
+                    Some(line) => {
+                        let mut parts = line.split(' ');
+                        match (parts.next(), parts.next(), parts.next().map(str::parse)) {
+                            (Some("+CONTINUE"), id, None) => {
+                                notice!("Successful partial resynchronization with master");
+                                return Ok(Some(Resync::Partial {
+                                    id: id.map(str::to_string),
+                                    stream: mem::take(&mut self.incoming),
+                                }));
+                            }
+                            (Some("+FULLRESYNC"), Some(id), Some(Ok(offset))) => {
+                                notice!("Full resync from master: {}:{}", id, offset);
+                                self.state = State::Bulk {
+                                    id: id.to_string(),
+                                    offset,
+                                };
+                            }
+                            _ => {
+                                return Err(error(format!(
+                                    "Unexpected reply to PSYNC from master: {}",
+                                    line
+                                )));
+                            }
+                        }
+                    }
+                    None => {
+                        self.state = State::Psync;
+                        return Ok(None);
+                    }
+                },
+                State::Bulk { id, offset } => {
+                    // a leader may send newlines to keep the link alive while it prepares the snapshot
+                    let newlines = self
+                        .incoming
+                        .iter()
+                        .take_while(|&&byte| byte == b'\n')
+                        .count();
+                    self.incoming.drain(..newlines);
+
+                    match self.next_line() {
+                        Some(line) => match line.strip_prefix('$').map(str::parse) {
+                            Some(Ok(len)) => self.state = State::Snapshot { id, offset, len },
+                            _ => {
+                                return Err(error(format!(
+                                    "Bad protocol from MASTER, the first byte is not '$': {}",
+                                    line
+                                )));
+                            }
+                        },
+                        None => {
+                            self.state = State::Bulk { id, offset };
+                            return Ok(None);
+                        }
+                    }
+                }
+                State::Snapshot { id, offset, len } => {
+                    if self.incoming.len() < len {
+                        self.state = State::Snapshot { id, offset, len };
+                        return Ok(None);
+                    }
+
+                    let stream = self.incoming.split_off(len);
+                    return Ok(Some(Resync::Full {
+                        id,
+                        offset,
+                        snapshot: mem::take(&mut self.incoming),
+                        stream,
+                    }));
+                }
+            }
+        }
+    }
+
+    fn send(&mut self, args: &[&str]) -> io::Result<()> {
+        let mut buffer = Vec::new();
+
+        encode_command(
+            &mut buffer,
+            &args
+                .iter()
+                .map(|arg| arg.as_bytes().to_vec())
+                .collect::<Vec<_>>(),
+        );
+        // the commands are small enough to fit in an empty socket buffer
+        self.stream.write_all(&buffer)
+    }
+
+    fn receive(&mut self) -> io::Result<()> {
+        let mut buffer = [0; 16 * 1024];
+
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "connection closed by the master",
+                    ));
+                }
+                Ok(n) => self.incoming.extend_from_slice(&buffer[..n]),
+                Err(ref err) if would_block(err) => return Ok(()),
+                Err(ref err) if interrupted(err) => (),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Removes and returns the next line of text received without its CRLF.
+    fn next_line(&mut self) -> Option<String> {
+        let end = self.incoming.windows(2).position(|pair| pair == b"\r\n")?;
+        let line = String::from_utf8_lossy(&self.incoming[..end]).into_owned();
+
+        self.incoming.drain(..end + 2);
+        Some(line)
+    }
+}
+
+fn error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::mem;
+
+use crate::storage::{Glob, key_slot};
+
+use super::Response;
+
+/// Message is a message published to a channel on its way to a subscriber.
+pub enum Message {
+    Channel {
+        channel: Vec<u8>,
+        payload: Vec<u8>,
+    },
+    /// a message to a channel the subscriber matched with the pattern
+    Pattern {
+        pattern: Vec<u8>,
+        channel: Vec<u8>,
+        payload: Vec<u8>,
+    },
+    Shard {
+        channel: Vec<u8>,
+        payload: Vec<u8>,
+    },
+}
+
+impl Message {
+    pub fn to_response(&self) -> Response<'_> {
+        match self {
+            Message::Channel { channel, payload } => Response::Push(vec![
+                Response::BulkStringRef(b"message"),
+                Response::BulkStringRef(channel),
+                Response::BulkStringRef(payload),
+            ]),
+            Message::Pattern {
+                pattern,
+                channel,
+                payload,
+            } => Response::Push(vec![
+                Response::BulkStringRef(b"pmessage"),
+                Response::BulkStringRef(pattern),
+                Response::BulkStringRef(channel),
+                Response::BulkStringRef(payload),
+            ]),
+            Message::Shard { channel, payload } => Response::Push(vec![
+                Response::BulkStringRef(b"smessage"),
+                Response::BulkStringRef(channel),
+                Response::BulkStringRef(payload),
+            ]),
+        }
+    }
+}
+
+/// PubSub tracks the sessions subscribed to each channel and pattern
+/// and holds published messages until the server writes them to the subscribers.
+///
+/// Shard channels are bucketed by the slot of their name
+/// so they can be split between the nodes of a cluster like keys.
+pub struct PubSub {
+    /// the subscribers to each channel in the order they subscribed
+    channels: HashMap<Vec<u8>, Vec<usize>>,
+    patterns: HashMap<Vec<u8>, (Glob, Vec<usize>)>,
+    shards: HashMap<u16, HashMap<Vec<u8>, Vec<usize>>>,
+    /// messages waiting to be written to each subscriber
+    outbox: Vec<(usize, Message)>,
+}
+
+impl PubSub {
+    pub fn new() -> Self {
+        Self {
+            channels: HashMap::new(),
+            patterns: HashMap::new(),
+            shards: HashMap::new(),
+            outbox: Vec::new(),
+        }
+    }
+
+    pub fn subscribe(&mut self, id: usize, channel: &[u8]) {
+        self.channels.entry(channel.to_vec()).or_default().push(id);
+    }
+
+    pub fn unsubscribe(&mut self, id: usize, channel: &[u8]) {
+        remove_subscriber(&mut self.channels, id, channel);
+    }
+
+    pub fn pattern_subscribe(&mut self, id: usize, pattern: &[u8]) {
+        self.patterns
+            .entry(pattern.to_vec())
+            .or_insert_with(|| (Glob::new(pattern), Vec::new()))
+            .1
+            .push(id);
+    }
+
+    pub fn pattern_unsubscribe(&mut self, id: usize, pattern: &[u8]) {
+        if let Some((_, subscribers)) = self.patterns.get_mut(pattern) {
+            subscribers.retain(|subscriber| *subscriber != id);
+            if subscribers.is_empty() {
+                self.patterns.remove(pattern);
+            }
+        }
+    }
+
+    pub fn shard_subscribe(&mut self, id: usize, channel: &[u8]) {
+        self.shards
+            .entry(key_slot(channel))
+            .or_default()
+            .entry(channel.to_vec())
+            .or_default()
+            .push(id);
+    }
+
+    pub fn shard_unsubscribe(&mut self, id: usize, channel: &[u8]) {
+        let slot = key_slot(channel);
+
+        if let Some(channels) = self.shards.get_mut(&slot) {
+            remove_subscriber(channels, id, channel);
+            if channels.is_empty() {
+                self.shards.remove(&slot);
+            }
+        }
+    }
+
+    /// Queues the message for each subscriber to the channel and each matching pattern.
+    /// Returns the number of subscribers that will receive it.
+    pub fn publish(&mut self, channel: &[u8], payload: &[u8]) -> usize {
+        let before = self.outbox.len();
+
+        for id in self.channels.get(channel).into_iter().flatten() {
+            self.outbox.push((
+                *id,
+                Message::Channel {
+                    channel: channel.to_vec(),
+                    payload: payload.to_vec(),
+                },
+            ));
+        }
+        for (pattern, (glob, subscribers)) in self.patterns.iter() {
+            if !glob.matches(channel) {
+                continue;
+            }
+            for id in subscribers {
+                self.outbox.push((
+                    *id,
+                    Message::Pattern {
+                        pattern: pattern.clone(),
+                        channel: channel.to_vec(),
+                        payload: payload.to_vec(),
+                    },
+                ));
+            }
+        }
+
+        self.outbox.len() - before
+    }
+
+    /// Queues the message for each subscriber to the shard channel.
+    /// Returns the number of subscribers that will receive it.
+    pub fn shard_publish(&mut self, channel: &[u8], payload: &[u8]) -> usize {
+        let subscribers = self
+            .shards
+            .get(&key_slot(channel))
+            .and_then(|channels| channels.get(channel))
+            .map_or(&[][..], |subscribers| subscribers.as_slice());
+
+        for id in subscribers {
+            self.outbox.push((
+                *id,
+                Message::Shard {
+                    channel: channel.to_vec(),
+                    payload: payload.to_vec(),
+                },
+            ));
+        }
+        subscribers.len()
+    }
+
+    /// Returns the messages to write to each subscriber.
+    pub fn take_messages(&mut self) -> Vec<(usize, Message)> {
+        mem::take(&mut self.outbox)
+    }
+
+    /// Returns the channels with at least one subscriber.
+    pub fn channels(&self) -> impl Iterator<Item = &Vec<u8>> {
+        self.channels.keys()
+    }
+
+    /// Returns the number of subscribers to the channel, not counting patterns.
+    pub fn subscribers(&self, channel: &[u8]) -> usize {
+        self.channels
+            .get(channel)
+            .map_or(0, |subscribers| subscribers.len())
+    }
+
+    /// Returns the number of patterns with at least one subscriber.
+    pub fn pattern_count(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Returns the shard channels with at least one subscriber.
+    pub fn shard_channels(&self) -> impl Iterator<Item = &Vec<u8>> {
+        self.shards.values().flat_map(|channels| channels.keys())
+    }
+
+    pub fn shard_subscribers(&self, channel: &[u8]) -> usize {
+        self.shards
+            .get(&key_slot(channel))
+            .and_then(|channels| channels.get(channel))
+            .map_or(0, |subscribers| subscribers.len())
+    }
+}
+
+fn remove_subscriber(channels: &mut HashMap<Vec<u8>, Vec<usize>>, id: usize, channel: &[u8]) {
+    if let Some(subscribers) = channels.get_mut(channel) {
+        subscribers.retain(|subscriber| *subscriber != id);
+        if subscribers.is_empty() {
+            channels.remove(channel);
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::VecDeque;
+use std::mem;
+
+use rand::{Rng, thread_rng};
+
+use crate::persistence::encode_command;
+
+/// The state of a replica's link to its leader as reported by ROLE.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LinkState {
+    Connect,
+    Connecting,
+    Sync,
+    Connected,
+}
+
+impl LinkState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkState::Connect => "connect",
+            LinkState::Connecting => "connecting",
+            LinkState::Sync => "sync",
+            LinkState::Connected => "connected",
+        }
+    }
+}
+
+/// Replica is a connection that completed a resync
+/// and receives the stream of write commands.
+pub struct Replica {
+    /// the id of the replica's session
+    pub id: usize,
+    pub ip: String,
+    /// the port the replica accepts clients on
+    pub port: u16,
+    /// the offset of the stream sent to the replica so far
+    pub offset: u64,
+    /// the offset of the stream the replica acknowledged applying
+    pub ack: u64,
+}
+
+/// Replication tracks the stream of write commands shared by a leader and its replicas.
+///
+/// Both sides identify the stream by its id and count the bytes in it with the offset:
+/// a leader as it feeds commands to its replicas and a replica as it applies them.
+/// The end of the stream is kept in a backlog so replicas that lose the link briefly
+/// may continue where they stopped instead of loading a new snapshot.
+pub struct Replication {
+    id: String,
+    /// the id of the stream this one continues and the offset it ended at
+    id2: Option<(String, u64)>,
+    offset: u64,
+    /// `true` while following a leader
+    replica: bool,
+    link: LinkState,
+    replicas: Vec<Replica>,
+    /// the end of the stream, created once there's a replica to continue it
+    backlog: Option<VecDeque<u8>>,
+    backlog_size: usize,
+    /// the stream added since it was last sent to the replicas
+    pending: Vec<u8>,
+    /// the database selected by the last command in the stream
+    db: Option<usize>,
+    /// the database the leader's stream had selected when the link was lost
+    leader_db: usize,
+    /// the sessions of replicas that must be disconnected
+    dropped: Vec<usize>,
+    /// ask the replicas to acknowledge the offset they've applied
+    getack: bool,
+    /// acknowledge the offset to the leader
+    ack: bool,
+}
+
+impl Replication {
+    pub fn new() -> Self {
+        Self {
+            id: new_id(),
+            id2: None,
+            offset: 0,
+            replica: false,
+            link: LinkState::Connect,
+            replicas: Vec::new(),
+            backlog: None,
+            backlog_size: 1024 * 1024,
+            pending: Vec::new(),
+            db: None,
+            leader_db: 0,
+            dropped: Vec::new(),
+            getack: false,
+            ack: false,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn link(&self) -> LinkState {
+        self.link
+    }
+
+    pub fn set_link(&mut self, link: LinkState) {
+        self.link = link;
+    }
+
+    pub fn replicas(&self) -> &[Replica] {
+        &self.replicas
+    }
+
+    pub fn has_backlog(&self) -> bool {
+        self.backlog.is_some()
+    }
+
+    /// Returns the number of bytes in the backlog.
+    pub fn backlog_len(&self) -> usize {
+        self.backlog.as_ref().map_or(0, |backlog| backlog.len())
+    }
+
+    /// Limits the backlog to the `repl-backlog-size`, dropping the oldest bytes.
+    pub fn set_backlog_size(&mut self, size: usize) {
+        self.backlog_size = size;
+        if let Some(backlog) = &mut self.backlog {
+            let excess = backlog.len().saturating_sub(size);
+            backlog.drain(..excess);
+        }
+    }
+
+    /// Returns `true` if write commands must be passed to `feed`.
+    pub fn is_feeding(&self) -> bool {
+        !self.replica && self.backlog.is_some()
+    }
+
+    /// Adds the commands executed against the database to the stream.
+    pub fn feed(&mut self, db: usize, commands: &[Vec<Vec<u8>>]) {
+        if !self.is_feeding() || commands.is_empty() {
+            return;
+        }
+
+        let mut data = Vec::new();
+        if self.db != Some(db) {
+            encode_command(
+                &mut data,
+                &[b"SELECT".to_vec(), db.to_string().into_bytes()],
+            );
+            self.db = Some(db);
+        }
+        for command in commands {
+            encode_command(&mut data, command);
+        }
+        self.append(&data);
+    }
+
+    /// Counts the bytes of the stream applied by a replica,
+    /// which passes them on to its own replicas.
+    pub fn processed(&mut self, data: &[u8]) {
+        self.append(data);
+    }
+
+    fn append(&mut self, data: &[u8]) {
+        if let Some(backlog) = &mut self.backlog {
+            backlog.extend(data);
+            let excess = backlog.len().saturating_sub(self.backlog_size);
+            backlog.drain(..excess);
+            self.pending.extend_from_slice(data);
+        }
+        self.offset += data.len() as u64;
+    }
+
+    /// Returns the part of the stream a replica is missing to continue from the offset
+    /// of the next byte it needs, or `None` if it must resync in full.
+    pub fn try_continue(&self, id: &str, offset: u64) -> Option<Vec<u8>> {
+        let known = id == self.id
+            || self
+                .id2
+                .as_ref()
+                .is_some_and(|(id2, end)| id == id2 && offset <= end + 1);
+        let backlog = self.backlog.as_ref()?;
+        let start = self.offset - backlog.len() as u64;
+
+        // offsets sent by replicas count from one
+        let offset = offset.checked_sub(1)?;
+        if !known || offset < start || offset > self.offset {
+            return None;
+        }
+        Some(
+            backlog
+                .range((offset - start) as usize..)
+                .copied()
+                .collect(),
+        )
+    }
+
+    /// Starts streaming to a replica that was sent the stream up to the current offset.
+    pub fn add_replica(&mut self, id: usize, ip: String, port: u16) {
+        if self.backlog.is_none() {
+            self.backlog = Some(VecDeque::new());
+        }
+        self.replicas.retain(|replica| replica.id != id);
+        self.replicas.push(Replica {
+            id,
+            ip,
+            port,
+            offset: self.offset,
+            ack: 0,
+        });
+        // the replica doesn't know which database the stream selected
+        self.db = None;
+    }
+
+    pub fn remove_replica(&mut self, id: usize) {
+        self.replicas.retain(|replica| replica.id != id);
+    }
+
+    /// Records the offset a replica applied as sent by REPLCONF ACK.
+    pub fn acknowledge(&mut self, id: usize, offset: u64) {
+        if let Some(replica) = self.replicas.iter_mut().find(|replica| replica.id == id) {
+            replica.ack = replica.ack.max(offset);
+        }
+    }
+
+    /// Returns the number of replicas that acknowledged applying the stream up to the offset.
+    pub fn acknowledged(&self, offset: u64) -> usize {
+        self.replicas
+            .iter()
+            .filter(|replica| replica.ack >= offset)
+            .count()
+    }
+
+    /// Asks the replicas to acknowledge their offsets the next time the stream is sent.
+    pub fn request_getack(&mut self) {
+        self.getack = true;
+    }
+
+    /// Acknowledges the offset to the leader the next time the server loop runs.
+    pub fn request_ack(&mut self) {
+        self.ack = true;
+    }
+
+    /// Returns `true` once after `request_ack` was called.
+    pub fn take_ack(&mut self) -> bool {
+        mem::take(&mut self.ack)
+    }
+
+    /// Passes the part of the pending stream that each replica hasn't been sent to `send`.
+    pub fn send(&mut self, mut send: impl FnMut(usize, &[u8])) {
+        if mem::take(&mut self.getack) && self.is_feeding() {
+            let mut data = Vec::new();
+            encode_command(
+                &mut data,
+                &[b"REPLCONF".to_vec(), b"GETACK".to_vec(), b"*".to_vec()],
+            );
+            self.append(&data);
+        }
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let start = self.offset - self.pending.len() as u64;
+        for replica in self.replicas.iter_mut() {
+            send(
+                replica.id,
+                &self.pending[(replica.offset - start) as usize..],
+            );
+            replica.offset = self.offset;
+        }
+        self.pending.clear();
+    }
+
+    /// Returns the sessions of the replicas that must be disconnected.
+    pub fn take_dropped(&mut self) -> Vec<usize> {
+        mem::take(&mut self.dropped)
+    }
+
+    /// Prepares to follow a leader, disconnecting the replicas
+    /// since the stream they were sent may be replaced.
+    pub fn follow(&mut self) {
+        self.replica = true;
+        self.drop_replicas();
+        self.link = LinkState::Connect;
+    }
+
+    /// Starts a new stream after a replica is promoted to leader
+    /// that replicas of the old leader may continue.
+    pub fn promote(&mut self) {
+        self.replica = false;
+        self.id2 = Some((mem::replace(&mut self.id, new_id()), self.offset));
+        self.db = None;
+        self.link = LinkState::Connect;
+    }
+
+    /// Starts the stream from the leader after loading its snapshot.
+    pub fn synced(&mut self, id: String, offset: u64) {
+        self.id = id;
+        self.id2 = None;
+        self.offset = offset;
+        self.backlog = Some(VecDeque::new());
+        self.leader_db = 0;
+        self.drop_replicas();
+        self.link = LinkState::Connected;
+    }
+
+    /// Continues the stream from the leader, which may have been given a new id.
+    pub fn continued(&mut self, id: Option<String>) {
+        if let Some(id) = id.filter(|id| *id != self.id) {
+            self.id2 = Some((mem::replace(&mut self.id, id), self.offset));
+            // let the replicas learn the new id
+            self.drop_replicas();
+        }
+        self.link = LinkState::Connected;
+    }
+
+    /// Returns the database the stream from the leader had selected when the link was lost.
+    pub fn leader_db(&self) -> usize {
+        self.leader_db
+    }
+
+    pub fn set_leader_db(&mut self, db: usize) {
+        self.leader_db = db;
+    }
+
+    fn drop_replicas(&mut self) {
+        self.dropped
+            .extend(self.replicas.drain(..).map(|replica| replica.id));
+        self.pending.clear();
+    }
+}
+
+/// Returns a random stream id of 40 hex digits.
+fn new_id() -> String {
+    let mut rng = thread_rng();
+
+    (0..40)
+        .map(|_| char::from_digit(rng.gen_range(0..16), 16).expect("hex digit"))
+        .collect()
+}
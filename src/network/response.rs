@@ -1,10 +1,12 @@
 use std::collections::HashSet;
+use std::time::Duration;
 
 use crate::storage::Value;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Response<'a> {
     Null,
+    NullArray,
     Ok,
     Zero,
     One,
@@ -15,17 +17,34 @@ pub enum Response<'a> {
     EmptyMap,
     EmptySet,
     I64(i64),
+    /// a double in RESP3 and a bulk string in RESP2
+    Double(f64),
     Usize(usize),
     Raw(&'static [u8]),
+    RawBytes(Vec<u8>),
     SimpleString(String),
-    BulkString(String),
-    BulkStringRef(&'a str),
-    List(Vec<String>),
-    ListRef(&'a [String]),
-    Set(HashSet<String>),
+    BulkString(Vec<u8>),
+    BulkStringRef(&'a [u8]),
+    List(Vec<Vec<u8>>),
+    /// members with scores, paired in RESP3 and flattened in RESP2
+    ScoredList(Vec<(Vec<u8>, f64)>),
+    ListRef(&'a [Vec<u8>]),
+    Set(HashSet<Vec<u8>>),
     Value(Value),
     ValueRef(&'a Value),
     ValueList(Vec<Value>),
+    Map(Vec<(Response<'a>, Response<'a>)>),
+    Array(Vec<Response<'a>>),
+    /// an out-of-band message sent as a push in RESP3 and an array in RESP2
+    Push(Vec<Response<'a>>),
+    /// several replies to a single command
+    Multiple(Vec<Response<'a>>),
+    /// runs the commands queued by the connection's transaction and replies with their responses
+    Exec,
+    /// blocks the client until `Apply::retry` responds or the timeout, if any, passes
+    Block(Option<Duration>),
+    /// sends nothing back to the client
+    NoReply,
 }
 
 impl<'a> Response<'a> {
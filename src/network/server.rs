@@ -1,87 +1,756 @@
 use std::collections::HashMap;
+use std::fs;
+use std::fs::OpenOptions;
 use std::io;
-use std::net::SocketAddr;
+use std::io::Write;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
+use chrono::Utc;
 use mio::{Events, Interest, Poll, Token};
 use mio::event::Event;
-use mio::net::TcpListener;
+use mio::net::{TcpListener, TcpStream};
 
+use crate::config::Config;
 use crate::parse::Parser;
+use crate::persistence::{Aof, decode_snapshot, encode_command, Snapshots};
 use crate::storage::Store;
 
-use super::{Client, interrupted};
+use super::{
+    Client, Context, Handshake, interrupted, LinkState, Peer, PubSub, Replication, Resync, Session,
+    Stats,
+};
 
-const SERVER: Token = Token(0);
+/// The Redis version this server reports to clients.
+pub const VERSION: &str = "7.2.0";
+
+/// The number of keys to expire between checks of the cycle's time limit.
+const EXPIRE_BATCH_SIZE: usize = 20;
+
+/// The longest a single expiration cycle may block the event loop.
+const EXPIRE_CYCLE_TIME: Duration = Duration::from_millis(25);
+
+/// How long a replica waits before connecting to its leader again.
+const CONNECT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// How often a replica acknowledges the offset it applied to its leader.
+const ACK_INTERVAL: Duration = Duration::from_secs(1);
 
 pub struct Server {
+    config: Config,
+    stats: Stats,
+    snapshots: Snapshots,
+    aof: Aof,
+    replication: Replication,
+    pubsub: PubSub,
     parser: Parser,
-    store: Store,
+    databases: Vec<Store>,
+    /// the database the next expiration cycle starts from
+    expire_db: usize,
     poll: Poll,
     last_token: Token,
     clients: HashMap<Token, Client>,
+    /// clients waiting for their command to run again in the order they blocked
+    blocked: Vec<Token>,
+    /// whether blocked clients are being served, so the commands they go on to run
+    /// don't pause to serve them again
+    serving_blocked: bool,
+    /// the leader this replica follows, which trails `replicaof` until the next loop
+    following: Option<(String, u16)>,
+    handshake: Option<Handshake>,
+    /// the client applying the stream from the leader after the handshake
+    leader: Option<Token>,
+    last_connect: Option<Instant>,
+    last_ack: Instant,
 }
 
 impl Server {
-    pub fn new() -> io::Result<Self> {
+    pub fn new(config: Config) -> io::Result<Self> {
+        let databases = (0..config.databases).map(|_| Store::new()).collect();
+
         Ok(Self {
+            config,
+            stats: Stats::new(),
+            snapshots: Snapshots::new(),
+            aof: Aof::new(),
+            replication: Replication::new(),
+            pubsub: PubSub::new(),
             parser: Parser::new(),
-            store: Store::new(),
+            databases,
+            expire_db: 0,
             poll: Poll::new()?,
-            last_token: SERVER,
+            last_token: Token(0),
             clients: HashMap::new(),
+            blocked: Vec::new(),
+            serving_blocked: false,
+            following: None,
+            handshake: None,
+            leader: None,
+            last_connect: None,
+            last_ack: Instant::now(),
         })
     }
 
-    pub fn start(&mut self, addr: SocketAddr) -> io::Result<()> {
-        let mut listener = TcpListener::bind(addr)?;
+    pub fn start(&mut self) -> io::Result<()> {
+        let mut listeners = Vec::with_capacity(self.config.bind.len());
         let mut events = Events::with_capacity(128);
 
-        self.poll
-            .registry()
-            .register(&mut listener, SERVER, Interest::READABLE)?;
+        self.load_data()?;
+
+        // listeners use the first tokens, and clients get the rest
+        for ip in self.config.bind.clone() {
+            let addr = SocketAddr::new(ip, self.config.port);
+            let mut listener = TcpListener::bind(addr)?;
+            let token = Token(listeners.len());
 
-        println!("listening for connections");
+            self.poll
+                .registry()
+                .register(&mut listener, token, Interest::READABLE)?;
+            notice!("listening for connections on {}", addr);
 
+            listeners.push(listener);
+        }
+        self.last_token = Token(listeners.len() - 1);
+
+        let mut expire_pending = false;
         loop {
-            if let Err(err) = self.poll.poll(&mut events, None) {
+            let timeout = self.poll_timeout(expire_pending);
+
+            if let Err(err) = self.poll.poll(&mut events, timeout) {
                 if interrupted(&err) {
                     continue;
                 }
                 return Err(err);
             }
 
+            expire_pending = self.expire_keys();
+
             for event in events.iter() {
                 match event.token() {
-                    SERVER => loop {
-                        let (stream, address) = match listener.accept() {
+                    Token(index) if index < listeners.len() => loop {
+                        let (mut stream, address) = match listeners[index].accept() {
                             Ok((stream, address)) => (stream, address),
                             Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
                                 // no more incoming connections
                                 break;
                             }
                             Err(e) => {
-                                return Err(e);
+                                warning!("Error accepting a connection: {}", e);
+                                break;
                             }
                         };
 
-                        println!("accepted connection from: {}", address);
+                        verbose!("accepted connection from: {}", address);
+                        self.stats.total_connections_received += 1;
+
+                        if self.clients.len() >= self.config.max_clients {
+                            warning!("rejected connection from: {}", address);
+                            self.stats.rejected_connections += 1;
+                            let _ = stream.write(b"-ERR max number of clients reached\r\n");
+                            continue;
+                        }
 
                         let token = self.next_token();
                         let mut client = Client::new(token, stream);
 
-                        client.start(self.poll.registry())?;
-                        self.clients.insert(token, client);
+                        match client.start(self.poll.registry()) {
+                            Ok(()) => {
+                                self.clients.insert(token, client);
+                            }
+                            Err(err) => warning!("Error registering a connection: {}", err),
+                        }
+                    },
+                    token if self.handshake.as_ref().is_some_and(|h| h.token() == token) => {
+                        self.handle_handshake(event);
+                    }
+                    token => match self.handle_event(token, event) {
+                        Ok(true) => self.remove_client(token),
+                        Ok(false) => (),
+                        Err(err) => self.drop_client(token, err),
                     },
-                    token => {
-                        let done = self.handle_event(token, event)?;
+                }
+            }
 
-                        if done {
-                            self.clients.remove(&token);
-                        }
+            self.run_blocked();
+            self.deliver_messages();
+            if self.config.timeout > 0 {
+                self.close_idle_clients();
+            }
+            self.run_replication();
+            self.snapshots.run(&self.config, &self.databases);
+            self.aof.run(&self.config, &self.databases);
+            self.aof.flush(self.config.append_fsync);
+        }
+    }
+
+    /// Loads the databases from the append-only file when it's enabled
+    /// and otherwise from the snapshot.
+    fn load_data(&mut self) -> io::Result<()> {
+        if !self.config.append_only {
+            return self.load_snapshot();
+        }
+
+        let path = PathBuf::from(&self.config.append_filename);
+        if path.exists() {
+            self.load_append_only_file(&path)?;
+        } else {
+            self.load_snapshot()?;
+        }
+        self.aof.open(&path, &self.databases)
+    }
+
+    /// Replays the commands in the append-only file,
+    /// truncating an incomplete command at the end left by a crash.
+    fn load_append_only_file(&mut self, path: &Path) -> io::Result<()> {
+        let start = Instant::now();
+        let data = fs::read(path)?;
+        let mut session = Session::new(0);
+        let mut index = 0;
+
+        while index < data.len() {
+            match self.parser.try_next_input(&data[index..]) {
+                Ok(Some((mut input, len))) => {
+                    index += len;
+                    let command = self.parser.try_parse_command(&mut input).map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "Bad file format reading the append only file",
+                        )
+                    })?;
+                    let mut propagated = None;
+                    let context = Context::new(
+                        &mut session,
+                        &mut self.config,
+                        &mut self.stats,
+                        &mut self.snapshots,
+                        &mut self.aof,
+                        &mut self.replication,
+                        &mut self.pubsub,
+                        &mut self.databases,
+                        &mut propagated,
+                    );
+                    if let Err(err) = command.execute(context) {
+                        debug!("error replaying the append only file: {:?}", err);
+                    }
+                }
+                Ok(None) => {
+                    warning!(
+                        "Short read while loading the append only file, truncating it to {} bytes",
+                        index
+                    );
+                    OpenOptions::new()
+                        .write(true)
+                        .open(path)?
+                        .set_len(index as u64)?;
+                    break;
+                }
+                Err(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Bad file format reading the append only file",
+                    ));
+                }
+            }
+        }
+
+        self.snapshots.clear_changes(&self.databases);
+        notice!(
+            "DB loaded from append only file: {:.3} seconds",
+            start.elapsed().as_secs_f64()
+        );
+        Ok(())
+    }
+
+    fn load_snapshot(&mut self) -> io::Result<()> {
+        let path = PathBuf::from(&self.config.db_filename);
+        let start = Instant::now();
+
+        match self.snapshots.load(&path, &mut self.databases) {
+            Ok(true) => {
+                notice!(
+                    "DB loaded from disk: {:.3} seconds",
+                    start.elapsed().as_secs_f64()
+                );
+                Ok(())
+            }
+            Ok(false) => Ok(()),
+            Err(err) => {
+                warning!("Error loading DB from {}: {}", path.display(), err);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns how long to wait for events before the next expiration is due
+    /// or idle clients must be checked, or `None` to wait indefinitely.
+    fn poll_timeout(&self, expire_pending: bool) -> Option<Duration> {
+        if expire_pending {
+            return Some(Duration::ZERO);
+        }
+
+        // wake up periodically to close idle clients, check save points, sync the AOF,
+        // and reconnect to the leader,
+        // and more often to collect a background save or rewrite
+        let idle = if self.snapshots.in_progress() || self.aof.in_progress() {
+            Some(Duration::from_millis(100))
+        } else if self.config.timeout > 0
+            || !self.config.save.is_empty()
+            || self.config.append_only
+            || self.config.replica_of.is_some()
+        {
+            Some(Duration::from_secs(1))
+        } else {
+            None
+        };
+        let expire = self
+            .databases
+            .iter()
+            .filter_map(|store| store.next_expiration())
+            .min()
+            .map(|at| {
+                let millis = (*at - Utc::now()).num_milliseconds() + 1;
+                Duration::from_millis(millis.max(0) as u64)
+            });
+        let blocked = self
+            .blocked
+            .iter()
+            .filter_map(|token| self.clients.get(token)?.deadline())
+            .min()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+        [idle, expire, blocked].into_iter().flatten().min()
+    }
+
+    /// Removes expired keys and hash fields from each database in batches
+    /// until none are due or the cycle runs out of time.
+    /// Each cycle starts from the database after the last one it visited,
+    /// so a database with many expired keys can't starve the ones after it.
+    /// Returns `true` if expired keys remain for the next cycle.
+    fn expire_keys(&mut self) -> bool {
+        let start = Instant::now();
+
+        for _ in 0..self.databases.len() {
+            let index = self.expire_db;
+            self.expire_db = (index + 1) % self.databases.len();
+            let store = &mut self.databases[index];
+
+            loop {
+                let expired = store.expire_items(EXPIRE_BATCH_SIZE);
+                let expired_fields = store.expire_fields(EXPIRE_BATCH_SIZE);
+                self.stats.expired_keys += expired as u64;
+                self.stats.expired_subkeys += expired_fields as u64;
+
+                if expired < EXPIRE_BATCH_SIZE && expired_fields < EXPIRE_BATCH_SIZE {
+                    break;
+                }
+                if start.elapsed() >= EXPIRE_CYCLE_TIME {
+                    debug!("expiration cycle ran out of time");
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Closes the connections of clients idle longer than the timeout,
+    /// except for those waiting on a blocking command or subscribed to messages.
+    fn close_idle_clients(&mut self) {
+        let timeout = self.config.timeout;
+
+        let idle = self
+            .clients
+            .iter()
+            .filter(|(_, client)| {
+                client.session().peer() == Peer::Client
+                    && !client.is_blocked()
+                    && !client.session().is_subscribed()
+                    && client.idle_secs() >= timeout
+            })
+            .map(|(token, _)| *token)
+            .collect::<Vec<_>>();
+
+        for token in idle {
+            if let Some(client) = self.clients.get_mut(&token) {
+                verbose!("closing idle client");
+                // the peer may have already closed its end
+                let _ = client.close(self.poll.registry());
+                self.remove_client(token);
+            }
+        }
+    }
+
+    /// Closes a connection that failed as though the peer had closed it.
+    fn drop_client(&mut self, token: Token, err: io::Error) {
+        let client = match self.clients.get_mut(&token) {
+            Some(client) => client,
+            None => return,
+        };
+        verbose!("closing connection after error: {}", err);
+        // the socket may already be gone
+        let _ = client.close(self.poll.registry());
+        self.remove_client(token);
+    }
+
+    /// Forgets a closed connection, reconnecting to the leader if it was the link to it.
+    fn remove_client(&mut self, token: Token) {
+        let mut client = self.clients.remove(&token);
+        self.replication.remove_replica(token.0);
+        self.blocked.retain(|blocked| *blocked != token);
+        if let Some(client) = &mut client {
+            client.unblock(&mut self.databases);
+            for channel in client.session().channels() {
+                self.pubsub.unsubscribe(token.0, channel);
+            }
+            for pattern in client.session().patterns() {
+                self.pubsub.pattern_unsubscribe(token.0, pattern);
+            }
+            for channel in client.session().shard_channels() {
+                self.pubsub.shard_unsubscribe(token.0, channel);
+            }
+            for (db, key, _) in client.session().watched() {
+                self.databases[*db].unwatch(key);
+            }
+        }
+
+        if self.leader == Some(token) {
+            warning!("Connection with master lost.");
+            if let Some(client) = client {
+                self.replication.set_leader_db(client.session().db());
+            }
+            self.leader = None;
+            self.replication.set_link(LinkState::Connect);
+            self.last_connect = Some(Instant::now());
+        }
+    }
+
+    /// Runs the commands of blocked clients again in the order they blocked,
+    /// continuing with the rest of their input once they unblock.
+    fn run_blocked(&mut self) {
+        // every blocked client is checked here, so earlier changes no longer need a pause
+        for store in self.databases.iter_mut() {
+            store.take_ready();
+        }
+        self.serving_blocked = true;
+        loop {
+            let mut unblocked = false;
+
+            for token in self.blocked.clone() {
+                match self.retry_blocked(token) {
+                    Ok(true) => unblocked = true,
+                    Ok(false) => (),
+                    Err(err) => {
+                        self.drop_client(token, err);
+                        unblocked = true;
                     }
                 }
             }
+
+            // unblocked clients may have run commands that other blocked clients wait for
+            if !unblocked {
+                self.serving_blocked = false;
+                return;
+            }
+        }
+    }
+
+    /// Runs the command of a blocked client again, returning `true` if it unblocked.
+    fn retry_blocked(&mut self, token: Token) -> io::Result<bool> {
+        let registry = self.poll.registry();
+        let client = match self.clients.get_mut(&token) {
+            Some(client) => client,
+            None => return Ok(false),
+        };
+        if !client.retry_blocked(
+            &mut self.config,
+            &mut self.stats,
+            &mut self.snapshots,
+            &mut self.aof,
+            &mut self.replication,
+            &mut self.pubsub,
+            &mut self.databases,
+            registry,
+        )? {
+            return Ok(false);
+        }
+
+        self.blocked.retain(|blocked| *blocked != token);
+        self.run_commands(token)?;
+        Ok(true)
+    }
+
+    /// Runs the commands the client sent, serving the clients blocked on a key
+    /// as soon as a command changes it and before the next command runs,
+    /// so they get what was pushed for them in the order they blocked.
+    fn run_commands(&mut self, token: Token) -> io::Result<()> {
+        loop {
+            let registry = self.poll.registry();
+            let client = match self.clients.get_mut(&token) {
+                Some(client) => client,
+                None => return Ok(()),
+            };
+            let pause_when_ready = !self.blocked.is_empty() && !self.serving_blocked;
+            let paused = client.run_commands(
+                &self.parser,
+                &mut self.config,
+                &mut self.stats,
+                &mut self.snapshots,
+                &mut self.aof,
+                &mut self.replication,
+                &mut self.pubsub,
+                &mut self.databases,
+                registry,
+                pause_when_ready,
+            )?;
+            if client.is_blocked() && !self.blocked.contains(&token) {
+                self.blocked.push(token);
+            }
+            if !paused {
+                return Ok(());
+            }
+            self.run_blocked();
+        }
+    }
+
+    /// Writes the messages published since the last loop to their subscribers.
+    fn deliver_messages(&mut self) {
+        let registry = self.poll.registry();
+        let mut failed = Vec::new();
+
+        for (id, message) in self.pubsub.take_messages() {
+            if let Some(client) = self.clients.get_mut(&Token(id)) {
+                if let Err(err) = client.push_response(&message.to_response(), registry) {
+                    failed.push((Token(id), err));
+                }
+            }
+        }
+        for (token, err) in failed {
+            self.drop_client(token, err);
+        }
+    }
+
+    /// Follows the leader set by `replicaof`, acknowledges the offset to it,
+    /// disconnects replicas that must resync, and sends the stream of commands to the rest.
+    fn run_replication(&mut self) {
+        if self.following != self.config.replica_of {
+            self.disconnect_leader();
+            match self.config.replica_of {
+                Some(_) => self.replication.follow(),
+                None => self.replication.promote(),
+            }
+            self.following = self.config.replica_of.clone();
+            self.last_connect = None;
+        }
+        self.replication
+            .set_backlog_size(self.config.repl_backlog_size);
+
+        if let Some((host, port)) = self.following.clone() {
+            if self.handshake.is_none()
+                && self.leader.is_none()
+                && self
+                    .last_connect
+                    .is_none_or(|at| at.elapsed() >= CONNECT_RETRY_DELAY)
+            {
+                self.last_connect = Some(Instant::now());
+                self.connect_to_leader(&host, port);
+            }
+        }
+
+        let registry = self.poll.registry();
+        if let Some(client) = self.leader.and_then(|token| self.clients.get_mut(&token)) {
+            if self.replication.take_ack() || self.last_ack.elapsed() >= ACK_INTERVAL {
+                let mut data = Vec::new();
+                encode_command(
+                    &mut data,
+                    &[
+                        b"REPLCONF".to_vec(),
+                        b"ACK".to_vec(),
+                        self.replication.offset().to_string().into_bytes(),
+                    ],
+                );
+                self.last_ack = Instant::now();
+                if let Err(err) = client.push(&data, registry) {
+                    let token = self.leader.expect("connected to the leader");
+                    self.drop_client(token, err);
+                }
+            }
+        }
+
+        for id in self.replication.take_dropped() {
+            let token = Token(id);
+            if let Some(client) = self.clients.get_mut(&token) {
+                // the replica may have already closed its end
+                let _ = client.close(self.poll.registry());
+                self.remove_client(token);
+            }
+        }
+
+        let registry = self.poll.registry();
+        let clients = &mut self.clients;
+        self.replication.send(|id, data| {
+            if let Some(client) = clients.get_mut(&Token(id)) {
+                if let Err(err) = client.push(data, registry) {
+                    warning!("Error sending the stream to a replica: {}", err);
+                }
+            }
+        });
+    }
+
+    fn connect_to_leader(&mut self, host: &str, port: u16) {
+        notice!("Connecting to MASTER {}:{}", host, port);
+
+        let addr = match (host, port).to_socket_addrs().map(|mut addrs| addrs.next()) {
+            Ok(Some(addr)) => addr,
+            _ => {
+                warning!("Unable to resolve MASTER {}:{}", host, port);
+                return;
+            }
+        };
+        let token = self.next_token();
+
+        // ask to continue the stream in case this server followed the leader before
+        let psync = (
+            self.replication.id().to_string(),
+            self.replication.offset() + 1,
+        );
+
+        match Handshake::connect(addr, token, self.config.port, psync, self.poll.registry()) {
+            Ok(handshake) => {
+                notice!("MASTER <-> REPLICA sync started");
+                self.handshake = Some(handshake);
+                self.replication.set_link(LinkState::Connecting);
+            }
+            Err(err) => warning!("Unable to connect to MASTER: {}", err),
+        }
+    }
+
+    /// Closes the connection to the leader, logging rather than failing
+    /// if the socket is already gone, since the server runs on without it.
+    fn disconnect_leader(&mut self) {
+        let registry = self.poll.registry();
+
+        if let Some(handshake) = self.handshake.take() {
+            if let Err(err) = handshake.close(registry) {
+                warning!("Error closing the connection to MASTER: {}", err);
+            }
+        }
+        if let Some(token) = self.leader.take() {
+            if let Some(client) = self.clients.get_mut(&token) {
+                self.replication.set_leader_db(client.session().db());
+                if let Err(err) = client.close(registry) {
+                    warning!("Error closing the connection to MASTER: {}", err);
+                }
+            }
+            self.remove_client(token);
+        }
+        self.replication.set_link(LinkState::Connect);
+    }
+
+    /// Moves the handshake with the leader along,
+    /// starting over after the retry delay if it fails.
+    fn handle_handshake(&mut self, event: &Event) {
+        let registry = self.poll.registry();
+        let handshake = self.handshake.as_mut().expect("handshake in progress");
+
+        match handshake.handle(event, registry) {
+            Ok(None) => {
+                if handshake.is_connected() {
+                    self.replication.set_link(LinkState::Sync);
+                }
+            }
+            Ok(Some(resync)) => {
+                let handshake = self.handshake.take().expect("handshake in progress");
+                let result = handshake
+                    .into_stream(registry)
+                    .and_then(|(token, stream)| self.finish_sync(token, stream, resync));
+
+                if let Err(err) = result {
+                    warning!("Error condition on socket for SYNC: {}", err);
+                    self.replication.set_link(LinkState::Connect);
+                }
+            }
+            Err(err) => {
+                warning!("Error condition on socket for SYNC: {}", err);
+                if let Some(handshake) = self.handshake.take() {
+                    // the leader may have already closed its end
+                    let _ = handshake.close(registry);
+                }
+                self.replication.set_link(LinkState::Connect);
+            }
+        }
+    }
+
+    /// Replaces the databases with the leader's snapshot after a full resync,
+    /// and starts applying the stream of commands that follows the reply to PSYNC.
+    fn finish_sync(&mut self, token: Token, stream: TcpStream, resync: Resync) -> io::Result<()> {
+        let (received, db) = match resync {
+            Resync::Full {
+                id,
+                offset,
+                snapshot,
+                stream: received,
+            } => {
+                if !self.load_leader_snapshot(&snapshot) {
+                    let _ = stream.shutdown(std::net::Shutdown::Both);
+                    self.replication.set_link(LinkState::Connect);
+                    return Ok(());
+                }
+                self.replication.synced(id, offset);
+                (received, 0)
+            }
+            Resync::Partial {
+                id,
+                stream: received,
+            } => {
+                self.replication.continued(id);
+                (received, self.replication.leader_db())
+            }
+        };
+
+        let registry = self.poll.registry();
+        let mut client = Client::from_leader(token, stream, received, db);
+        client.start(registry)?;
+        self.leader = Some(token);
+
+        self.clients.insert(token, client);
+
+        // losing the link from here on reconnects to continue the stream
+        if let Err(err) = self.run_commands(token) {
+            self.drop_client(token, err);
+        }
+        Ok(())
+    }
+
+    /// Replaces the databases with the snapshot, returning `false` if it's invalid.
+    fn load_leader_snapshot(&mut self, snapshot: &[u8]) -> bool {
+        notice!(
+            "MASTER <-> REPLICA sync: Loading DB in memory ({} bytes)",
+            snapshot.len()
+        );
+        for store in self.databases.iter_mut() {
+            store.flush();
+        }
+        if let Err(err) = decode_snapshot(snapshot, &mut self.databases) {
+            warning!(
+                "Failed trying to load the MASTER synchronization DB: {}",
+                err
+            );
+            for store in self.databases.iter_mut() {
+                store.flush();
+            }
+            return false;
         }
+        notice!("MASTER <-> REPLICA sync: Finished with success");
+
+        if self.aof.is_logging() && !self.aof.in_progress() {
+            // the file holds the data that was just replaced
+            self.aof.start_rewrite(
+                &PathBuf::from(&self.config.append_filename),
+                &self.databases,
+            );
+        }
+        true
     }
 
     fn next_token(&mut self) -> Token {
@@ -95,30 +764,18 @@ impl Server {
 
         if let Some(client) = self.clients.get_mut(&token) {
             if event.is_writable() {
-                println!("writable");
-                match client.send(registry) {
-                    Ok(true) => return Ok(true),
-                    Ok(false) => (),
-                    Err(err) => return Err(err),
-                };
+                debug!("writable");
+                if client.send(registry)? {
+                    return Ok(true);
+                }
             }
 
             if event.is_readable() {
-                println!("readable");
-                match client.receive(registry) {
-                    Ok(true) => return Ok(true),
-                    Ok(false) => {
-                        self.store.expire_items();
-                        match client.run_commands(&self.parser, &mut self.store, registry) {
-                            Ok(()) => return Ok(false),
-                            Err(err) => {
-                                println!("error: {}", err);
-                                return Ok(false);
-                            }
-                        }
-                    }
-                    Err(err) => return Err(err),
-                };
+                debug!("readable");
+                if client.receive(registry)? {
+                    return Ok(true);
+                }
+                self.run_commands(token)?;
             }
 
             Ok(false)
@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+
+use super::Transaction;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Protocol {
+    Resp2,
+    Resp3,
+}
+
+impl Protocol {
+    pub fn try_from_version(version: i64) -> Option<Self> {
+        match version {
+            2 => Some(Protocol::Resp2),
+            3 => Some(Protocol::Resp3),
+            _ => None,
+        }
+    }
+
+    pub fn version(&self) -> i64 {
+        match self {
+            Protocol::Resp2 => 2,
+            Protocol::Resp3 => 3,
+        }
+    }
+}
+
+/// Peer is the role of the other end of a connection in replication.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Peer {
+    Client,
+    /// sends the stream of commands to this replica
+    Leader,
+    /// receives the stream of commands from this leader
+    Replica,
+}
+
+/// Session holds the state of a single connection
+/// that commands may inspect and change.
+pub struct Session {
+    id: usize,
+    protocol: Protocol,
+    name: Option<String>,
+    db: usize,
+    ip: Option<String>,
+    peer: Peer,
+    /// the port a replica accepts clients on as sent by REPLCONF
+    listening_port: Option<u16>,
+    /// the offset of the replication stream after the last command
+    repl_offset: u64,
+    channels: HashSet<Vec<u8>>,
+    patterns: HashSet<Vec<u8>>,
+    shard_channels: HashSet<Vec<u8>>,
+    /// the commands queued since MULTI
+    transaction: Option<Transaction>,
+    /// the database, key and version of each key watched for EXEC
+    watched: Vec<(usize, Vec<u8>, u64)>,
+}
+
+impl Session {
+    pub fn new(id: usize) -> Self {
+        Self {
+            id,
+            protocol: Protocol::Resp2,
+            name: None,
+            db: 0,
+            ip: None,
+            peer: Peer::Client,
+            listening_port: None,
+            repl_offset: 0,
+            channels: HashSet::new(),
+            patterns: HashSet::new(),
+            shard_channels: HashSet::new(),
+            transaction: None,
+            watched: Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    pub fn set_protocol(&mut self, protocol: Protocol) {
+        self.protocol = protocol;
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    /// Returns the index of the selected database.
+    pub fn db(&self) -> usize {
+        self.db
+    }
+
+    pub fn set_db(&mut self, db: usize) {
+        self.db = db;
+    }
+
+    /// Returns the address of the other end of the connection.
+    pub fn ip(&self) -> Option<&str> {
+        self.ip.as_deref()
+    }
+
+    pub fn set_ip(&mut self, ip: Option<String>) {
+        self.ip = ip;
+    }
+
+    pub fn peer(&self) -> Peer {
+        self.peer
+    }
+
+    pub fn set_peer(&mut self, peer: Peer) {
+        self.peer = peer;
+    }
+
+    pub fn listening_port(&self) -> Option<u16> {
+        self.listening_port
+    }
+
+    pub fn set_listening_port(&mut self, port: u16) {
+        self.listening_port = Some(port);
+    }
+
+    pub fn repl_offset(&self) -> u64 {
+        self.repl_offset
+    }
+
+    pub fn set_repl_offset(&mut self, offset: u64) {
+        self.repl_offset = offset;
+    }
+
+    /// Returns `true` if the connection is subscribed to any channel, pattern or shard channel,
+    /// which limits a RESP2 connection to the subscriber commands.
+    pub fn is_subscribed(&self) -> bool {
+        self.subscriptions() > 0 || !self.shard_channels.is_empty()
+    }
+
+    /// Returns the number of channels and patterns the connection is subscribed to.
+    pub fn subscriptions(&self) -> usize {
+        self.channels.len() + self.patterns.len()
+    }
+
+    pub fn channels(&self) -> &HashSet<Vec<u8>> {
+        &self.channels
+    }
+
+    /// Returns `true` if the connection wasn't already subscribed to the channel.
+    pub fn add_channel(&mut self, channel: &[u8]) -> bool {
+        self.channels.insert(channel.to_vec())
+    }
+
+    /// Returns `true` if the connection was subscribed to the channel.
+    pub fn remove_channel(&mut self, channel: &[u8]) -> bool {
+        self.channels.remove(channel)
+    }
+
+    pub fn patterns(&self) -> &HashSet<Vec<u8>> {
+        &self.patterns
+    }
+
+    /// Returns `true` if the connection wasn't already subscribed to the pattern.
+    pub fn add_pattern(&mut self, pattern: &[u8]) -> bool {
+        self.patterns.insert(pattern.to_vec())
+    }
+
+    /// Returns `true` if the connection was subscribed to the pattern.
+    pub fn remove_pattern(&mut self, pattern: &[u8]) -> bool {
+        self.patterns.remove(pattern)
+    }
+
+    pub fn shard_channels(&self) -> &HashSet<Vec<u8>> {
+        &self.shard_channels
+    }
+
+    /// Returns `true` if the connection wasn't already subscribed to the shard channel.
+    pub fn add_shard_channel(&mut self, channel: &[u8]) -> bool {
+        self.shard_channels.insert(channel.to_vec())
+    }
+
+    /// Returns `true` if the connection was subscribed to the shard channel.
+    pub fn remove_shard_channel(&mut self, channel: &[u8]) -> bool {
+        self.shard_channels.remove(channel)
+    }
+
+    pub fn in_multi(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    /// Starts queueing commands instead of running them.
+    pub fn start_multi(&mut self) {
+        self.transaction = Some(Transaction::new());
+    }
+
+    pub fn transaction_mut(&mut self) -> Option<&mut Transaction> {
+        self.transaction.as_mut()
+    }
+
+    /// Ends the transaction, returning its queued commands.
+    pub fn take_transaction(&mut self) -> Option<Transaction> {
+        self.transaction.take()
+    }
+
+    pub fn watched(&self) -> &[(usize, Vec<u8>, u64)] {
+        &self.watched
+    }
+
+    /// Returns `false` if the key was already watched.
+    pub fn watch(&mut self, db: usize, key: &[u8], version: u64) -> bool {
+        if self
+            .watched
+            .iter()
+            .any(|(watched_db, watched, _)| *watched_db == db && watched == key)
+        {
+            return false;
+        }
+        self.watched.push((db, key.to_vec(), version));
+        true
+    }
+
+    pub fn take_watched(&mut self) -> Vec<(usize, Vec<u8>, u64)> {
+        std::mem::take(&mut self.watched)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::parse::Apply;
+
+/// A queued command with the arguments to log and replicate if it writes.
+type Queued = (Box<dyn Apply>, Option<Vec<Vec<u8>>>);
+
+/// Transaction holds the commands a connection queued after MULTI until EXEC runs them.
+pub struct Transaction {
+    commands: Vec<Queued>,
+    /// a command failed to queue, so EXEC must discard the transaction
+    aborted: bool,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+            aborted: false,
+        }
+    }
+
+    pub fn queue(&mut self, command: Box<dyn Apply>, args: Option<Vec<Vec<u8>>>) {
+        self.commands.push((command, args));
+    }
+
+    pub fn abort(&mut self) {
+        self.aborted = true;
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.aborted
+    }
+
+    pub fn into_commands(self) -> Vec<Queued> {
+        self.commands
+    }
+}
@@ -1,9 +1,11 @@
-pub use expiration::Expiration;
+pub use expiration::{expire_at, Expiration};
+pub use inline::split_args;
 pub use input::Input;
 pub use options::{Options, parse_options};
-pub use parser::{Apply, Parser, TryParse};
+pub use parser::{Apply, ApplyToStore, parse_f64, parse_i64, Parser, TryParse};
 
 mod expiration;
+mod inline;
 mod input;
 mod options;
 mod parser;
@@ -1,6 +1,4 @@
-use std::time::Duration;
-
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 
 use crate::network::Error;
 
@@ -14,8 +12,10 @@ pub enum Expiration {
 
 impl Expiration {
     pub fn try_parse(token: &str, input: &mut Input) -> Result<Self, Error> {
-        if token == "KEEPTTL" {
-            return Ok(Expiration::Keep);
+        match token {
+            "KEEPTTL" => return Ok(Expiration::Keep),
+            "PERSIST" => return Ok(Expiration::Never),
+            _ => (),
         }
 
         let time = input.next_i64()?;
@@ -23,28 +23,20 @@ impl Expiration {
             return Err(Error::ExpireTime);
         }
 
-        let at = match token {
-            "EX" => Utc::now() + Duration::new(time as u64, 0),
-            "PX" => {
-                Utc::now()
-                    + if time >= 1_000 {
-                        Duration::new(time as u64 / 1_000, (time % 1_000) as u32 * 1_000_000)
-                    } else {
-                        Duration::new(0, time as u32 * 1_000_000)
-                    }
-            }
-            "EXAT" => match DateTime::from_timestamp_millis(time * 1_000) {
-                Some(at) => at,
-                _ => return Err(Error::ExpireTime),
-            },
-            "PXAT" => match DateTime::from_timestamp_millis(time) {
-                Some(at) => at,
-                _ => return Err(Error::ExpireTime),
-            },
-            _ => return Err(Error::ExpireTime),
-        };
-
-        println!("expires at {}", at.format("%Y-%m-%d %H:%M:%S"));
+        let at = expire_at(token, time).ok_or(Error::ExpireTime)?;
+        debug!("expires at {}", at.format("%Y-%m-%d %H:%M:%S"));
         Ok(Expiration::At(at))
     }
 }
+
+/// Returns when a time given in the unit of the EX, PX, EXAT or PXAT token expires,
+/// or `None` if that's beyond the dates that can be represented.
+pub fn expire_at(token: &str, time: i64) -> Option<DateTime<Utc>> {
+    match token {
+        "EX" => Utc::now().checked_add_signed(TimeDelta::try_seconds(time)?),
+        "PX" => Utc::now().checked_add_signed(TimeDelta::try_milliseconds(time)?),
+        "EXAT" => DateTime::from_timestamp(time, 0),
+        "PXAT" => DateTime::from_timestamp_millis(time),
+        _ => None,
+    }
+}
@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+
+use crate::network::Error;
+
+const UNBALANCED_QUOTES: Error =
+    Error::Raw(b"-ERR Protocol error: unbalanced quotes in request\r\n");
+
+/// Splits an inline command into arguments the same way redis-cli does.
+///
+/// Arguments are separated by whitespace and may be wrapped in double quotes,
+/// which support `\n`, `\r`, `\t`, `\b`, `\a` and `\xHH` escapes,
+/// or single quotes, which only support `\'`.
+/// Arguments without quotes are borrowed from the line.
+pub fn split_args(line: &[u8]) -> Result<Vec<Cow<'_, [u8]>>, Error> {
+    let mut args = Vec::new();
+    let mut index = 0;
+
+    loop {
+        while index < line.len() && line[index].is_ascii_whitespace() {
+            index += 1;
+        }
+        if index == line.len() {
+            return Ok(args);
+        }
+
+        let start = index;
+        while index < line.len()
+            && !line[index].is_ascii_whitespace()
+            && line[index] != b'"'
+            && line[index] != b'\''
+        {
+            index += 1;
+        }
+        if index == line.len() || line[index].is_ascii_whitespace() {
+            args.push(Cow::Borrowed(&line[start..index]));
+            continue;
+        }
+
+        let mut arg = line[start..index].to_vec();
+        index = split_quoted(line, index, &mut arg)?;
+        args.push(Cow::Owned(arg));
+    }
+}
+
+/// Appends the quoted part of an argument starting at `index`
+/// and returns the index following it.
+fn split_quoted(line: &[u8], mut index: usize, arg: &mut Vec<u8>) -> Result<usize, Error> {
+    let quote = line[index];
+    index += 1;
+
+    loop {
+        if index == line.len() {
+            return Err(UNBALANCED_QUOTES);
+        }
+
+        let byte = line[index];
+        if byte == quote {
+            index += 1;
+            if index < line.len() && !line[index].is_ascii_whitespace() {
+                return Err(UNBALANCED_QUOTES);
+            }
+            return Ok(index);
+        }
+
+        if byte == b'\\' && index + 1 < line.len() {
+            let next = line[index + 1];
+            if quote == b'\'' {
+                if next == b'\'' {
+                    arg.push(next);
+                    index += 2;
+                    continue;
+                }
+            } else if next == b'x'
+                && index + 3 < line.len()
+                && line[index + 2].is_ascii_hexdigit()
+                && line[index + 3].is_ascii_hexdigit()
+            {
+                arg.push(hex_value(line[index + 2]) << 4 | hex_value(line[index + 3]));
+                index += 4;
+                continue;
+            } else {
+                arg.push(match next {
+                    b'n' => b'\n',
+                    b'r' => b'\r',
+                    b't' => b'\t',
+                    b'b' => 0x08,
+                    b'a' => 0x07,
+                    other => other,
+                });
+                index += 2;
+                continue;
+            }
+        }
+
+        arg.push(byte);
+        index += 1;
+    }
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(line: &[u8]) -> Vec<Vec<u8>> {
+        split_args(line)
+            .unwrap()
+            .into_iter()
+            .map(|arg| arg.into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn splits_on_whitespace() {
+        assert_eq!(
+            split(b"  SET\tkey  value "),
+            vec![b"SET".to_vec(), b"key".to_vec(), b"value".to_vec()]
+        );
+        assert!(split(b" \t ").is_empty());
+    }
+
+    #[test]
+    fn borrows_arguments_without_quotes() {
+        let args = split_args(b"GET key").unwrap();
+        assert!(args.iter().all(|arg| matches!(arg, Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn unescapes_double_quotes() {
+        assert_eq!(
+            split(br#"SET "a b\n\r\t\b\a" "\x00\xfF\x4a" "say \"hi\"\\""#),
+            vec![
+                b"SET".to_vec(),
+                b"a b\n\r\t\x08\x07".to_vec(),
+                b"\x00\xff\x4a".to_vec(),
+                br#"say "hi"\"#.to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_invalid_hex_escapes() {
+        assert_eq!(split(br#""\xZZ""#), vec![b"xZZ".to_vec()]);
+    }
+
+    #[test]
+    fn only_unescapes_quotes_in_single_quotes() {
+        assert_eq!(
+            split(br"ECHO 'it\'s \n \x41'"),
+            vec![b"ECHO".to_vec(), br"it's \n \x41".to_vec()]
+        );
+    }
+
+    #[test]
+    fn joins_a_prefix_to_quotes() {
+        assert_eq!(split(br#"key"s p""#), vec![b"keys p".to_vec()]);
+        assert_eq!(split(b"''"), vec![b"".to_vec()]);
+    }
+
+    #[test]
+    fn rejects_unbalanced_quotes() {
+        for line in [
+            &br#"SET "key"#[..],
+            br"SET 'key",
+            br#"SET "key"value"#,
+            br"SET 'key'value",
+            br#"SET "key\"#,
+        ] {
+            assert_eq!(split_args(line), Err(UNBALANCED_QUOTES));
+        }
+    }
+}
@@ -1,14 +1,17 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
 use crate::network::Error;
 
-use super::parser::{parse_i64, parse_u64};
+use super::parser::{parse_f64, parse_i64, parse_u64};
 
 pub struct Input<'a> {
-    tokens: Vec<&'a str>,
+    tokens: Vec<Cow<'a, [u8]>>,
     index: usize,
 }
 
 impl<'a> Input<'a> {
-    pub fn new(tokens: Vec<&'a str>) -> Self {
+    pub fn new(tokens: Vec<Cow<'a, [u8]>>) -> Self {
         Self { tokens, index: 0 }
     }
 
@@ -20,54 +23,62 @@ impl<'a> Input<'a> {
         self.index < self.tokens.len()
     }
 
-    pub fn next(&mut self) -> Result<&'a str, Error> {
+    pub fn next(&mut self) -> Result<&[u8], Error> {
         if !self.has_next() {
             Err(Error::Syntax)
         } else {
             self.index += 1;
-            Ok(self.tokens[self.index - 1])
+            Ok(&self.tokens[self.index - 1])
         }
     }
 
+    pub fn next_bytes(&mut self) -> Result<Vec<u8>, Error> {
+        Ok(self.next()?.to_vec())
+    }
+
     pub fn next_string(&mut self) -> Result<String, Error> {
-        Ok(self.next()?.to_string())
+        Ok(String::from_utf8_lossy(self.next()?).into_owned())
     }
 
-    pub fn next_strings(
+    pub fn next_multiple(
         &mut self,
         command: &str,
         arg: &str,
         count: usize,
-    ) -> Result<Vec<String>, Error> {
+    ) -> Result<Vec<Vec<u8>>, Error> {
         if self.len() < count {
             return Err(Error::MissingArgument(command.to_string(), arg.to_string()));
         }
-        let strings = self.tokens[self.index..self.index + count]
+        let values = self.tokens[self.index..self.index + count]
             .iter()
-            .map(|&s| s.to_string())
+            .map(|s| s.to_vec())
             .collect();
         self.index += count;
-        Ok(strings)
+        Ok(values)
     }
 
     pub fn next_token(&mut self) -> Result<String, Error> {
-        Ok(self.next()?.to_uppercase())
+        Ok(String::from_utf8_lossy(self.next()?).to_uppercase())
     }
 
     pub fn next_i64(&mut self) -> Result<i64, Error> {
-        parse_i64(self.next()?.as_bytes())
+        parse_i64(self.next()?)
+    }
+
+    pub fn next_f64(&mut self) -> Result<f64, Error> {
+        parse_f64(self.next()?)
     }
 
     pub fn next_u64(&mut self) -> Result<u64, Error> {
-        parse_u64(self.next()?.as_bytes())
+        parse_u64(self.next()?)
     }
 
     pub fn next_usize(&mut self) -> Result<usize, Error> {
-        parse_u64(self.next()?.as_bytes()).map(|n| n as usize)
+        parse_u64(self.next()?).map(|n| n as usize)
     }
 
     pub fn next_u64_min(&mut self, min: u64) -> Result<u64, Error> {
-        let value = parse_u64(self.next()?.as_bytes())?;
+        let value = parse_u64(self.next()?)?;
         if value >= min {
             Ok(value)
         } else {
@@ -75,10 +86,48 @@ impl<'a> Input<'a> {
         }
     }
 
-    pub fn rest(&mut self) -> Result<Vec<String>, Error> {
+    /// Returns the next token in uppercase without reading it.
+    pub fn peek_token(&self) -> Option<String> {
+        self.tokens
+            .get(self.index)
+            .map(|token| String::from_utf8_lossy(token).to_uppercase())
+    }
+
+    /// Reads a timeout in seconds that may have a fraction, where zero waits forever.
+    pub fn next_timeout(&mut self) -> Result<Option<Duration>, Error> {
+        let secs = std::str::from_utf8(self.next()?)
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .filter(|secs| secs.is_finite())
+            .ok_or(Error::Raw(
+                b"-ERR timeout is not a float or out of range\r\n",
+            ))?;
+
+        if secs < 0.0 {
+            Err(Error::Raw(b"-ERR timeout is negative\r\n"))
+        } else if secs == 0.0 {
+            Ok(None)
+        } else {
+            Duration::try_from_secs_f64(secs)
+                .map(Some)
+                .map_err(|_| Error::Raw(b"-ERR timeout is out of range\r\n"))
+        }
+    }
+
+    /// Returns the name of the command in lowercase.
+    pub fn command(&self) -> String {
+        String::from_utf8_lossy(&self.tokens[0]).to_lowercase()
+    }
+
+    /// Returns a copy of every token, including those already read.
+    pub fn to_vec(&self) -> Vec<Vec<u8>> {
+        self.tokens.iter().map(|s| s.to_vec()).collect()
+    }
+
+    pub fn rest(&mut self) -> Result<Vec<Vec<u8>>, Error> {
         let rest = self.tokens[self.index..]
             .iter()
-            .map(|&s| s.to_string())
+            .map(|s| s.to_vec())
             .collect();
         self.index = self.tokens.len();
         Ok(rest)
@@ -16,9 +16,9 @@ pub fn parse_options<T>(
     'outer: while input.has_next() {
         let token = input.next_token().unwrap();
 
-        println!("token: {}", token);
+        debug!("token: {}", token);
         for (ref tokens, ref op) in options {
-            println!("tokens: {:?}", tokens);
+            debug!("tokens: {:?}", tokens);
             if tokens.contains(&token.as_str()) {
                 if used_ops.contains(&op) {
                     return Err(Error::DuplicateOption(command.to_string(), token));
@@ -1,11 +1,15 @@
-use std::str::from_utf8;
+use std::borrow::Cow;
 
 use crate::commands::CommandTree;
-use crate::network::{Error, Response};
+use crate::network::{Context, Error, Response};
 use crate::storage::Store;
 
+use super::inline::split_args;
 use super::Input;
 
+/// The longest partial line kept while waiting for the end of an inline command.
+const INLINE_MAX_SIZE: usize = 64 * 1024;
+
 pub struct Parser {
     commands: CommandTree,
 }
@@ -17,17 +21,32 @@ impl Parser {
         }
     }
 
+    /// Parses the next command, skipping the blank lines clients like telnet send between them,
+    /// and returns it with the length of the buffer it takes up.
     pub fn try_next_input<'a>(
         &self,
         buffer: &'a [u8],
     ) -> Result<Option<(Input<'a>, usize)>, Error> {
-        if buffer.is_empty() {
-            return Ok(None);
-        }
-        if buffer[0] != b'*' {
-            return Err(Error::Protocol);
+        let skipped = blank_lines(buffer);
+        let rest = &buffer[skipped..];
+        let next = match rest.first() {
+            None => None,
+            Some(b'*') => self.try_next_multibulk_input(rest)?,
+            Some(_) => self.try_next_inline_input(rest)?,
+        };
+
+        if next.is_none() && rest.first() != Some(&b'*') && buffer.len() > INLINE_MAX_SIZE {
+            return Err(Error::Raw(
+                b"-ERR Protocol error: too big inline request\r\n",
+            ));
         }
+        Ok(next.map(|(input, len)| (input, skipped + len)))
+    }
 
+    fn try_next_multibulk_input<'a>(
+        &self,
+        buffer: &'a [u8],
+    ) -> Result<Option<(Input<'a>, usize)>, Error> {
         if let Some(end) = find_cr_lf(buffer) {
             let len = parse_i64(&buffer[1..end])?;
             if len < 1 {
@@ -40,7 +59,7 @@ impl Parser {
             for _ in 0..len {
                 if let Some((token, end)) = self.try_next_token(&buffer[index..])? {
                     index += end;
-                    tokens.push(token);
+                    tokens.push(Cow::Borrowed(token));
                 } else {
                     return Ok(None);
                 }
@@ -52,7 +71,23 @@ impl Parser {
         }
     }
 
-    fn try_next_token<'a>(&self, buffer: &'a [u8]) -> Result<Option<(&'a str, usize)>, Error> {
+    /// Parses a command sent as a single line of text
+    /// for clients like telnet that don't speak RESP.
+    fn try_next_inline_input<'a>(
+        &self,
+        buffer: &'a [u8],
+    ) -> Result<Option<(Input<'a>, usize)>, Error> {
+        match find_lf(buffer) {
+            Some(end) => {
+                let line = &buffer[..end];
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                Ok(Some((Input::new(split_args(line)?), end + 1)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn try_next_token<'a>(&self, buffer: &'a [u8]) -> Result<Option<(&'a [u8], usize)>, Error> {
         if buffer.is_empty() {
             return Ok(None);
         }
@@ -66,12 +101,12 @@ impl Parser {
                 return Err(Error::Protocol);
             }
             let start = end + 2;
-            if len as usize > buffer.len() - (start + 2) {
+            if start + len as usize + 2 > buffer.len() {
                 return Ok(None);
             }
 
             Ok(Some((
-                from_utf8(&buffer[start..start + len as usize]).map_err(|_| Error::Protocol)?,
+                &buffer[start..start + len as usize],
                 start + len as usize + 2,
             )))
         } else {
@@ -79,15 +114,15 @@ impl Parser {
         }
     }
 
-    pub fn try_parse_command(&self, mut input: Input) -> Result<Box<dyn Apply>, Error> {
+    pub fn try_parse_command(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error> {
         let command = input.next()?;
-        println!("command: {}", command);
+        debug!("command: {}", String::from_utf8_lossy(command));
         let parser = self
             .commands
             .get(command)
-            .ok_or(Error::UnknownCommand(command.to_string()))?;
+            .ok_or_else(|| Error::UnknownCommand(String::from_utf8_lossy(command).into_owned()))?;
 
-        parser.try_parse(&mut input).and_then(|parsed| {
+        parser.try_parse(input).and_then(|parsed| {
             if input.has_next() {
                 Err(Error::Syntax)
             } else {
@@ -107,43 +142,192 @@ fn find_cr_lf(buffer: &[u8]) -> Option<usize> {
     None
 }
 
-pub fn parse_i64(buffer: &[u8]) -> Result<i64, Error> {
-    let mut result: i64 = 0;
-    let mut negative = false;
+fn find_lf(buffer: &[u8]) -> Option<usize> {
+    buffer.iter().position(|&byte| byte == b'\n')
+}
 
-    for (i, &byte) in buffer.iter().enumerate() {
-        if i == 0 && byte == b'-' {
-            negative = true;
-            continue;
+/// Returns the length of the complete lines holding only whitespace at the start of the buffer.
+fn blank_lines(buffer: &[u8]) -> usize {
+    let mut index = 0;
+    while let Some(end) = find_lf(&buffer[index..]) {
+        if !buffer[index..index + end]
+            .iter()
+            .all(u8::is_ascii_whitespace)
+        {
+            break;
         }
-        if byte.is_ascii_digit() {
-            result = result * 10 + (byte - b'0') as i64;
-        } else {
+        index += end + 1;
+    }
+    index
+}
+
+pub fn parse_i64(buffer: &[u8]) -> Result<i64, Error> {
+    let (negative, digits) = match buffer {
+        [b'-', digits @ ..] => (true, digits),
+        digits => (false, digits),
+    };
+    if digits.is_empty() {
+        return Err(Error::Integer);
+    }
+
+    // accumulate toward the sign so the most negative integer doesn't overflow
+    let mut result: i64 = 0;
+    for &byte in digits {
+        if !byte.is_ascii_digit() {
             return Err(Error::Integer);
         }
+        let digit = (byte - b'0') as i64;
+        result = result
+            .checked_mul(10)
+            .and_then(|result| {
+                if negative {
+                    result.checked_sub(digit)
+                } else {
+                    result.checked_add(digit)
+                }
+            })
+            .ok_or(Error::Integer)?;
     }
 
-    Ok(if negative { -result } else { result })
+    Ok(result)
 }
 
 pub fn parse_u64(buffer: &[u8]) -> Result<u64, Error> {
-    let mut result: u64 = 0;
+    if buffer.is_empty() {
+        return Err(Error::Integer);
+    }
 
-    for byte in buffer.iter() {
-        if byte.is_ascii_digit() {
-            result = result * 10 + (byte - b'0') as u64;
-        } else {
+    let mut result: u64 = 0;
+    for &byte in buffer {
+        if !byte.is_ascii_digit() {
             return Err(Error::Integer);
         }
+        result = result
+            .checked_mul(10)
+            .and_then(|result| result.checked_add((byte - b'0') as u64))
+            .ok_or(Error::Integer)?;
     }
 
     Ok(result)
 }
 
+/// Parses a float that may be `inf` or `-inf` but not NaN.
+pub fn parse_f64(buffer: &[u8]) -> Result<f64, Error> {
+    std::str::from_utf8(buffer)
+        .ok()
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|value| !value.is_nan())
+        .ok_or(Error::Raw(b"-ERR value is not a valid float\r\n"))
+}
+
+/// Commands that only touch the store implement `ApplyToStore::apply`,
+/// while those that need the connection or server implement `execute`.
+///
+/// Commands that change the store return `true` from `is_write`
+/// so they are appended to the AOF and sent to replicas,
+/// and those that can't be replayed as written call `Context::propagate` with commands that can.
+///
+/// Commands that must wait return `Response::Block` from `execute`, which blocks the client
+/// and calls `retry` each time the server loop runs until it responds with something else,
+/// or `timeout` once the timeout passes.
+/// Those waiting for keys return them from `blocking_keys` to be retried only after one changes.
+///
+/// Commands that a RESP2 connection may send while subscribed to channels
+/// return `true` from `is_subscriber`,
+/// and those that control a transaction return `true` from `is_transaction` so MULTI doesn't queue them.
 pub trait Apply {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error>;
+
+    fn retry<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        self.execute(context)
+    }
+
+    fn timeout<'a>(&self, _context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        Ok(Response::NullArray)
+    }
+
+    fn is_write(&self) -> bool {
+        false
+    }
+
+    fn blocking_keys(&self) -> &[Vec<u8>] {
+        &[]
+    }
+
+    fn is_subscriber(&self) -> bool {
+        false
+    }
+
+    fn is_transaction(&self) -> bool {
+        false
+    }
+}
+
+/// A command that only needs the selected database, which gets `Apply` for free.
+pub trait ApplyToStore {
     fn apply<'a>(&self, store: &'a mut Store) -> Result<Response<'a>, Error>;
+
+    fn is_write(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ApplyToStore> Apply for T {
+    fn execute<'a>(&self, context: Context<'a, '_>) -> Result<Response<'a>, Error> {
+        self.apply(context.store())
+    }
+
+    fn is_write(&self) -> bool {
+        ApplyToStore::is_write(self)
+    }
 }
 
 pub trait TryParse {
     fn try_parse(&self, input: &mut Input) -> Result<Box<dyn Apply>, Error>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next_input(buffer: &[u8]) -> Option<(Vec<Vec<u8>>, usize)> {
+        Parser::new()
+            .try_next_input(buffer)
+            .unwrap()
+            .map(|(input, len)| (input.to_vec(), len))
+    }
+
+    #[test]
+    fn skips_blank_lines_before_a_multibulk_command() {
+        let buffer = b"\r\n \n*1\r\n$4\r\nPING\r\n";
+        assert_eq!(
+            next_input(buffer),
+            Some((vec![b"PING".to_vec()], buffer.len()))
+        );
+    }
+
+    #[test]
+    fn waits_for_a_command_after_blank_lines() {
+        assert_eq!(next_input(b"\r\n\r\n"), None);
+        assert_eq!(next_input(b"\r\n*1\r\n$4\r\nPI"), None);
+    }
+
+    #[test]
+    fn reads_one_inline_command_at_a_time() {
+        assert_eq!(
+            next_input(b"\r\nECHO 'a b'\r\nPING\r\n"),
+            Some((vec![b"ECHO".to_vec(), b"a b".to_vec()], 14))
+        );
+    }
+
+    #[test]
+    fn rejects_an_inline_command_too_big_to_wait_for() {
+        assert_eq!(next_input(&[b'x'; INLINE_MAX_SIZE]), None);
+        assert!(matches!(
+            Parser::new().try_next_input(&[b'x'; INLINE_MAX_SIZE + 1]),
+            Err(Error::Raw(
+                b"-ERR Protocol error: too big inline request\r\n"
+            ))
+        ));
+    }
+}
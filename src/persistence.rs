@@ -0,0 +1,7 @@
+pub use aof::{Aof, encode_command, Fsync};
+pub use rdb::{decode as decode_snapshot, encode as encode_snapshot};
+pub use snapshots::Snapshots;
+
+mod aof;
+mod rdb;
+mod snapshots;
@@ -0,0 +1,431 @@
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{BufWriter, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+
+use crate::config::Config;
+use crate::storage::{format_score, Store, Stream, StreamId, Value};
+
+/// The most items a rewritten RPUSH, SADD or HSET adds at once,
+/// which must be even to keep each field with its value.
+const ITEMS_PER_COMMAND: usize = 64;
+
+const FSYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Fsync controls how often the append-only file is flushed to disk.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fsync {
+    Always,
+    EverySec,
+    No,
+}
+
+impl Fsync {
+    pub fn try_parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "always" => Some(Fsync::Always),
+            "everysec" => Some(Fsync::EverySec),
+            "no" => Some(Fsync::No),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Fsync::Always => "always",
+            Fsync::EverySec => "everysec",
+            Fsync::No => "no",
+        }
+    }
+}
+
+/// Aof appends each write command to a file that is replayed on startup
+/// and compacted from the live keyspace by a background rewrite.
+pub struct Aof {
+    file: Option<File>,
+    /// encoded commands waiting for the next flush
+    buffer: Vec<u8>,
+    /// the database selected by the last command in the file
+    db: Option<usize>,
+    last_fsync: Instant,
+    fsync: Option<JoinHandle<io::Result<()>>>,
+    rewrite: Option<JoinHandle<io::Result<()>>>,
+    /// commands appended while the rewrite runs that the new file must include
+    rewrite_buffer: Vec<u8>,
+}
+
+impl Aof {
+    pub fn new() -> Self {
+        Self {
+            file: None,
+            buffer: Vec::new(),
+            db: None,
+            last_fsync: Instant::now(),
+            fsync: None,
+            rewrite: None,
+            rewrite_buffer: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if write commands must be passed to `append`,
+    /// either for the open file or a rewrite that will become it.
+    pub fn is_logging(&self) -> bool {
+        self.file.is_some() || self.rewrite.is_some()
+    }
+
+    pub fn in_progress(&self) -> bool {
+        self.rewrite.is_some()
+    }
+
+    /// Opens the file to append commands, creating it from the databases if it doesn't exist.
+    pub fn open(&mut self, path: &Path, databases: &[Store]) -> io::Result<()> {
+        if !path.exists() {
+            write_rewrite(&temp_path(path), databases)?;
+            fs::rename(temp_path(path), path)?;
+            notice!("Created append only file {}", path.display());
+        }
+
+        self.file = Some(OpenOptions::new().append(true).open(path)?);
+        self.db = None;
+        Ok(())
+    }
+
+    /// Encodes the commands executed against the database for the next flush.
+    pub fn append(&mut self, db: usize, commands: &[Vec<Vec<u8>>], fsync: Fsync) {
+        if !self.is_logging() || commands.is_empty() {
+            return;
+        }
+
+        let start = self.buffer.len();
+        if self.db != Some(db) {
+            encode_command(
+                &mut self.buffer,
+                &[b"SELECT".to_vec(), db.to_string().into_bytes()],
+            );
+            self.db = Some(db);
+        }
+        for command in commands {
+            encode_command(&mut self.buffer, command);
+        }
+        if self.rewrite.is_some() {
+            self.rewrite_buffer.extend_from_slice(&self.buffer[start..]);
+        }
+
+        if fsync == Fsync::Always {
+            self.flush(fsync);
+        }
+    }
+
+    /// Writes the buffered commands to the file and syncs it according to the policy.
+    pub fn flush(&mut self, fsync: Fsync) {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => {
+                self.buffer.clear();
+                return;
+            }
+        };
+
+        if !self.buffer.is_empty() {
+            if let Err(err) = file.write_all(&self.buffer) {
+                // keep the commands to try again on the next flush
+                warning!("Error writing to the AOF file: {}", err);
+                return;
+            }
+            self.buffer.clear();
+
+            if fsync == Fsync::Always {
+                if let Err(err) = file.sync_data() {
+                    warning!("Can't persist AOF for fsync error: {}", err);
+                }
+                self.last_fsync = Instant::now();
+            }
+        }
+
+        if fsync == Fsync::EverySec
+            && self.fsync.is_none()
+            && self.last_fsync.elapsed() >= FSYNC_INTERVAL
+        {
+            // sync from another thread so a slow disk doesn't block clients
+            if let Ok(file) = file.try_clone() {
+                self.fsync = Some(thread::spawn(move || file.sync_data()));
+                self.last_fsync = Instant::now();
+            }
+        }
+    }
+
+    /// Writes the commands that recreate a copy of the databases to a new file
+    /// from another thread while clients keep appending to the current one.
+    pub fn start_rewrite(&mut self, path: &Path, databases: &[Store]) {
+        let temp = temp_path(path);
+        let copy = databases.to_vec();
+
+        notice!("Background append only file rewriting started");
+        self.rewrite = Some(thread::spawn(move || write_rewrite(&temp, &copy)));
+        self.rewrite_buffer.clear();
+        // make the first command appended during the rewrite select its database
+        self.db = None;
+    }
+
+    /// Collects finished background work, finishes a rewrite by swapping in the new file,
+    /// and opens or closes the file when `appendonly` changes.
+    pub fn run(&mut self, config: &Config, databases: &[Store]) {
+        if let Some(handle) = self.fsync.take_if(|handle| handle.is_finished()) {
+            if let Ok(Err(err)) = handle.join() {
+                warning!("Can't persist AOF for fsync error: {}", err);
+            }
+        }
+
+        let path = PathBuf::from(&config.append_filename);
+        if let Some(handle) = self.rewrite.take_if(|handle| handle.is_finished()) {
+            let buffer = mem::take(&mut self.rewrite_buffer);
+            match handle.join() {
+                Ok(Ok(())) => match self.finish_rewrite(&path, &buffer, config.append_only) {
+                    Ok(()) => notice!("Background AOF rewrite finished successfully"),
+                    Err(err) => warning!("Error finishing the AOF rewrite: {}", err),
+                },
+                Ok(Err(err)) => warning!("Background AOF rewrite error: {}", err),
+                Err(_) => warning!("Background AOF rewrite terminated abnormally"),
+            }
+        }
+
+        if config.append_only && !self.is_logging() {
+            self.start_rewrite(&path, databases);
+        } else if !config.append_only && self.file.is_some() {
+            self.flush(config.append_fsync);
+            self.file = None;
+            notice!("Append only file closed");
+        }
+    }
+
+    /// Replaces the file with the rewritten one, appending to it from now on if `open`.
+    fn finish_rewrite(&mut self, path: &Path, buffer: &[u8], open: bool) -> io::Result<()> {
+        let temp = temp_path(path);
+        let mut file = OpenOptions::new().append(true).open(&temp)?;
+
+        file.write_all(buffer)?;
+        file.sync_data()?;
+        fs::rename(&temp, path)?;
+
+        // the buffer was copied to the new file
+        self.buffer.clear();
+        self.file = open.then_some(file);
+        Ok(())
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    path.with_file_name(format!("temp-rewriteaof-bg-{}.aof", process::id()))
+}
+
+/// Writes the fewest commands that recreate the keys and expirations in the databases.
+fn write_rewrite(path: &Path, databases: &[Store]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut buffer = Vec::new();
+    let now = Utc::now();
+
+    for (index, store) in databases.iter().enumerate() {
+        let mut selected = false;
+
+        for (key, value, expires) in store.entries() {
+            if expires.is_some_and(|at| *at <= now) {
+                continue;
+            }
+            if !selected {
+                encode_command(
+                    &mut buffer,
+                    &[b"SELECT".to_vec(), index.to_string().into_bytes()],
+                );
+                selected = true;
+            }
+
+            match value {
+                Value::Hash(fields) => encode_items(
+                    &mut buffer,
+                    b"HSET",
+                    key,
+                    fields.iter().flat_map(|(field, value)| [field, value]),
+                ),
+                Value::Integer(i) => encode_command(
+                    &mut buffer,
+                    &[b"SET".to_vec(), key.clone(), i.to_string().into_bytes()],
+                ),
+                Value::List(list) => encode_items(&mut buffer, b"RPUSH", key, list.iter()),
+                Value::Set(members) => encode_items(&mut buffer, b"SADD", key, members.iter()),
+                Value::SortedSet(members) => {
+                    let items = members
+                        .iter()
+                        .flat_map(|(member, score)| {
+                            [format_score(score).into_bytes(), member.clone()]
+                        })
+                        .collect::<Vec<_>>();
+                    encode_items(&mut buffer, b"ZADD", key, items.iter())
+                }
+                Value::Stream(stream) => encode_stream(&mut buffer, key, stream),
+                Value::String(s) => {
+                    encode_command(&mut buffer, &[b"SET".to_vec(), key.clone(), s.clone()])
+                }
+            }
+            if let Value::Hash(fields) = value {
+                for (field, at) in fields.expirations() {
+                    encode_command(
+                        &mut buffer,
+                        &[
+                            b"HPEXPIREAT".to_vec(),
+                            key.clone(),
+                            at.timestamp_millis().to_string().into_bytes(),
+                            b"FIELDS".to_vec(),
+                            b"1".to_vec(),
+                            field.clone(),
+                        ],
+                    );
+                }
+            }
+            if let Some(at) = expires {
+                encode_command(
+                    &mut buffer,
+                    &[
+                        b"PEXPIREAT".to_vec(),
+                        key.clone(),
+                        at.timestamp_millis().to_string().into_bytes(),
+                    ],
+                );
+            }
+
+            writer.write_all(&buffer)?;
+            buffer.clear();
+        }
+    }
+
+    writer.flush()?;
+    writer.get_ref().sync_all()
+}
+
+fn encode_items<'a>(
+    buffer: &mut Vec<u8>,
+    command: &[u8],
+    key: &[u8],
+    items: impl Iterator<Item = &'a Vec<u8>>,
+) {
+    let mut args = vec![command.to_vec(), key.to_vec()];
+
+    for item in items {
+        args.push(item.clone());
+        if args.len() == 2 + ITEMS_PER_COMMAND {
+            encode_command(buffer, &args);
+            args.truncate(2);
+        }
+    }
+    if args.len() > 2 {
+        encode_command(buffer, &args);
+    }
+}
+
+/// Encodes an XADD for each entry, or one trimmed away for an empty stream,
+/// and an XSETID to restore the IDs that new entries follow on from.
+fn encode_stream(buffer: &mut Vec<u8>, key: &[u8], stream: &Stream) {
+    if stream.is_empty() {
+        encode_command(
+            buffer,
+            &[
+                b"XADD".to_vec(),
+                key.to_vec(),
+                b"MAXLEN".to_vec(),
+                b"0".to_vec(),
+                // 0-0 can't be added, and XSETID puts the last ID back anyway
+                stream.last_id().max(StreamId::new(0, 1)).to_bytes(),
+                Vec::new(),
+                Vec::new(),
+            ],
+        );
+    }
+    for (id, fields) in stream.range(StreamId::MIN, StreamId::MAX) {
+        let mut args = vec![b"XADD".to_vec(), key.to_vec(), id.to_bytes()];
+        for (field, value) in fields {
+            args.push(field.clone());
+            args.push(value.clone());
+        }
+        encode_command(buffer, &args);
+    }
+    encode_command(
+        buffer,
+        &[
+            b"XSETID".to_vec(),
+            key.to_vec(),
+            stream.last_id().to_bytes(),
+            b"ENTRIESADDED".to_vec(),
+            stream.entries_added().to_string().into_bytes(),
+            b"MAXDELETEDID".to_vec(),
+            stream.max_deleted_id().to_bytes(),
+        ],
+    );
+
+    for (name, group) in stream.groups() {
+        let entries_read = group.entries_read.map_or(-1, |n| n as i64);
+        encode_command(
+            buffer,
+            &[
+                b"XGROUP".to_vec(),
+                b"CREATE".to_vec(),
+                key.to_vec(),
+                name.clone(),
+                group.last_id.to_bytes(),
+                b"ENTRIESREAD".to_vec(),
+                entries_read.to_string().into_bytes(),
+            ],
+        );
+        for consumer in group.consumers().keys() {
+            encode_command(
+                buffer,
+                &[
+                    b"XGROUP".to_vec(),
+                    b"CREATECONSUMER".to_vec(),
+                    key.to_vec(),
+                    name.clone(),
+                    consumer.clone(),
+                ],
+            );
+        }
+        for (id, entry) in group.pending() {
+            encode_command(
+                buffer,
+                &[
+                    b"XCLAIM".to_vec(),
+                    key.to_vec(),
+                    name.clone(),
+                    entry.consumer.clone(),
+                    b"0".to_vec(),
+                    id.to_bytes(),
+                    b"TIME".to_vec(),
+                    entry
+                        .delivered_at
+                        .timestamp_millis()
+                        .to_string()
+                        .into_bytes(),
+                    b"RETRYCOUNT".to_vec(),
+                    entry.delivery_count.to_string().into_bytes(),
+                    b"FORCE".to_vec(),
+                    b"JUSTID".to_vec(),
+                ],
+            );
+        }
+    }
+}
+
+/// Encodes the command as a RESP array of bulk strings.
+pub fn encode_command(buffer: &mut Vec<u8>, args: &[Vec<u8>]) {
+    buffer.extend_from_slice(format!("*{}\r\n", args.len()).as_bytes());
+    for arg in args {
+        buffer.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        buffer.extend_from_slice(arg);
+        buffer.extend_from_slice(b"\r\n");
+    }
+}
@@ -0,0 +1,723 @@
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::Path;
+use std::process;
+
+use chrono::{DateTime, Utc};
+
+use crate::storage::{ConsumerGroup, Hash, SortedSet, Store, Stream, StreamId, Value};
+
+const MAGIC: &[u8] = b"REDIS-RUST";
+// version 1 held only strings, integers, lists and sets
+const VERSION: u16 = 2;
+
+// opcodes
+const EXPIRE_MS: u8 = 0xFC;
+const SELECT_DB: u8 = 0xFE;
+const END: u8 = 0xFF;
+
+// value types
+const STRING: u8 = 0;
+const LIST: u8 = 1;
+const SET: u8 = 2;
+const INTEGER: u8 = 3;
+const HASH: u8 = 4;
+// a hash with field expirations, each written after its value or as -1 if it has none
+const HASH_TTL: u8 = 5;
+const SORTED_SET: u8 = 6;
+// the last ID, largest deleted ID and count of entries ever added, then the entries
+const STREAM: u8 = 7;
+// a stream followed by its consumer groups, each with its pending entries and consumers
+const STREAM_GROUPS: u8 = 8;
+
+/// Writes every key that hasn't expired to a temporary file
+/// and renames it to `path` so a failed save never replaces a good snapshot.
+///
+/// The file holds the magic and version, a section per non-empty database,
+/// and the end marker followed by a CRC-64 of everything before it.
+pub fn save(path: &Path, databases: &[Store]) -> io::Result<()> {
+    let temp = path.with_file_name(format!("temp-{}.rdb", process::id()));
+    let result = write_file(&temp, databases).and_then(|_| fs::rename(&temp, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+fn write_file(path: &Path, databases: &[Store]) -> io::Result<()> {
+    let file = write(BufWriter::new(File::create(path)?), databases)?;
+    file.get_ref().sync_all()
+}
+
+/// Returns the snapshot of the databases in memory, such as to send to a replica.
+pub fn encode(databases: &[Store]) -> Vec<u8> {
+    write(Vec::new(), databases).expect("writing to memory can't fail")
+}
+
+fn write<W: Write>(out: W, databases: &[Store]) -> io::Result<W> {
+    let mut writer = Writer::new(out);
+    let now = Utc::now();
+
+    writer.write(MAGIC)?;
+    writer.write(&VERSION.to_le_bytes())?;
+
+    for (index, store) in databases.iter().enumerate() {
+        let mut entries = store
+            .entries()
+            .filter(|(_, value, expires)| {
+                // a hash whose fields have all expired is as good as gone
+                expires.is_none_or(|at| *at > now)
+                    && !matches!(value, Value::Hash(hash) if hash.is_expired(&now))
+            })
+            .peekable();
+        if entries.peek().is_none() {
+            continue;
+        }
+
+        writer.write(&[SELECT_DB])?;
+        writer.write_length(index)?;
+        for (key, value, expires) in entries {
+            if let Some(at) = expires {
+                writer.write(&[EXPIRE_MS])?;
+                writer.write(&at.timestamp_millis().to_le_bytes())?;
+            }
+            writer.write_value(key, value)?;
+        }
+    }
+
+    writer.write(&[END])?;
+    writer.finish()
+}
+
+/// Loads the snapshot at `path` into the databases.
+/// Returns `false` if there's no snapshot to load.
+pub fn load(path: &Path, databases: &mut [Store]) -> io::Result<bool> {
+    match fs::read(path) {
+        Ok(data) => decode(&data, databases).map(|_| true),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Loads the snapshot into the databases, skipping keys that have expired.
+pub fn decode(data: &[u8], databases: &mut [Store]) -> io::Result<()> {
+    if data.len() < MAGIC.len() + 2 + 1 + 8 {
+        return Err(invalid("Short read loading DB"));
+    }
+
+    let (body, checksum) = data.split_at(data.len() - 8);
+    if crc64(0, body) != u64::from_le_bytes(checksum.try_into().unwrap()) {
+        return Err(invalid("Wrong RDB checksum"));
+    }
+
+    let mut reader = Reader::new(body);
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(invalid("Wrong signature trying to load DB from file"));
+    }
+    let version = u16::from_le_bytes(reader.take(2)?.try_into().unwrap());
+    if version > VERSION {
+        return Err(invalid(&format!(
+            "Can't handle RDB format version {}",
+            version
+        )));
+    }
+
+    let now = Utc::now();
+    let mut db = 0;
+    loop {
+        let mut opcode = reader.byte()?;
+        match opcode {
+            END => break,
+            SELECT_DB => {
+                db = reader.length()?;
+                if db >= databases.len() {
+                    return Err(invalid(&format!(
+                        "Data file was created with a server configured to handle more than {} databases",
+                        databases.len()
+                    )));
+                }
+                continue;
+            }
+            _ => (),
+        }
+
+        let expires = if opcode == EXPIRE_MS {
+            let at = DateTime::from_timestamp_millis(reader.i64()?)
+                .ok_or_else(|| invalid("Invalid expire time"))?;
+            opcode = reader.byte()?;
+            Some(at)
+        } else {
+            None
+        };
+
+        let key = reader.bytes()?;
+        let value = reader.value(opcode)?;
+        // a hash comes back empty if all its fields expired since the save
+        let emptied = matches!(&value, Value::Hash(hash) if hash.is_empty());
+        if expires.is_none_or(|at| at > now) && !emptied {
+            databases[db].set_entry(&key, value, expires);
+        }
+    }
+
+    if !reader.is_empty() {
+        return Err(invalid("Unexpected data after the end of the DB"));
+    }
+    Ok(())
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+/// Writer computes the checksum as it goes.
+struct Writer<W: Write> {
+    out: W,
+    crc: u64,
+}
+
+impl<W: Write> Writer<W> {
+    fn new(out: W) -> Self {
+        Self { out, crc: 0 }
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.crc = crc64(self.crc, data);
+        self.out.write_all(data)
+    }
+
+    /// Writes the length as an unsigned LEB128 integer.
+    fn write_length(&mut self, mut len: usize) -> io::Result<()> {
+        let mut buffer = [0; 10];
+        let mut size = 0;
+
+        loop {
+            let byte = (len & 0x7F) as u8;
+            len >>= 7;
+            if len == 0 {
+                buffer[size] = byte;
+                size += 1;
+                break;
+            }
+            buffer[size] = byte | 0x80;
+            size += 1;
+        }
+
+        self.write(&buffer[..size])
+    }
+
+    fn write_bytes(&mut self, data: &[u8]) -> io::Result<()> {
+        self.write_length(data.len())?;
+        self.write(data)
+    }
+
+    fn write_value(&mut self, key: &[u8], value: &Value) -> io::Result<()> {
+        match value {
+            Value::Hash(fields) if fields.next_expiration().is_some() => {
+                self.write(&[HASH_TTL])?;
+                self.write_bytes(key)?;
+                self.write_length(fields.len())?;
+                fields.iter().try_for_each(|(field, value)| {
+                    self.write_bytes(field)?;
+                    self.write_bytes(value)?;
+                    let at = fields.expires(field).map_or(-1, |at| at.timestamp_millis());
+                    self.write(&at.to_le_bytes())
+                })
+            }
+            Value::Hash(fields) => {
+                self.write(&[HASH])?;
+                self.write_bytes(key)?;
+                self.write_length(fields.len())?;
+                fields.iter().try_for_each(|(field, value)| {
+                    self.write_bytes(field)?;
+                    self.write_bytes(value)
+                })
+            }
+            Value::Integer(i) => {
+                self.write(&[INTEGER])?;
+                self.write_bytes(key)?;
+                self.write(&i.to_le_bytes())
+            }
+            Value::List(list) => {
+                self.write(&[LIST])?;
+                self.write_bytes(key)?;
+                self.write_length(list.len())?;
+                list.iter().try_for_each(|item| self.write_bytes(item))
+            }
+            Value::Set(members) => {
+                self.write(&[SET])?;
+                self.write_bytes(key)?;
+                self.write_length(members.len())?;
+                members
+                    .iter()
+                    .try_for_each(|member| self.write_bytes(member))
+            }
+            Value::SortedSet(members) => {
+                self.write(&[SORTED_SET])?;
+                self.write_bytes(key)?;
+                self.write_length(members.len())?;
+                members.iter().try_for_each(|(member, score)| {
+                    self.write_bytes(member)?;
+                    self.write(&score.to_le_bytes())
+                })
+            }
+            Value::Stream(stream) => {
+                let has_groups = stream.groups().next().is_some();
+                self.write(&[if has_groups { STREAM_GROUPS } else { STREAM }])?;
+                self.write_bytes(key)?;
+                self.write_id(&stream.last_id())?;
+                self.write_id(&stream.max_deleted_id())?;
+                self.write(&stream.entries_added().to_le_bytes())?;
+                self.write_length(stream.len())?;
+                stream
+                    .range(StreamId::MIN, StreamId::MAX)
+                    .try_for_each(|(id, fields)| {
+                        self.write_id(id)?;
+                        self.write_length(fields.len())?;
+                        fields.iter().try_for_each(|(field, value)| {
+                            self.write_bytes(field)?;
+                            self.write_bytes(value)
+                        })
+                    })?;
+                if has_groups {
+                    self.write_groups(stream)?;
+                }
+                Ok(())
+            }
+            Value::String(s) => {
+                self.write(&[STRING])?;
+                self.write_bytes(key)?;
+                self.write_bytes(s)
+            }
+        }
+    }
+
+    /// Writes each group's last ID and entries read, or -1 if unknown,
+    /// then its consumers with the times they were seen and active, or -1 if never,
+    /// then its pending entries.
+    fn write_groups(&mut self, stream: &Stream) -> io::Result<()> {
+        self.write_length(stream.groups().count())?;
+        stream.groups().try_for_each(|(name, group)| {
+            self.write_bytes(name)?;
+            self.write_id(&group.last_id)?;
+            let entries_read = group.entries_read.map_or(-1, |n| n as i64);
+            self.write(&entries_read.to_le_bytes())?;
+
+            self.write_length(group.consumers().len())?;
+            group.consumers().iter().try_for_each(|(name, consumer)| {
+                self.write_bytes(name)?;
+                self.write(&consumer.seen_at.timestamp_millis().to_le_bytes())?;
+                let active_at = consumer.active_at.map_or(-1, |at| at.timestamp_millis());
+                self.write(&active_at.to_le_bytes())
+            })?;
+
+            self.write_length(group.pending().len())?;
+            group.pending().iter().try_for_each(|(id, entry)| {
+                self.write_id(id)?;
+                self.write_bytes(&entry.consumer)?;
+                self.write(&entry.delivered_at.timestamp_millis().to_le_bytes())?;
+                self.write(&entry.delivery_count.to_le_bytes())
+            })
+        })
+    }
+
+    fn write_id(&mut self, id: &StreamId) -> io::Result<()> {
+        self.write(&id.ms.to_le_bytes())?;
+        self.write(&id.seq.to_le_bytes())
+    }
+
+    /// Appends the checksum and returns the flushed output.
+    fn finish(mut self) -> io::Result<W> {
+        let crc = self.crc;
+        self.out.write_all(&crc.to_le_bytes())?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, index: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.index == self.data.len()
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if len > self.data.len() - self.index {
+            return Err(invalid("Short read loading DB"));
+        }
+
+        self.index += len;
+        Ok(&self.data[self.index - len..self.index])
+    }
+
+    fn byte(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i64(&mut self) -> io::Result<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn id(&mut self) -> io::Result<StreamId> {
+        Ok(StreamId::new(self.u64()?, self.u64()?))
+    }
+
+    fn time(&mut self) -> io::Result<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(self.i64()?)
+            .ok_or_else(|| invalid("Invalid time loading DB"))
+    }
+
+    fn groups(&mut self, stream: &mut Stream) -> io::Result<()> {
+        let len = self.length()?;
+        for _ in 0..len {
+            let name = self.bytes()?;
+            let last_id = self.id()?;
+            let entries_read = Some(self.i64()?).filter(|n| *n >= 0).map(|n| n as u64);
+            let mut group = ConsumerGroup::new(last_id, entries_read);
+
+            let consumers = self.length()?;
+            for _ in 0..consumers {
+                let consumer = self.bytes()?;
+                group.create_consumer(&consumer, self.time()?);
+                let active_at = match self.i64()? {
+                    -1 => None,
+                    ms => Some(
+                        DateTime::from_timestamp_millis(ms)
+                            .ok_or_else(|| invalid("Invalid time loading DB"))?,
+                    ),
+                };
+                group.consumer_mut(&consumer).expect("created").active_at = active_at;
+            }
+
+            let pending = self.length()?;
+            for _ in 0..pending {
+                let id = self.id()?;
+                let consumer = self.bytes()?;
+                let delivered_at = self.time()?;
+                group.deliver(id, &consumer, delivered_at, self.u64()?);
+            }
+            stream.create_group(&name, group);
+        }
+        Ok(())
+    }
+
+    fn length(&mut self) -> io::Result<usize> {
+        let mut len = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            len |= ((byte & 0x7F) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(len);
+            }
+        }
+
+        Err(invalid("Invalid length loading DB"))
+    }
+
+    fn bytes(&mut self) -> io::Result<Vec<u8>> {
+        let len = self.length()?;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn value(&mut self, kind: u8) -> io::Result<Value> {
+        match kind {
+            HASH => {
+                let len = self.length()?;
+                let fields = (0..len)
+                    .map(|_| Ok((self.bytes()?, self.bytes()?)))
+                    .collect::<io::Result<_>>()?;
+                Ok(Value::Hash(fields))
+            }
+            HASH_TTL => {
+                let now = Utc::now();
+                let len = self.length()?;
+                let mut fields = Hash::new();
+                for _ in 0..len {
+                    let (field, value) = (self.bytes()?, self.bytes()?);
+                    let at = match self.i64()? {
+                        -1 => None,
+                        ms => Some(
+                            DateTime::from_timestamp_millis(ms)
+                                .ok_or_else(|| invalid("Invalid expire time"))?,
+                        ),
+                    };
+                    // skip fields that expired since the save
+                    if at.is_none_or(|at| at > now) {
+                        fields.insert(field.clone(), value);
+                        if let Some(at) = at {
+                            fields.expire_at(&field, &at);
+                        }
+                    }
+                }
+                Ok(Value::Hash(fields))
+            }
+            INTEGER => Ok(Value::Integer(self.i64()?)),
+            LIST => {
+                let len = self.length()?;
+                let list = (0..len).map(|_| self.bytes()).collect::<io::Result<_>>()?;
+                Ok(Value::List(list))
+            }
+            SET => {
+                let len = self.length()?;
+                let members = (0..len).map(|_| self.bytes()).collect::<io::Result<_>>()?;
+                Ok(Value::Set(members))
+            }
+            SORTED_SET => {
+                let len = self.length()?;
+                let mut members = SortedSet::new();
+                for _ in 0..len {
+                    let member = self.bytes()?;
+                    let score = f64::from_le_bytes(self.take(8)?.try_into().unwrap());
+                    if score.is_nan() {
+                        return Err(invalid("Invalid score loading DB"));
+                    }
+                    members.insert(member, score);
+                }
+                Ok(Value::SortedSet(members))
+            }
+            STREAM | STREAM_GROUPS => {
+                let (last_id, max_deleted_id) = (self.id()?, self.id()?);
+                let entries_added = self.u64()?;
+                let len = self.length()?;
+                let mut stream = Stream::new();
+                for _ in 0..len {
+                    let id = self.id()?;
+                    let count = self.length()?;
+                    let fields = (0..count)
+                        .map(|_| Ok((self.bytes()?, self.bytes()?)))
+                        .collect::<io::Result<_>>()?;
+                    stream.add(id, fields);
+                }
+                stream.set_last_id(last_id, Some(entries_added), Some(max_deleted_id));
+                if kind == STREAM_GROUPS {
+                    self.groups(&mut stream)?;
+                }
+                Ok(Value::Stream(stream))
+            }
+            STRING => Ok(Value::String(self.bytes()?)),
+            _ => Err(invalid(&format!("Unknown value type {}", kind))),
+        }
+    }
+}
+
+/// The reflected Jones polynomial Redis uses for its CRC-64.
+const CRC64_POLY: u64 = 0x95AC_9329_AC4B_C9B5;
+
+const CRC64_TABLE: [u64; 256] = crc64_table();
+
+const fn crc64_table() -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ CRC64_POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+fn crc64(mut crc: u64, data: &[u8]) -> u64 {
+    for byte in data {
+        crc = CRC64_TABLE[((crc ^ *byte as u64) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    /// Returns a time in whole milliseconds, which is all a snapshot keeps.
+    fn at(offset: TimeDelta) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis((Utc::now() + offset).timestamp_millis()).unwrap()
+    }
+
+    fn sorted_entries(store: &Store) -> Vec<(Vec<u8>, Value, Option<DateTime<Utc>>)> {
+        let mut entries: Vec<_> = store
+            .entries()
+            .map(|(key, value, expires)| (key.clone(), value.clone(), expires.copied()))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    fn round_trip(databases: &[Store]) -> Vec<Store> {
+        let mut loaded = vec![Store::new(); databases.len()];
+        decode(&encode(databases), &mut loaded).unwrap();
+        loaded
+    }
+
+    fn stream() -> Stream {
+        let mut stream = Stream::new();
+        stream.add(StreamId::new(1, 0), vec![(b"a".to_vec(), b"1".to_vec())]);
+        stream.add(StreamId::new(1, 1), vec![(b"b".to_vec(), b"2".to_vec())]);
+        stream.add(StreamId::new(5, 0), Vec::new());
+        stream.remove(&StreamId::new(1, 1));
+        stream
+    }
+
+    #[test]
+    fn round_trips_every_value_type() {
+        let mut hash = Hash::new();
+        hash.insert(b"field".to_vec(), b"value".to_vec());
+        let mut hash_ttl = hash.clone();
+        hash_ttl.insert(b"volatile".to_vec(), b"value".to_vec());
+        hash_ttl.expire_at(b"volatile", &at(TimeDelta::hours(1)));
+
+        let sorted_set: SortedSet = [
+            (b"low".to_vec(), f64::NEG_INFINITY),
+            (b"mid".to_vec(), 1.5),
+            (b"high".to_vec(), f64::INFINITY),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut stream_groups = stream();
+        let mut group = ConsumerGroup::new(StreamId::new(1, 0), Some(1));
+        group.deliver(StreamId::new(1, 0), b"alice", at(TimeDelta::zero()), 2);
+        group.create_consumer(b"bob", at(TimeDelta::seconds(-5)));
+        stream_groups.create_group(b"group", group);
+        stream_groups.create_group(b"empty", ConsumerGroup::new(StreamId::default(), None));
+
+        let mut databases = vec![Store::new(), Store::new(), Store::new()];
+        let values = [
+            ("string", Value::String(b"hello".to_vec())),
+            ("integer", Value::Integer(-42)),
+            (
+                "list",
+                Value::List(vec![b"a".to_vec(), b"".to_vec(), b"a".to_vec()]),
+            ),
+            (
+                "set",
+                Value::Set(HashSet::from([b"x".to_vec(), b"y".to_vec()])),
+            ),
+            ("hash", Value::Hash(hash)),
+            ("hash_ttl", Value::Hash(hash_ttl)),
+            ("sorted_set", Value::SortedSet(sorted_set)),
+            ("stream", Value::Stream(stream())),
+            ("stream_groups", Value::Stream(stream_groups)),
+        ];
+        for (key, value) in values {
+            databases[0].set(key.as_bytes(), value);
+        }
+        databases[2].set_entry(
+            b"volatile",
+            Value::Integer(1),
+            Some(at(TimeDelta::hours(1))),
+        );
+
+        let loaded = round_trip(&databases);
+        for (db, store) in databases.iter().enumerate() {
+            assert_eq!(sorted_entries(&loaded[db]), sorted_entries(store));
+        }
+    }
+
+    #[test]
+    fn skips_expired_keys() {
+        let mut databases = vec![Store::new()];
+        databases[0].set_entry(
+            b"expired",
+            Value::Integer(1),
+            Some(at(TimeDelta::seconds(-1))),
+        );
+        databases[0].set(b"kept", Value::Integer(2));
+
+        let loaded = round_trip(&databases);
+        let keys: Vec<_> = sorted_entries(&loaded[0])
+            .into_iter()
+            .map(|(key, ..)| key)
+            .collect();
+        assert_eq!(keys, vec![b"kept".to_vec()]);
+    }
+
+    #[test]
+    fn skips_hashes_whose_fields_all_expired() {
+        let mut expired = Hash::new();
+        expired.insert(b"field".to_vec(), b"value".to_vec());
+        expired.expire_at(b"field", &at(TimeDelta::seconds(-1)));
+        let mut expiring = Hash::new();
+        expiring.insert(b"field".to_vec(), b"value".to_vec());
+        expiring.expire_at(b"field", &at(TimeDelta::milliseconds(50)));
+
+        let mut databases = vec![Store::new()];
+        databases[0].set(b"expired", Value::Hash(expired));
+        databases[0].set(b"expiring", Value::Hash(expiring));
+        databases[0].set(b"kept", Value::Integer(1));
+
+        let data = encode(&databases);
+        assert!(!data
+            .windows(b"expired".len())
+            .any(|window| window == b"expired"));
+
+        // the other expires between saving and loading
+        std::thread::sleep(std::time::Duration::from_millis(60));
+        let mut loaded = vec![Store::new()];
+        decode(&data, &mut loaded).unwrap();
+        let keys: Vec<_> = sorted_entries(&loaded[0])
+            .into_iter()
+            .map(|(key, ..)| key)
+            .collect();
+        assert_eq!(keys, vec![b"kept".to_vec()]);
+    }
+
+    #[test]
+    fn rejects_corrupted_checksum() {
+        let mut databases = vec![Store::new()];
+        databases[0].set(b"key", Value::String(b"value".to_vec()));
+        let data = encode(&databases);
+
+        for index in [MAGIC.len() + 4, data.len() - 1] {
+            let mut corrupted = data.clone();
+            corrupted[index] ^= 0x01;
+
+            let err = decode(&corrupted, &mut [Store::new()]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+            assert_eq!(err.to_string(), "Wrong RDB checksum");
+        }
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let data = encode(&[Store::new()]);
+        let mut newer = data[..data.len() - 8].to_vec();
+        newer[MAGIC.len()..MAGIC.len() + 2].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        newer.extend_from_slice(&crc64(0, &newer).to_le_bytes());
+
+        let err = decode(&newer, &mut [Store::new()]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            format!("Can't handle RDB format version {}", VERSION + 1)
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::thread::JoinHandle;
+
+use chrono::{DateTime, Utc};
+
+use crate::config::Config;
+use crate::storage::Store;
+
+use super::rdb;
+
+/// How long to wait after a failed background save before the save points may try again.
+const RETRY_DELAY_SECS: i64 = 5;
+
+/// Snapshots tracks when the databases were last saved to disk
+/// and the background save in progress, if any.
+pub struct Snapshots {
+    last_save: DateTime<Utc>,
+    last_attempt: DateTime<Utc>,
+    last_ok: bool,
+    /// the total changes to the databases as of the last successful save
+    saved_dirty: u64,
+    /// the background save and the total changes when it started
+    background: Option<(JoinHandle<io::Result<()>>, u64)>,
+    scheduled: bool,
+}
+
+impl Snapshots {
+    pub fn new() -> Self {
+        let now = Utc::now();
+
+        Self {
+            last_save: now,
+            last_attempt: now,
+            last_ok: true,
+            saved_dirty: 0,
+            background: None,
+            scheduled: false,
+        }
+    }
+
+    pub fn last_save(&self) -> &DateTime<Utc> {
+        &self.last_save
+    }
+
+    pub fn in_progress(&self) -> bool {
+        self.background.is_some()
+    }
+
+    /// Returns `true` if the last save succeeded.
+    pub fn last_ok(&self) -> bool {
+        self.last_ok
+    }
+
+    /// Returns the number of changes since the last successful save.
+    pub fn changes(&self, databases: &[Store]) -> u64 {
+        dirty(databases).saturating_sub(self.saved_dirty)
+    }
+
+    /// Loads the snapshot into the empty databases.
+    /// Returns `false` if there's no snapshot to load.
+    pub fn load(&mut self, path: &Path, databases: &mut [Store]) -> io::Result<bool> {
+        let loaded = rdb::load(path, databases)?;
+
+        self.clear_changes(databases);
+        Ok(loaded)
+    }
+
+    /// Treats the data in the databases as saved, such as after loading it from disk.
+    pub fn clear_changes(&mut self, databases: &[Store]) {
+        self.saved_dirty = dirty(databases);
+    }
+
+    /// Saves the databases, blocking until the snapshot is on disk.
+    pub fn save(&mut self, path: &Path, databases: &[Store]) -> io::Result<()> {
+        let dirty = dirty(databases);
+        let result = rdb::save(path, databases);
+
+        match &result {
+            Ok(()) => {
+                notice!("DB saved on disk");
+                self.saved(dirty);
+            }
+            Err(err) => {
+                warning!("Error saving DB on disk: {}", err);
+                self.failed();
+            }
+        }
+        result
+    }
+
+    /// Saves a copy of the databases from another thread
+    /// so clients can keep using them while it's written.
+    pub fn start_background_save(&mut self, path: PathBuf, databases: &[Store]) {
+        let copy = databases.to_vec();
+        let handle = thread::spawn(move || rdb::save(&path, &copy));
+
+        notice!("Background saving started");
+        self.background = Some((handle, dirty(databases)));
+        self.scheduled = false;
+    }
+
+    /// Starts a background save once the one in progress finishes.
+    pub fn schedule(&mut self) {
+        self.scheduled = true;
+    }
+
+    /// Collects the background save if it has finished
+    /// and starts another if one was scheduled or a save point was reached.
+    pub fn run(&mut self, config: &Config, databases: &[Store]) {
+        if let Some((handle, dirty)) = self.background.take_if(|(handle, _)| handle.is_finished()) {
+            match handle.join() {
+                Ok(Ok(())) => {
+                    notice!("Background saving terminated with success");
+                    self.saved(dirty);
+                }
+                Ok(Err(err)) => {
+                    warning!("Background saving error: {}", err);
+                    self.failed();
+                }
+                Err(_) => {
+                    warning!("Background saving terminated abnormally");
+                    self.failed();
+                }
+            }
+        }
+        if self.background.is_some() {
+            return;
+        }
+
+        let path = PathBuf::from(&config.db_filename);
+        if self.scheduled {
+            self.start_background_save(path, databases);
+            return;
+        }
+
+        let now = Utc::now();
+        if !self.last_ok && (now - self.last_attempt).num_seconds() <= RETRY_DELAY_SECS {
+            return;
+        }
+
+        let changes = self.changes(databases);
+        let elapsed = (now - self.last_save).num_seconds().max(0) as u64;
+        if let Some((seconds, _)) = config
+            .save
+            .iter()
+            .find(|(seconds, min_changes)| changes >= *min_changes && elapsed >= *seconds)
+        {
+            notice!("{} changes in {} seconds. Saving...", changes, seconds);
+            self.start_background_save(path, databases);
+        }
+    }
+
+    fn saved(&mut self, dirty: u64) {
+        self.last_save = Utc::now();
+        self.last_attempt = self.last_save;
+        self.last_ok = true;
+        self.saved_dirty = dirty;
+    }
+
+    fn failed(&mut self) {
+        self.last_attempt = Utc::now();
+        self.last_ok = false;
+    }
+}
+
+fn dirty(databases: &[Store]) -> u64 {
+    databases.iter().map(|store| store.dirty()).sum()
+}
@@ -129,7 +129,7 @@ impl Parser {
         if self.input.is_empty() {
             return Ok(None);
         }
-        match self.input[*index..].as_bytes()[0] {
+        match self.input.as_bytes()[*index] {
             // '+' => self.parse_simple_string(),
             // '-' => self.parse_error(),
             // ':' => self.parse_integer(),
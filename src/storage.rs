@@ -1,11 +1,26 @@
+pub use consumer_group::{Consumer, ConsumerGroup, PendingEntry};
+pub use glob::Glob;
+pub use hash::Hash;
 pub use pattern::Pattern;
 pub use ranges::{clamp, clamp_range};
-pub use sets::{diff, intersect, pop_random_members, Random, random_members, SetOp, union};
+pub use sets::{diff, intersect, pop_random_members, Random, random_members, SetOp, SetOpCard, union};
+pub use slot::key_slot;
+pub use sorted_set::{format_score, LexBound, ScoreBound, SortedSet};
+pub use sorted_sets::{Aggregate, SortedSetOp, zdiff, zinter, zinter_card, zunion};
 pub use store::{IfKindResult, Store};
+pub use stream::{Fields, Stream, StreamId, Trim};
 pub use value::{Kind, Value};
 
+mod consumer_group;
+mod glob;
+mod hash;
 mod pattern;
 mod ranges;
 mod sets;
+mod skiplist;
+mod slot;
+mod sorted_set;
+mod sorted_sets;
 mod store;
+mod stream;
 mod value;
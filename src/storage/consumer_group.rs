@@ -0,0 +1,150 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::prelude::*;
+
+use super::StreamId;
+
+/// PendingEntry is an entry delivered to a consumer that hasn't acknowledged it yet.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingEntry {
+    pub consumer: Vec<u8>,
+    pub delivered_at: DateTime<Utc>,
+    pub delivery_count: u64,
+}
+
+/// Consumer tracks when a consumer last tried to read or claim entries,
+/// when it last succeeded, and the entries pending for it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Consumer {
+    pub seen_at: DateTime<Utc>,
+    pub active_at: Option<DateTime<Utc>>,
+    pending: BTreeSet<StreamId>,
+}
+
+impl Consumer {
+    pub fn new(seen_at: DateTime<Utc>) -> Self {
+        Self {
+            seen_at,
+            active_at: None,
+            pending: BTreeSet::new(),
+        }
+    }
+
+    pub fn pending(&self) -> &BTreeSet<StreamId> {
+        &self.pending
+    }
+}
+
+/// ConsumerGroup tracks the last entry delivered to a group of consumers
+/// and the entries they haven't acknowledged.
+///
+/// `entries_read` counts the entries added up to the last one delivered,
+/// or is `None` if deletions made it impossible to tell.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsumerGroup {
+    pub last_id: StreamId,
+    pub entries_read: Option<u64>,
+    pending: BTreeMap<StreamId, PendingEntry>,
+    consumers: BTreeMap<Vec<u8>, Consumer>,
+}
+
+impl ConsumerGroup {
+    pub fn new(last_id: StreamId, entries_read: Option<u64>) -> Self {
+        Self {
+            last_id,
+            entries_read,
+            pending: BTreeMap::new(),
+            consumers: BTreeMap::new(),
+        }
+    }
+
+    pub fn pending(&self) -> &BTreeMap<StreamId, PendingEntry> {
+        &self.pending
+    }
+
+    pub fn consumers(&self) -> &BTreeMap<Vec<u8>, Consumer> {
+        &self.consumers
+    }
+
+    pub fn consumer(&self, name: &[u8]) -> Option<&Consumer> {
+        self.consumers.get(name)
+    }
+
+    pub fn consumer_mut(&mut self, name: &[u8]) -> Option<&mut Consumer> {
+        self.consumers.get_mut(name)
+    }
+
+    /// Adds the consumer and returns `true` if it didn't exist.
+    pub fn create_consumer(&mut self, name: &[u8], now: DateTime<Utc>) -> bool {
+        if self.consumers.contains_key(name) {
+            return false;
+        }
+        self.consumers.insert(name.to_vec(), Consumer::new(now));
+        true
+    }
+
+    /// Marks the consumer as seen, and active if `active`, creating it if needed.
+    pub fn touch_consumer(&mut self, name: &[u8], now: DateTime<Utc>, active: bool) {
+        let consumer = self
+            .consumers
+            .entry(name.to_vec())
+            .or_insert_with(|| Consumer::new(now));
+        consumer.seen_at = now;
+        if active {
+            consumer.active_at = Some(now);
+        }
+    }
+
+    /// Removes the consumer and its pending entries, returning how many it had.
+    pub fn delete_consumer(&mut self, name: &[u8]) -> Option<usize> {
+        let consumer = self.consumers.remove(name)?;
+        for id in &consumer.pending {
+            self.pending.remove(id);
+        }
+        Some(consumer.pending.len())
+    }
+
+    /// Records the entry as pending for the consumer, taking it from any other consumer.
+    pub fn deliver(
+        &mut self,
+        id: StreamId,
+        consumer: &[u8],
+        delivered_at: DateTime<Utc>,
+        delivery_count: u64,
+    ) {
+        let entry = PendingEntry {
+            consumer: consumer.to_vec(),
+            delivered_at,
+            delivery_count,
+        };
+        if let Some(old) = self.pending.insert(id, entry) {
+            if let Some(owner) = self.consumers.get_mut(&old.consumer) {
+                owner.pending.remove(&id);
+            }
+        }
+        self.consumers
+            .entry(consumer.to_vec())
+            .or_insert_with(|| Consumer::new(delivered_at))
+            .pending
+            .insert(id);
+    }
+
+    /// Records that the pending entry was delivered to its consumer again.
+    pub fn redeliver(&mut self, id: &StreamId, delivered_at: DateTime<Utc>) {
+        if let Some(entry) = self.pending.get_mut(id) {
+            entry.delivered_at = delivered_at;
+            entry.delivery_count += 1;
+        }
+    }
+
+    /// Removes the entry from those pending and returns `true` if it was pending.
+    pub fn ack(&mut self, id: &StreamId) -> bool {
+        let Some(entry) = self.pending.remove(id) else {
+            return false;
+        };
+        if let Some(consumer) = self.consumers.get_mut(&entry.consumer) {
+            consumer.pending.remove(id);
+        }
+        true
+    }
+}
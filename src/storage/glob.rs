@@ -0,0 +1,121 @@
+/// Glob matches strings using Redis' glob-style patterns:
+/// `*` matches any sequence, `?` matches any single byte,
+/// `[abc]`, `[^abc]` and `[a-z]` match classes of bytes,
+/// and `\` escapes the next byte.
+pub struct Glob {
+    pattern: Vec<u8>,
+    no_case: bool,
+}
+
+impl Glob {
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            no_case: false,
+        }
+    }
+
+    pub fn new_no_case(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            no_case: true,
+        }
+    }
+
+    pub fn matches(&self, s: &[u8]) -> bool {
+        self.matches_from(0, s)
+    }
+
+    fn matches_from(&self, mut p: usize, s: &[u8]) -> bool {
+        let pattern = &self.pattern;
+        let mut i = 0;
+
+        while p < pattern.len() {
+            match pattern[p] {
+                b'*' => {
+                    while p + 1 < pattern.len() && pattern[p + 1] == b'*' {
+                        p += 1;
+                    }
+                    if p + 1 == pattern.len() {
+                        return true;
+                    }
+                    return (i..=s.len()).any(|start| self.matches_from(p + 1, &s[start..]));
+                }
+                b'?' => {
+                    if i == s.len() {
+                        return false;
+                    }
+                    i += 1;
+                }
+                b'[' => {
+                    if i == s.len() {
+                        return false;
+                    }
+                    let (matched, end) = self.match_class(p + 1, s[i]);
+                    if !matched {
+                        return false;
+                    }
+                    p = end;
+                    i += 1;
+                }
+                byte => {
+                    let byte = if byte == b'\\' && p + 1 < pattern.len() {
+                        p += 1;
+                        pattern[p]
+                    } else {
+                        byte
+                    };
+                    if i == s.len() || !self.eq(byte, s[i]) {
+                        return false;
+                    }
+                    i += 1;
+                }
+            }
+            p += 1;
+        }
+
+        i == s.len()
+    }
+
+    /// Returns whether the byte matches the class starting at `p`
+    /// and the index of the closing bracket.
+    fn match_class(&self, mut p: usize, byte: u8) -> (bool, usize) {
+        let pattern = &self.pattern;
+        let negate = p < pattern.len() && pattern[p] == b'^';
+        let mut matched = false;
+
+        if negate {
+            p += 1;
+        }
+        while p < pattern.len() && pattern[p] != b']' {
+            if pattern[p] == b'\\' && p + 1 < pattern.len() {
+                p += 1;
+                matched |= self.eq(pattern[p], byte);
+            } else if p + 2 < pattern.len() && pattern[p + 1] == b'-' && pattern[p + 2] != b']' {
+                let (mut start, mut end) = (pattern[p], pattern[p + 2]);
+                if start > end {
+                    (start, end) = (end, start);
+                }
+                matched |= if self.no_case {
+                    (start..=end).any(|c| c.eq_ignore_ascii_case(&byte))
+                } else {
+                    (start..=end).contains(&byte)
+                };
+                p += 2;
+            } else {
+                matched |= self.eq(pattern[p], byte);
+            }
+            p += 1;
+        }
+
+        (matched != negate, usize::min(p, pattern.len() - 1))
+    }
+
+    fn eq(&self, a: u8, b: u8) -> bool {
+        if self.no_case {
+            a.eq_ignore_ascii_case(&b)
+        } else {
+            a == b
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use chrono::prelude::*;
+use priority_queue::PriorityQueue;
+
+/// Hash maps fields to values, each of which may expire on its own.
+///
+/// Expired fields remain until `expire_fields` removes them,
+/// which the store does before handing out the hash.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Hash {
+    fields: HashMap<Vec<u8>, Vec<u8>>,
+    // reversed so the earliest expiration has the highest priority
+    expirations: PriorityQueue<Vec<u8>, Reverse<DateTime<Utc>>>,
+}
+
+impl Hash {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn contains_key(&self, field: &[u8]) -> bool {
+        self.fields.contains_key(field)
+    }
+
+    pub fn get(&self, field: &[u8]) -> Option<&Vec<u8>> {
+        self.fields.get(field)
+    }
+
+    /// Sets the field's value, clearing any expiration it had.
+    pub fn insert(&mut self, field: Vec<u8>, value: Vec<u8>) -> Option<Vec<u8>> {
+        self.expirations.remove(&field);
+        self.fields.insert(field, value)
+    }
+
+    /// Sets the field's value, keeping any expiration it had.
+    pub fn replace(&mut self, field: Vec<u8>, value: Vec<u8>) -> Option<Vec<u8>> {
+        self.fields.insert(field, value)
+    }
+
+    pub fn remove(&mut self, field: &[u8]) -> Option<Vec<u8>> {
+        self.expirations.remove(field);
+        self.fields.remove(field)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Vec<u8>, &Vec<u8>)> {
+        self.fields.iter()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &Vec<u8>> {
+        self.fields.keys()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Vec<u8>> {
+        self.fields.values()
+    }
+
+    pub fn expires(&self, field: &[u8]) -> Option<&DateTime<Utc>> {
+        self.expirations.get_priority(field).map(|Reverse(at)| at)
+    }
+
+    /// Iterates over every field that has an expiration.
+    pub fn expirations(&self) -> impl Iterator<Item = (&Vec<u8>, &DateTime<Utc>)> {
+        self.expirations
+            .iter()
+            .map(|(field, Reverse(at))| (field, at))
+    }
+
+    /// Sets the expiration of a field that exists.
+    pub fn expire_at(&mut self, field: &[u8], at: &DateTime<Utc>) {
+        if self.fields.contains_key(field) {
+            self.expirations.push(field.to_vec(), Reverse(*at));
+        }
+    }
+
+    /// Clears the field's expiration and returns `true` if it had one.
+    pub fn persist(&mut self, field: &[u8]) -> bool {
+        self.expirations.remove(field).is_some()
+    }
+
+    /// Returns `true` if every field has expired by `now`, leaving the hash empty once they're removed.
+    pub fn is_expired(&self, now: &DateTime<Utc>) -> bool {
+        self.expirations.len() == self.fields.len()
+            && self.expirations.iter().all(|(_, Reverse(at))| at <= now)
+    }
+
+    pub fn next_expiration(&self) -> Option<&DateTime<Utc>> {
+        self.expirations.peek().map(|(_, Reverse(at))| at)
+    }
+
+    /// Removes the fields that expired by `now` and returns how many were removed.
+    pub fn expire_fields(&mut self, now: &DateTime<Utc>) -> usize {
+        let mut expired = 0;
+
+        while let Some((_, Reverse(at))) = self.expirations.peek() {
+            if at > now {
+                break;
+            }
+            let (field, _) = self.expirations.pop().expect("peeked");
+            self.fields.remove(&field);
+            expired += 1;
+        }
+
+        expired
+    }
+}
+
+impl From<HashMap<Vec<u8>, Vec<u8>>> for Hash {
+    fn from(fields: HashMap<Vec<u8>, Vec<u8>>) -> Self {
+        Self {
+            fields,
+            expirations: PriorityQueue::new(),
+        }
+    }
+}
+
+impl FromIterator<(Vec<u8>, Vec<u8>)> for Hash {
+    fn from_iter<T: IntoIterator<Item = (Vec<u8>, Vec<u8>)>>(iter: T) -> Self {
+        Self::from(iter.into_iter().collect::<HashMap<_, _>>())
+    }
+}
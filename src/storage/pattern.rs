@@ -1,4 +1,4 @@
-use regex::Regex;
+use regex::bytes::Regex;
 
 /// Pattern uses a regular expression to match keys
 /// instead of Redis' pattern matching.
@@ -7,21 +7,44 @@ pub struct Pattern {
 }
 
 impl Pattern {
-    pub fn try_parse(pattern: &str) -> Result<Self, String> {
+    pub fn try_parse(pattern: &[u8]) -> Result<Self, String> {
         Ok(Self {
             pattern: parse_pattern(pattern)?,
         })
     }
 
-    pub fn matches(&self, key: &str) -> bool {
+    pub fn matches(&self, key: &[u8]) -> bool {
         self.pattern.is_match(key)
     }
 }
 
-fn parse_pattern(str: &str) -> Result<Regex, String> {
+fn parse_pattern(bytes: &[u8]) -> Result<Regex, String> {
     let mut pattern = String::new();
 
-    pattern.push_str(str);
+    // bytes that aren't UTF-8 match themselves
+    for chunk in bytes.utf8_chunks() {
+        pattern.push_str(chunk.valid());
+        for byte in chunk.invalid() {
+            pattern.push_str(&format!("(?-u:\\x{byte:02X})"));
+        }
+    }
 
     Regex::new(&pattern).map_err(|err| err.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_keys_that_are_not_utf8() {
+        let pattern = Pattern::try_parse(b"key:\xff.*").unwrap();
+        assert!(pattern.matches(b"key:\xff\x00"));
+        assert!(!pattern.matches(b"key:\xfe"));
+
+        let pattern = Pattern::try_parse(b"caf\xc3\xa9|\xff+").unwrap();
+        assert!(pattern.matches("caf\u{e9}".as_bytes()));
+        assert!(pattern.matches(b"\xff\xff"));
+        assert!(!pattern.matches(b"\xfe"));
+    }
+}
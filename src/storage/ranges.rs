@@ -2,11 +2,8 @@ use std::ops::Range;
 
 pub fn clamp(len: usize, index: i64) -> usize {
     match index {
-        0 => 0,
-        i if i < 0 && -i >= len as i64 => 0,
-        i if i < 0 => (i + len as i64) as usize,
-        i if i > len as i64 => len,
-        i => i as usize,
+        i if i < 0 => len.saturating_sub(i.unsigned_abs() as usize),
+        i => len.min(i as usize),
     }
 }
 
@@ -14,7 +11,7 @@ pub fn clamp_range(len: usize, start: i64, end: i64) -> Range<usize> {
     let start = clamp(len, start);
     let end = 1 + clamp(len, end);
 
-    println!("start: {}, end: {}", start, end);
+    debug!("start: {}, end: {}", start, end);
 
     if len < end {
         start..len
@@ -7,7 +7,7 @@ use rand::seq::SliceRandom;
 use crate::storage::{IfKindResult, Kind, Store, Value};
 
 pub enum SetOp<'a> {
-    New(HashSet<String>),
+    New(HashSet<Vec<u8>>),
     ValueRef(&'a Value),
     Empty,
     WrongType,
@@ -19,7 +19,7 @@ pub enum SetOpCard {
     WrongType,
 }
 
-pub fn diff<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) -> SetOp<'a> {
+pub fn diff<'a>(store: &'a mut Store, keys: &Vec<Vec<u8>>, limit: usize) -> SetOp<'a> {
     if keys.len() == 1 {
         return trim_to_limit(store, &keys[0], limit);
     }
@@ -28,7 +28,7 @@ pub fn diff<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) -> SetOp
         IfKindResult::Matched(values) => {
             let mut diff = HashSet::new();
             do_diff(values, limit, |member| {
-                diff.insert(member.to_string());
+                diff.insert(member.to_vec());
                 diff.len()
             });
 
@@ -45,9 +45,9 @@ pub fn diff<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) -> SetOp
 
 fn do_diff<Insert>(values: Vec<&Value>, limit: usize, mut insert: Insert)
 where
-    Insert: FnMut(&str) -> usize,
+    Insert: FnMut(&[u8]) -> usize,
 {
-    let sets: Vec<&HashSet<String>> = values.iter().map(|set| set.expect_set()).collect();
+    let sets: Vec<&HashSet<Vec<u8>>> = values.iter().map(|set| set.expect_set()).collect();
 
     'outer: for member in sets[0] {
         for set in &sets[1..] {
@@ -61,7 +61,7 @@ where
     }
 }
 
-pub fn intersect<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) -> SetOp<'a> {
+pub fn intersect<'a>(store: &'a mut Store, keys: &Vec<Vec<u8>>, limit: usize) -> SetOp<'a> {
     if keys.len() == 1 {
         return trim_to_limit(store, &keys[0], limit);
     }
@@ -74,7 +74,7 @@ pub fn intersect<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) ->
 
             let mut intersection = HashSet::new();
             do_intersect(values, limit, |member| {
-                intersection.insert(member.to_string());
+                intersection.insert(member.to_vec());
                 intersection.len()
             });
 
@@ -89,7 +89,7 @@ pub fn intersect<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) ->
     }
 }
 
-pub fn intersect_card(store: &mut Store, keys: &Vec<String>, limit: usize) -> SetOpCard {
+pub fn intersect_card(store: &mut Store, keys: &Vec<Vec<u8>>, limit: usize) -> SetOpCard {
     if keys.len() == 1 {
         return match store.get_if_kind(Kind::Set, &keys[0]) {
             IfKindResult::Matched(Value::Set(members)) => {
@@ -125,9 +125,9 @@ pub fn intersect_card(store: &mut Store, keys: &Vec<String>, limit: usize) -> Se
 
 fn do_intersect<Insert>(values: Vec<&Value>, limit: usize, mut insert: Insert)
 where
-    Insert: FnMut(&str) -> usize,
+    Insert: FnMut(&[u8]) -> usize,
 {
-    let sets: Vec<&HashSet<String>> = values
+    let sets: Vec<&HashSet<Vec<u8>>> = values
         .iter()
         .map(|set| set.expect_set())
         .sorted_by(|a, b| a.len().cmp(&b.len()))
@@ -145,7 +145,7 @@ where
     }
 }
 
-pub fn union<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) -> SetOp<'a> {
+pub fn union<'a>(store: &'a mut Store, keys: &Vec<Vec<u8>>, limit: usize) -> SetOp<'a> {
     if keys.len() == 1 {
         return trim_to_limit(store, &keys[0], limit);
     }
@@ -154,7 +154,7 @@ pub fn union<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) -> SetO
         IfKindResult::Matched(values) => {
             let mut union = HashSet::new();
             do_union(values, limit, |member| {
-                (union.insert(member.to_string()), union.len())
+                (union.insert(member.to_vec()), union.len())
             });
 
             if union.is_empty() {
@@ -170,7 +170,7 @@ pub fn union<'a>(store: &'a mut Store, keys: &Vec<String>, limit: usize) -> SetO
 
 fn do_union<Insert>(values: Vec<&Value>, limit: usize, mut insert: Insert)
 where
-    Insert: FnMut(&str) -> (bool, usize),
+    Insert: FnMut(&[u8]) -> (bool, usize),
 {
     for set in values.iter().map(|value| value.expect_set()) {
         for member in set {
@@ -182,8 +182,8 @@ where
     }
 }
 
-fn trim_to_limit<'a>(store: &'a Store, key: &str, limit: usize) -> SetOp<'a> {
-    return match store.get_if_kind(Kind::Set, key) {
+fn trim_to_limit<'a>(store: &'a mut Store, key: &[u8], limit: usize) -> SetOp<'a> {
+    match store.get_if_kind(Kind::Set, key) {
         IfKindResult::Matched(value) if value.is_set() => {
             let members = value.expect_set();
             if members.len() > limit {
@@ -194,18 +194,18 @@ fn trim_to_limit<'a>(store: &'a Store, key: &str, limit: usize) -> SetOp<'a> {
         }
         IfKindResult::NotSet => SetOp::Empty,
         _ => SetOp::WrongType,
-    };
+    }
 }
 
 pub enum Random {
-    Single(String),
-    Elements(Vec<String>),
+    Single(Vec<u8>),
+    Elements(Vec<Vec<u8>>),
     Empty,
     NotSet,
     WrongType,
 }
 
-pub fn random_members(store: &mut Store, key: &str, count: usize, dupes: bool) -> Random {
+pub fn random_members(store: &mut Store, key: &[u8], count: usize, dupes: bool) -> Random {
     if count == 0 {
         return Random::Empty;
     }
@@ -243,7 +243,7 @@ pub fn random_members(store: &mut Store, key: &str, count: usize, dupes: bool) -
     }
 }
 
-pub fn pop_random_members(store: &mut Store, key: &str, count: usize) -> Random {
+pub fn pop_random_members(store: &mut Store, key: &[u8], count: usize) -> Random {
     if count == 0 {
         return Random::Empty;
     }
@@ -0,0 +1,381 @@
+use rand::{Rng, thread_rng};
+
+const MAX_LEVEL: usize = 32;
+// the chance a node also appears on the next level up
+const P: f64 = 0.25;
+// the node before the first, which appears on every level
+const HEAD: usize = 0;
+
+#[derive(Clone, Debug)]
+struct Level {
+    forward: Option<usize>,
+    // the number of nodes passed by following `forward`
+    span: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    member: Vec<u8>,
+    score: f64,
+    backward: Option<usize>,
+    levels: Vec<Level>,
+}
+
+impl Node {
+    fn new(member: Vec<u8>, score: f64, level: usize) -> Self {
+        Self {
+            member,
+            score,
+            backward: None,
+            levels: vec![
+                Level {
+                    forward: None,
+                    span: 0,
+                };
+                level
+            ],
+        }
+    }
+
+    /// Returns `true` if the node sorts before the score and member.
+    fn precedes(&self, score: f64, member: &[u8]) -> bool {
+        self.score < score || (self.score == score && self.member.as_slice() < member)
+    }
+}
+
+/// SkipList orders members by score and then by member,
+/// tracking the span of each link so it can find a member's rank
+/// or the member at a rank in O(log n).
+///
+/// Nodes live in a vector and link by index, reusing the slots of removed nodes.
+#[derive(Clone, Debug)]
+pub struct SkipList {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    tail: Option<usize>,
+    level: usize,
+    len: usize,
+}
+
+impl SkipList {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node::new(Vec::new(), 0.0, MAX_LEVEL)],
+            free: Vec::new(),
+            tail: None,
+            level: 1,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Adds the member, which must not be in the list already.
+    pub fn insert(&mut self, score: f64, member: Vec<u8>) {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut rank = [0; MAX_LEVEL];
+        let mut x = HEAD;
+
+        for i in (0..self.level).rev() {
+            rank[i] = if i == self.level - 1 { 0 } else { rank[i + 1] };
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !self.nodes[next].precedes(score, &member) {
+                    break;
+                }
+                rank[i] += self.nodes[x].levels[i].span;
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        let level = random_level();
+        if level > self.level {
+            for i in self.level..level {
+                rank[i] = 0;
+                update[i] = HEAD;
+                self.nodes[HEAD].levels[i].span = self.len;
+            }
+            self.level = level;
+        }
+
+        let node = self.alloc(Node::new(member, score, level));
+        for i in 0..level {
+            let prev = update[i];
+            let passed = rank[0] - rank[i];
+            self.nodes[node].levels[i] = Level {
+                forward: self.nodes[prev].levels[i].forward,
+                span: self.nodes[prev].levels[i].span - passed,
+            };
+            self.nodes[prev].levels[i] = Level {
+                forward: Some(node),
+                span: passed + 1,
+            };
+        }
+        for (i, &prev) in update.iter().enumerate().take(self.level).skip(level) {
+            self.nodes[prev].levels[i].span += 1;
+        }
+
+        self.nodes[node].backward = (update[0] != HEAD).then_some(update[0]);
+        match self.nodes[node].levels[0].forward {
+            Some(next) => self.nodes[next].backward = Some(node),
+            None => self.tail = Some(node),
+        }
+        self.len += 1;
+    }
+
+    /// Removes the member with the score and returns `true` if it was found.
+    pub fn remove(&mut self, score: f64, member: &[u8]) -> bool {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut x = HEAD;
+
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !self.nodes[next].precedes(score, member) {
+                    break;
+                }
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        match self.nodes[x].levels[0].forward {
+            Some(node) if self.nodes[node].score == score && self.nodes[node].member == member => {
+                self.unlink(node, &update);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the number of members before the member with the score,
+    /// which is its rank if it's in the list.
+    pub fn rank(&self, score: f64, member: &[u8]) -> usize {
+        self.count_before(|node_score, node_member| {
+            node_score < score || (node_score == score && node_member < member)
+        })
+    }
+
+    /// Returns the number of members at the start of the list for which `before` is `true`,
+    /// which must be `false` for every member after the first for which it's `false`.
+    pub fn count_before(&self, before: impl Fn(f64, &[u8]) -> bool) -> usize {
+        let mut rank = 0;
+        let mut x = HEAD;
+
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !before(self.nodes[next].score, &self.nodes[next].member) {
+                    break;
+                }
+                rank += self.nodes[x].levels[i].span;
+                x = next;
+            }
+        }
+
+        rank
+    }
+
+    /// Iterates from the member at the rank toward the highest score.
+    pub fn iter_from(&self, rank: usize) -> Iter<'_> {
+        Iter {
+            list: self,
+            next: self.node_at(rank),
+            reverse: false,
+        }
+    }
+
+    /// Iterates from the member at the rank toward the lowest score.
+    pub fn iter_rev_from(&self, rank: usize) -> Iter<'_> {
+        Iter {
+            list: self,
+            next: self.node_at(rank),
+            reverse: true,
+        }
+    }
+
+    fn node_at(&self, rank: usize) -> Option<usize> {
+        if rank >= self.len {
+            return None;
+        }
+        if rank == self.len - 1 {
+            return self.tail;
+        }
+
+        // ranks count from one here, leaving zero for the head
+        let target = rank + 1;
+        let mut traversed = 0;
+        let mut x = HEAD;
+
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if traversed + self.nodes[x].levels[i].span > target {
+                    break;
+                }
+                traversed += self.nodes[x].levels[i].span;
+                x = next;
+            }
+            if traversed == target {
+                return Some(x);
+            }
+        }
+
+        None
+    }
+
+    fn unlink(&mut self, node: usize, update: &[usize; MAX_LEVEL]) {
+        for (i, &prev) in update.iter().enumerate().take(self.level) {
+            if self.nodes[prev].levels[i].forward == Some(node) {
+                self.nodes[prev].levels[i] = Level {
+                    forward: self.nodes[node].levels[i].forward,
+                    span: self.nodes[prev].levels[i].span + self.nodes[node].levels[i].span - 1,
+                };
+            } else {
+                self.nodes[prev].levels[i].span -= 1;
+            }
+        }
+
+        match self.nodes[node].levels[0].forward {
+            Some(next) => self.nodes[next].backward = self.nodes[node].backward,
+            None => self.tail = self.nodes[node].backward,
+        }
+        while self.level > 1 && self.nodes[HEAD].levels[self.level - 1].forward.is_none() {
+            self.level -= 1;
+        }
+        self.len -= 1;
+
+        // free the member now rather than when the slot is reused
+        self.nodes[node] = Node::new(Vec::new(), 0.0, 0);
+        self.free.push(node);
+    }
+
+    fn alloc(&mut self, node: Node) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+}
+
+impl Default for SkipList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iter walks the list in either direction from a starting node.
+pub struct Iter<'a> {
+    list: &'a SkipList,
+    next: Option<usize>,
+    reverse: bool,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a Vec<u8>, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &self.list.nodes[self.next?];
+        self.next = if self.reverse {
+            node.backward
+        } else {
+            node.levels[0].forward
+        };
+        Some((&node.member, node.score))
+    }
+}
+
+fn random_level() -> usize {
+    let mut rng = thread_rng();
+    let mut level = 1;
+
+    while level < MAX_LEVEL && rng.gen_bool(P) {
+        level += 1;
+    }
+    level
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    /// Checks every rank and iterator against the members sorted by score then member.
+    fn check(list: &SkipList, expected: &[(f64, Vec<u8>)]) {
+        assert_eq!(list.len(), expected.len());
+
+        for (rank, (score, member)) in expected.iter().enumerate() {
+            assert_eq!(list.rank(*score, member), rank);
+            assert_eq!(
+                list.count_before(|s, _| s < *score),
+                expected.partition_point(|(s, _)| s < score)
+            );
+            assert_eq!(
+                list.node_at(rank).map(|node| &list.nodes[node].member),
+                Some(member)
+            );
+
+            let forward: Vec<_> = list
+                .iter_from(rank)
+                .map(|(member, score)| (score, member.clone()))
+                .collect();
+            assert_eq!(forward, expected[rank..]);
+            let backward: Vec<_> = list
+                .iter_rev_from(rank)
+                .map(|(member, score)| (score, member.clone()))
+                .collect();
+            let mut reversed = expected[..=rank].to_vec();
+            reversed.reverse();
+            assert_eq!(backward, reversed);
+        }
+        assert_eq!(list.node_at(expected.len()), None);
+        assert_eq!(list.iter_from(expected.len()).count(), 0);
+    }
+
+    #[test]
+    fn matches_sorted_vec() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut list = SkipList::new();
+        let mut expected: Vec<(f64, Vec<u8>)> = Vec::new();
+
+        for step in 0..2_000usize {
+            // few distinct scores so ties are ordered by member
+            let score = rng.gen_range(0..20) as f64;
+            let member = rng.gen_range(0..200u32).to_string().into_bytes();
+            let position = expected.iter().position(|(_, m)| *m == member);
+
+            match position {
+                Some(i) if rng.gen_bool(0.6) => {
+                    let (score, member) = expected.remove(i);
+                    assert!(list.remove(score, &member));
+                }
+                Some(_) => assert!(!list.remove(score + 0.5, &member)),
+                None => {
+                    list.insert(score, member.clone());
+                    let i = expected
+                        .partition_point(|(s, m)| *s < score || (*s == score && *m < member));
+                    expected.insert(i, (score, member));
+                }
+            }
+            if step.is_multiple_of(100) {
+                check(&list, &expected);
+            }
+        }
+        check(&list, &expected);
+
+        while let Some((score, member)) = expected.pop() {
+            assert!(list.remove(score, &member));
+            if expected.len().is_multiple_of(50) {
+                check(&list, &expected);
+            }
+        }
+        assert_eq!(list.level, 1);
+    }
+}
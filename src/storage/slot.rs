@@ -0,0 +1,55 @@
+/// The number of slots keys are bucketed into, as in a Redis cluster.
+const SLOTS: u16 = 16384;
+
+/// Returns the cluster slot of the key, hashing only the part between the first `{`
+/// and the next `}` when it isn't empty so related keys can share a slot.
+pub fn key_slot(key: &[u8]) -> u16 {
+    let tag = key
+        .iter()
+        .position(|&byte| byte == b'{')
+        .and_then(|start| {
+            key[start + 1..]
+                .iter()
+                .position(|&byte| byte == b'}')
+                .filter(|&len| len > 0)
+                .map(|len| &key[start + 1..start + 1 + len])
+        })
+        .unwrap_or(key);
+
+    crc16(tag) % SLOTS
+}
+
+/// The CCITT polynomial of the XMODEM CRC-16 used for cluster slots.
+const CRC16_POLY: u16 = 0x1021;
+
+const CRC16_TABLE: [u16; 256] = crc16_table();
+
+const fn crc16_table() -> [u16; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ CRC16_POLY
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0;
+    for byte in data {
+        crc = (crc << 8) ^ CRC16_TABLE[(((crc >> 8) ^ *byte as u16) & 0xFF) as usize];
+    }
+    crc
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use super::skiplist::{Iter, SkipList};
+
+/// SortedSet maps members to scores and indexes them by score,
+/// ordering members with the same score lexicographically.
+#[derive(Clone, Debug, Default)]
+pub struct SortedSet {
+    scores: HashMap<Vec<u8>, f64>,
+    index: SkipList,
+}
+
+impl SortedSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn score(&self, member: &[u8]) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    /// Adds the member or changes its score, returning its old score.
+    pub fn insert(&mut self, member: Vec<u8>, score: f64) -> Option<f64> {
+        let old = self.scores.insert(member.clone(), score);
+        match old {
+            Some(old) if old == score => (),
+            Some(old) => {
+                self.index.remove(old, &member);
+                self.index.insert(score, member);
+            }
+            None => self.index.insert(score, member),
+        }
+        old
+    }
+
+    pub fn remove(&mut self, member: &[u8]) -> Option<f64> {
+        let score = self.scores.remove(member)?;
+        self.index.remove(score, member);
+        Some(score)
+    }
+
+    /// Returns the member's position counting from the lowest score.
+    pub fn rank(&self, member: &[u8]) -> Option<usize> {
+        let score = self.score(member)?;
+        Some(self.index.rank(score, member))
+    }
+
+    /// Returns the member's position counting from the highest score.
+    pub fn rev_rank(&self, member: &[u8]) -> Option<usize> {
+        Some(self.len() - 1 - self.rank(member)?)
+    }
+
+    /// Iterates from the lowest score.
+    pub fn iter(&self) -> Iter<'_> {
+        self.index.iter_from(0)
+    }
+
+    /// Iterates from the rank, counting from the highest score when `rev` is `true`.
+    pub fn iter_from(&self, rank: usize, rev: bool) -> Iter<'_> {
+        if rank >= self.len() {
+            self.index.iter_from(rank)
+        } else if rev {
+            self.index.iter_rev_from(self.len() - 1 - rank)
+        } else {
+            self.index.iter_from(rank)
+        }
+    }
+
+    /// Returns the number of members with scores between the bounds.
+    pub fn count(&self, min: &ScoreBound, max: &ScoreBound) -> usize {
+        let below = self.index.count_before(|score, _| min.starts_after(score));
+        let within = self.index.count_before(|score, _| !max.ends_before(score));
+        within.saturating_sub(below)
+    }
+
+    /// Iterates over the members with scores between the bounds,
+    /// from the highest score when `rev` is `true`.
+    pub fn range_by_score<'a>(
+        &'a self,
+        min: &'a ScoreBound,
+        max: &'a ScoreBound,
+        rev: bool,
+    ) -> impl Iterator<Item = (&'a Vec<u8>, f64)> {
+        let iter = if rev {
+            match self.index.count_before(|score, _| !max.ends_before(score)) {
+                0 => self.index.iter_from(self.len()),
+                end => self.index.iter_rev_from(end - 1),
+            }
+        } else {
+            self.index
+                .iter_from(self.index.count_before(|score, _| min.starts_after(score)))
+        };
+
+        iter.take_while(move |(_, score)| {
+            if rev {
+                !min.starts_after(*score)
+            } else {
+                !max.ends_before(*score)
+            }
+        })
+    }
+
+    /// Iterates over the members between the bounds,
+    /// from the highest when `rev` is `true`,
+    /// assuming every member has the same score.
+    pub fn range_by_lex<'a>(
+        &'a self,
+        min: &'a LexBound,
+        max: &'a LexBound,
+        rev: bool,
+    ) -> impl Iterator<Item = (&'a Vec<u8>, f64)> {
+        let iter = if rev {
+            match self
+                .index
+                .count_before(|_, member| !max.ends_before(member))
+            {
+                0 => self.index.iter_from(self.len()),
+                end => self.index.iter_rev_from(end - 1),
+            }
+        } else {
+            self.index.iter_from(
+                self.index
+                    .count_before(|_, member| min.starts_after(member)),
+            )
+        };
+
+        iter.take_while(move |(member, _)| {
+            if rev {
+                !min.starts_after(member)
+            } else {
+                !max.ends_before(member)
+            }
+        })
+    }
+
+    /// Removes up to `count` members with the lowest scores,
+    /// or the highest when `rev` is `true`.
+    pub fn pop(&mut self, count: usize, rev: bool) -> Vec<(Vec<u8>, f64)> {
+        let popped = self
+            .iter_from(0, rev)
+            .take(count)
+            .map(|(member, score)| (member.clone(), score))
+            .collect::<Vec<_>>();
+
+        for (member, score) in &popped {
+            self.scores.remove(member);
+            self.index.remove(*score, member);
+        }
+        popped
+    }
+}
+
+// scores are never NaN, so they're always equal to themselves
+impl Eq for SortedSet {}
+
+impl PartialEq for SortedSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.scores == other.scores
+    }
+}
+
+impl FromIterator<(Vec<u8>, f64)> for SortedSet {
+    fn from_iter<T: IntoIterator<Item = (Vec<u8>, f64)>>(iter: T) -> Self {
+        let mut sorted_set = SortedSet::new();
+        for (member, score) in iter {
+            sorted_set.insert(member, score);
+        }
+        sorted_set
+    }
+}
+
+/// Returns the shortest text that parses back to the score, switching to
+/// an exponent where `%.17g` would: below 1e-4 and from 1e17 up.
+pub fn format_score(score: f64) -> String {
+    if score == 0.0 || score.is_infinite() {
+        return score.to_string();
+    }
+    let scientific = format!("{score:e}");
+    let (mantissa, exponent) = scientific.split_once('e').expect("exponent");
+    let exponent: i32 = exponent.parse().expect("integer exponent");
+    if (-4..17).contains(&exponent) {
+        score.to_string()
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{mantissa}e{sign}{:02}", exponent.abs())
+    }
+}
+
+/// ScoreBound is one end of a range of scores.
+#[derive(Clone, Copy, Debug)]
+pub struct ScoreBound {
+    pub score: f64,
+    pub exclusive: bool,
+}
+
+impl ScoreBound {
+    /// Returns `true` if a range from this bound starts after the score.
+    pub fn starts_after(&self, score: f64) -> bool {
+        if self.exclusive {
+            score <= self.score
+        } else {
+            score < self.score
+        }
+    }
+
+    /// Returns `true` if a range to this bound ends before the score.
+    pub fn ends_before(&self, score: f64) -> bool {
+        if self.exclusive {
+            score >= self.score
+        } else {
+            score > self.score
+        }
+    }
+}
+
+/// LexBound is one end of a range of members.
+#[derive(Clone, Debug)]
+pub enum LexBound {
+    Min,
+    Max,
+    Inclusive(Vec<u8>),
+    Exclusive(Vec<u8>),
+}
+
+impl LexBound {
+    /// Returns `true` if a range from this bound starts after the member.
+    pub fn starts_after(&self, member: &[u8]) -> bool {
+        match self {
+            LexBound::Min => false,
+            LexBound::Max => true,
+            LexBound::Inclusive(bound) => member < bound.as_slice(),
+            LexBound::Exclusive(bound) => member <= bound.as_slice(),
+        }
+    }
+
+    /// Returns `true` if a range to this bound ends before the member.
+    pub fn ends_before(&self, member: &[u8]) -> bool {
+        match self {
+            LexBound::Min => true,
+            LexBound::Max => false,
+            LexBound::Inclusive(bound) => member > bound.as_slice(),
+            LexBound::Exclusive(bound) => member >= bound.as_slice(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_scores_like_printf_g() {
+        assert_eq!(format_score(0.0), "0");
+        assert_eq!(format_score(1.5), "1.5");
+        assert_eq!(format_score(-3.0), "-3");
+        assert_eq!(format_score(f64::INFINITY), "inf");
+        assert_eq!(format_score(f64::NEG_INFINITY), "-inf");
+        assert_eq!(format_score(1e16), "10000000000000000");
+        assert_eq!(format_score(1e17), "1e+17");
+        assert_eq!(format_score(1e20), "1e+20");
+        assert_eq!(format_score(-2.5e100), "-2.5e+100");
+        assert_eq!(format_score(1e-4), "0.0001");
+        assert_eq!(format_score(1.5e-4), "0.00015");
+        assert_eq!(format_score(1e-5), "1e-05");
+        assert_eq!(format_score(-1.5e-5), "-1.5e-05");
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::storage::{IfKindResult, SetOpCard, SortedSet, Store, Value};
+
+/// Aggregate combines the weighted scores a member has in each input.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Aggregate {
+    #[default]
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    fn combine(&self, total: f64, score: f64) -> f64 {
+        match self {
+            Aggregate::Sum => zero_nan(total + score),
+            Aggregate::Min => total.min(score),
+            Aggregate::Max => total.max(score),
+        }
+    }
+}
+
+pub enum SortedSetOp {
+    New(SortedSet),
+    Empty,
+    WrongType,
+}
+
+/// Members reads a sorted set or a plain set, whose members all score 1.
+enum Members<'a> {
+    Set(&'a HashSet<Vec<u8>>),
+    SortedSet(&'a SortedSet),
+}
+
+impl<'a> Members<'a> {
+    fn len(&self) -> usize {
+        match self {
+            Members::Set(members) => members.len(),
+            Members::SortedSet(sorted_set) => sorted_set.len(),
+        }
+    }
+
+    fn score(&self, member: &[u8]) -> Option<f64> {
+        match self {
+            Members::Set(members) => members.contains(member).then_some(1.0),
+            Members::SortedSet(sorted_set) => sorted_set.score(member),
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&'a Vec<u8>, f64)> + 'a> {
+        match self {
+            Members::Set(members) => Box::new(members.iter().map(|member| (member, 1.0))),
+            Members::SortedSet(sorted_set) => Box::new(sorted_set.iter()),
+        }
+    }
+}
+
+pub fn zdiff(store: &mut Store, keys: &[Vec<u8>]) -> SortedSetOp {
+    let inputs = match get_members(store, keys) {
+        IfKindResult::Matched(inputs) => inputs,
+        _ => return SortedSetOp::WrongType,
+    };
+    let Some((Some(first), rest)) = inputs.split_first() else {
+        return SortedSetOp::Empty;
+    };
+
+    let diff = first
+        .iter()
+        .filter(|(member, _)| {
+            rest.iter()
+                .flatten()
+                .all(|other| other.score(member).is_none())
+        })
+        .map(|(member, score)| (member.clone(), score))
+        .collect();
+
+    new_or_empty(diff)
+}
+
+pub fn zinter(
+    store: &mut Store,
+    keys: &[Vec<u8>],
+    weights: &[f64],
+    aggregate: Aggregate,
+) -> SortedSetOp {
+    let mut intersection = HashMap::new();
+    match do_intersect(
+        store,
+        keys,
+        weights,
+        aggregate,
+        usize::MAX,
+        |member, score| {
+            intersection.insert(member.clone(), score);
+            intersection.len()
+        },
+    ) {
+        IfKindResult::Matched(()) => new_or_empty(intersection),
+        _ => SortedSetOp::WrongType,
+    }
+}
+
+pub fn zinter_card(store: &mut Store, keys: &[Vec<u8>], limit: usize) -> SetOpCard {
+    let mut count = 0;
+    let weights = vec![1.0; keys.len()];
+    match do_intersect(store, keys, &weights, Aggregate::Sum, limit, |_, _| {
+        count += 1;
+        count
+    }) {
+        IfKindResult::Matched(()) if count == 0 => SetOpCard::Empty,
+        IfKindResult::Matched(()) => SetOpCard::Count(count),
+        _ => SetOpCard::WrongType,
+    }
+}
+
+/// Inserts each member found in every input with its combined score,
+/// until `insert` returns `limit`.
+fn do_intersect<Insert>(
+    store: &mut Store,
+    keys: &[Vec<u8>],
+    weights: &[f64],
+    aggregate: Aggregate,
+    limit: usize,
+    mut insert: Insert,
+) -> IfKindResult<()>
+where
+    Insert: FnMut(&Vec<u8>, f64) -> usize,
+{
+    let inputs = match get_members(store, keys) {
+        IfKindResult::Matched(inputs) => inputs,
+        _ => return IfKindResult::NotMatched,
+    };
+    if inputs.iter().any(Option::is_none) {
+        return IfKindResult::Matched(());
+    }
+
+    // walk the smallest input, looking each member up in the others
+    let inputs = inputs
+        .into_iter()
+        .flatten()
+        .zip(weights.iter().copied())
+        .sorted_by(|(a, _), (b, _)| a.len().cmp(&b.len()))
+        .collect_vec();
+    let Some(((smallest, weight), rest)) = inputs.split_first() else {
+        return IfKindResult::Matched(());
+    };
+
+    'outer: for (member, score) in smallest.iter() {
+        let mut total = zero_nan(score * weight);
+        for (members, weight) in rest {
+            match members.score(member) {
+                Some(score) => total = aggregate.combine(total, zero_nan(score * weight)),
+                None => continue 'outer,
+            }
+        }
+        if insert(member, total) == limit {
+            break;
+        }
+    }
+
+    IfKindResult::Matched(())
+}
+
+pub fn zunion(
+    store: &mut Store,
+    keys: &[Vec<u8>],
+    weights: &[f64],
+    aggregate: Aggregate,
+) -> SortedSetOp {
+    let inputs = match get_members(store, keys) {
+        IfKindResult::Matched(inputs) => inputs,
+        _ => return SortedSetOp::WrongType,
+    };
+
+    let mut union: HashMap<Vec<u8>, f64> = HashMap::new();
+    for (members, weight) in inputs.iter().zip(weights) {
+        let Some(members) = members else {
+            continue;
+        };
+        for (member, score) in members.iter() {
+            let score = zero_nan(score * weight);
+            union
+                .entry(member.clone())
+                .and_modify(|total| *total = aggregate.combine(*total, score))
+                .or_insert(score);
+        }
+    }
+
+    new_or_empty(union)
+}
+
+/// Returns the members of each key in order, or `None` for a key that isn't set.
+fn get_members<'a>(
+    store: &'a mut Store,
+    keys: &[Vec<u8>],
+) -> IfKindResult<Vec<Option<Members<'a>>>> {
+    let mut inputs = Vec::with_capacity(keys.len());
+
+    for value in store.get_multi(keys) {
+        inputs.push(match value {
+            Some(Value::Set(members)) => Some(Members::Set(members)),
+            Some(Value::SortedSet(sorted_set)) => Some(Members::SortedSet(sorted_set)),
+            Some(_) => return IfKindResult::NotMatched,
+            None => None,
+        });
+    }
+
+    IfKindResult::Matched(inputs)
+}
+
+fn new_or_empty(scores: HashMap<Vec<u8>, f64>) -> SortedSetOp {
+    if scores.is_empty() {
+        SortedSetOp::Empty
+    } else {
+        SortedSetOp::New(scores.into_iter().collect())
+    }
+}
+
+// multiplying infinity by zero, or adding opposite infinities, scores zero
+fn zero_nan(score: f64) -> f64 {
+    if score.is_nan() {
+        0.0
+    } else {
+        score
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp::Reverse;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
 
@@ -7,9 +8,21 @@ use priority_queue::PriorityQueue;
 
 use super::{Kind, Pattern, Value};
 
+#[derive(Clone)]
 pub struct Store {
-    values: HashMap<String, Value>,
-    expirations: PriorityQueue<String, DateTime<Utc>>,
+    values: HashMap<Vec<u8>, Value>,
+    // reversed so the earliest expiration has the highest priority
+    expirations: PriorityQueue<Vec<u8>, Reverse<DateTime<Utc>>>,
+    // the earliest field expiration of each hash with one,
+    // which may be earlier than the hash's own after its fields change;
+    // both hold only keys that have a value
+    hash_expirations: PriorityQueue<Vec<u8>, Reverse<DateTime<Utc>>>,
+    // counts the calls that may have changed the data
+    dirty: u64,
+    // the number of connections watching each key and the times it changed since
+    watched: HashMap<Vec<u8>, (usize, u64)>,
+    // whether a watched key changed since `take_ready`
+    ready: bool,
 }
 
 impl Store {
@@ -17,73 +30,191 @@ impl Store {
         Self {
             values: HashMap::new(),
             expirations: PriorityQueue::new(),
+            hash_expirations: PriorityQueue::new(),
+            dirty: 0,
+            watched: HashMap::new(),
+            ready: false,
         }
     }
 
-    pub fn set(&mut self, key: &str, value: Value) -> Option<Value> {
-        self.values.insert(key.to_string(), value)
+    /// Returns the number of changes made since the store was created.
+    pub fn dirty(&self) -> u64 {
+        self.dirty
     }
 
-    pub fn set_if_kind(&mut self, kind: Kind, key: &str, value: Value) -> IfKindResult<Value> {
-        match self.values.entry(key.to_string()) {
+    /// Removes all keys, returning them in a new store
+    /// so the caller decides when to free them.
+    pub fn flush(&mut self) -> Store {
+        let mut old = Store::new();
+        std::mem::swap(&mut old.values, &mut self.values);
+        std::mem::swap(&mut old.expirations, &mut self.expirations);
+        std::mem::swap(&mut old.hash_expirations, &mut self.hash_expirations);
+        self.dirty += old.values.len() as u64;
+        self.touch_watched();
+        old
+    }
+
+    /// Exchanges the keys with another store,
+    /// keeping the keys watched in each since their values change.
+    pub fn swap(&mut self, other: &mut Store) {
+        std::mem::swap(&mut self.values, &mut other.values);
+        std::mem::swap(&mut self.expirations, &mut other.expirations);
+        std::mem::swap(&mut self.hash_expirations, &mut other.hash_expirations);
+        self.touch_watched();
+        other.touch_watched();
+    }
+
+    /// Starts tracking changes to the key for a connection
+    /// and returns its version to compare with `version` later.
+    pub fn watch(&mut self, key: &[u8]) -> u64 {
+        self.expire_if_due(key);
+        let watched = self.watched.entry(key.to_vec()).or_insert((0, 0));
+        watched.0 += 1;
+        watched.1
+    }
+
+    pub fn unwatch(&mut self, key: &[u8]) {
+        if let Some(watched) = self.watched.get_mut(key) {
+            watched.0 -= 1;
+            if watched.0 == 0 {
+                self.watched.remove(key);
+            }
+        }
+    }
+
+    /// Returns the number of times the watched key was changed, including by expiring.
+    pub fn version(&mut self, key: &[u8]) -> u64 {
+        self.expire_if_due(key);
+        self.watched.get(key).map_or(0, |(_, version)| *version)
+    }
+
+    /// Returns `true` if a watched key changed since the last call,
+    /// which may have made a blocked command ready to run.
+    pub fn take_ready(&mut self) -> bool {
+        std::mem::take(&mut self.ready)
+    }
+
+    /// Iterates over every key with its value and expiration, including expired keys.
+    pub fn entries(&self) -> impl Iterator<Item = (&Vec<u8>, &Value, Option<&DateTime<Utc>>)> {
+        self.values.iter().map(|(key, value)| {
+            let expires = self.expirations.get_priority(key).map(|Reverse(at)| at);
+            (key, value, expires)
+        })
+    }
+
+    pub fn set(&mut self, key: &[u8], value: Value) -> Option<Value> {
+        self.expire_if_due(key);
+        let old = self.values.insert(key.to_vec(), value);
+        self.schedule_fields(key);
+        self.touch(key);
+        old
+    }
+
+    pub fn set_if_kind(&mut self, kind: Kind, key: &[u8], value: Value) -> IfKindResult<Value> {
+        self.expire_if_due(key);
+        let result = match self.values.entry(key.to_vec()) {
             Occupied(mut entry) => {
                 if entry.get().kind() == kind {
                     IfKindResult::Matched(entry.insert(value))
                 } else {
-                    IfKindResult::NotMatched
+                    return IfKindResult::NotMatched;
                 }
             }
             Vacant(entry) => {
                 entry.insert(value);
                 IfKindResult::NotSet
             }
-        }
+        };
+        self.schedule_fields(key);
+        self.touch(key);
+        result
     }
 
-    pub fn rename(&mut self, key: &str, new_key: &str) -> bool {
+    pub fn rename(&mut self, key: &[u8], new_key: &[u8]) -> bool {
+        self.expire_if_due(key);
         if let Some(value) = self.values.remove(key) {
-            self.values.insert(new_key.to_string(), value);
-            self.expirations.remove(key);
+            self.values.insert(new_key.to_vec(), value);
+            self.forget(key);
             self.expirations.remove(new_key);
+            self.schedule_fields(new_key);
+            self.touch(key);
+            self.touch(new_key);
             true
         } else {
             false
         }
     }
 
-    pub fn remove(&mut self, key: &str) -> Option<Value> {
-        self.expirations.remove(key);
-        self.values.remove(key)
+    pub fn remove(&mut self, key: &[u8]) -> Option<Value> {
+        self.forget(key);
+        let value = self.values.remove(key)?;
+        self.touch(key);
+        Some(value)
     }
 
-    pub fn contains_key(&self, key: &str) -> bool {
+    pub fn contains_key(&mut self, key: &[u8]) -> bool {
+        self.expire_if_due(key);
         self.values.contains_key(key)
     }
 
-    pub fn keys(&self, pattern: &Pattern) -> Vec<String> {
+    /// Returns the number of keys that haven't expired,
+    /// leaving those that have for the expiration cycle to remove.
+    pub fn len(&self) -> usize {
+        let now = Utc::now();
+        let expired = self
+            .expirations
+            .iter()
+            .filter(|(_, Reverse(at))| now >= *at)
+            .count();
+        let emptied = self
+            .hash_expirations
+            .iter()
+            .filter(|(key, Reverse(at))| {
+                now >= *at && !self.key_expired(key, &now) && self.fields_expired(key, &now)
+            })
+            .count();
+        // both only count keys with values, but never underflow if that breaks
+        self.values.len().saturating_sub(expired + emptied)
+    }
+
+    /// Returns the number of keys with an expiration that haven't expired.
+    pub fn volatile_len(&self) -> usize {
+        let now = Utc::now();
+        self.expirations
+            .iter()
+            .filter(|(key, _)| !self.is_expired(key, &now))
+            .count()
+    }
+
+    pub fn keys(&self, pattern: &Pattern) -> Vec<Vec<u8>> {
+        let now = Utc::now();
         self.values
             .keys()
-            .filter(|key| pattern.matches(key))
+            .filter(|key| pattern.matches(key) && !self.is_expired(key, &now))
             .cloned()
             .collect_vec()
     }
 
-    pub fn kind(&self, key: &str) -> Option<Kind> {
+    pub fn kind(&mut self, key: &[u8]) -> Option<Kind> {
+        self.expire_if_due(key);
         self.values.get(key).map(|value| value.kind())
     }
 
-    pub fn is_not_kind(&self, key: &str, kind: Kind) -> bool {
+    pub fn is_not_kind(&mut self, key: &[u8], kind: Kind) -> bool {
+        self.expire_if_due(key);
         self.values
             .get(key)
             .map(|value| value.kind() != kind)
             .unwrap_or(false)
     }
 
-    pub fn get(&self, key: &str) -> Option<&Value> {
+    pub fn get(&mut self, key: &[u8]) -> Option<&Value> {
+        self.expire_if_due(key);
         self.values.get(key)
     }
 
-    pub fn get_if_kind(&self, kind: Kind, key: &str) -> IfKindResult<&Value> {
+    pub fn get_if_kind(&mut self, kind: Kind, key: &[u8]) -> IfKindResult<&Value> {
+        self.expire_if_due(key);
         if let Some(value) = self.values.get(key) {
             if value.kind() == kind {
                 IfKindResult::Matched(value)
@@ -95,32 +226,46 @@ impl Store {
         }
     }
 
-    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+    /// Returns the value to change, counting it as changed only if there is one.
+    pub fn get_mut(&mut self, key: &[u8]) -> Option<&mut Value> {
+        self.expire_if_due(key);
+        if !self.values.contains_key(key) {
+            return None;
+        }
+        self.touch(key);
         self.values.get_mut(key)
     }
 
-    pub fn get_mut_if_kind(&mut self, kind: Kind, key: &str) -> IfKindResult<&mut Value> {
-        if let Some(value) = self.values.get_mut(key) {
-            if value.kind() == kind {
-                IfKindResult::Matched(value)
-            } else {
-                IfKindResult::NotMatched
-            }
-        } else {
-            IfKindResult::NotSet
+    /// Returns the value to change if it's of the kind,
+    /// counting it as changed only if it is.
+    pub fn get_mut_if_kind(&mut self, kind: Kind, key: &[u8]) -> IfKindResult<&mut Value> {
+        self.expire_if_due(key);
+        match self.values.get(key) {
+            Some(value) if value.kind() == kind => (),
+            Some(_) => return IfKindResult::NotMatched,
+            None => return IfKindResult::NotSet,
         }
+        self.touch(key);
+        IfKindResult::Matched(self.values.get_mut(key).expect("checked"))
     }
 
-    pub fn get_and_remove(&mut self, key: &str) -> Option<Value> {
-        self.expirations.remove(key);
-        self.values.remove(key)
+    pub fn get_and_remove(&mut self, key: &[u8]) -> Option<Value> {
+        self.expire_if_due(key);
+        self.forget(key);
+        let value = self.values.remove(key)?;
+        self.touch(key);
+        Some(value)
     }
 
-    pub fn get_and_remove_if_kind(&mut self, kind: Kind, key: &str) -> IfKindResult<Value> {
-        match self.values.entry(key.to_string()) {
+    pub fn get_and_remove_if_kind(&mut self, kind: Kind, key: &[u8]) -> IfKindResult<Value> {
+        self.expire_if_due(key);
+        match self.values.entry(key.to_vec()) {
             Occupied(entry) => {
                 if entry.get().kind() == kind {
-                    IfKindResult::Matched(entry.remove())
+                    let value = entry.remove();
+                    self.forget(key);
+                    self.touch(key);
+                    IfKindResult::Matched(value)
                 } else {
                     IfKindResult::NotMatched
                 }
@@ -129,7 +274,15 @@ impl Store {
         }
     }
 
-    pub fn get_multi_if_kind(&self, kind: Kind, keys: &Vec<String>) -> IfKindResult<Vec<&Value>> {
+    pub fn get_multi_if_kind(
+        &mut self,
+        kind: Kind,
+        keys: &Vec<Vec<u8>>,
+    ) -> IfKindResult<Vec<&Value>> {
+        for key in keys {
+            self.expire_if_due(key);
+        }
+
         let mut values = Vec::with_capacity(keys.len());
 
         for key in keys {
@@ -145,41 +298,214 @@ impl Store {
         IfKindResult::Matched(values)
     }
 
-    pub fn copy(&mut self, source: &str, destination: &str) -> bool {
-        if let Some(value) = self.get(source) {
-            self.values.insert(destination.to_string(), value.clone());
-            true
-        } else {
-            false
+    /// Returns the value of each key in order, or `None` for a key that isn't set.
+    pub fn get_multi(&mut self, keys: &[Vec<u8>]) -> Vec<Option<&Value>> {
+        for key in keys {
+            self.expire_if_due(key);
+        }
+
+        keys.iter().map(|key| self.values.get(key)).collect()
+    }
+
+    /// Returns a copy of the key's value and expiration.
+    pub fn clone_entry(&mut self, key: &[u8]) -> Option<(Value, Option<DateTime<Utc>>)> {
+        let value = self.get(key).cloned()?;
+        Some((value, self.expires(key).copied()))
+    }
+
+    /// Removes the key and returns its value and expiration.
+    pub fn remove_entry(&mut self, key: &[u8]) -> Option<(Value, Option<DateTime<Utc>>)> {
+        self.expire_if_due(key);
+        let value = self.values.remove(key)?;
+        let expires = self.expirations.remove(key).map(|(_, Reverse(at))| at);
+        self.hash_expirations.remove(key);
+        self.touch(key);
+        Some((value, expires))
+    }
+
+    /// Sets the key's value and expiration, replacing any existing key.
+    pub fn set_entry(&mut self, key: &[u8], value: Value, expires: Option<DateTime<Utc>>) {
+        self.values.insert(key.to_vec(), value);
+        self.schedule_fields(key);
+        match expires {
+            Some(at) => self.expire_at(key, &at),
+            None => {
+                self.expirations.remove(key);
+                self.touch(key);
+            }
         }
     }
 
-    pub fn is_volatile(&self, key: &str) -> bool {
+    pub fn is_volatile(&mut self, key: &[u8]) -> bool {
+        self.expire_if_due(key);
         self.expirations.get(key).is_some()
     }
 
-    pub fn expires(&self, key: &str) -> Option<&DateTime<Utc>> {
-        self.expirations.get_priority(key)
+    pub fn expires(&mut self, key: &[u8]) -> Option<&DateTime<Utc>> {
+        self.expire_if_due(key);
+        self.expirations.get_priority(key).map(|Reverse(at)| at)
     }
 
-    pub fn persist(&mut self, key: &str) -> bool {
-        self.expirations.remove(key).is_some()
+    pub fn persist(&mut self, key: &[u8]) -> bool {
+        self.expire_if_due(key);
+        if self.expirations.remove(key).is_none() {
+            return false;
+        }
+        self.touch(key);
+        true
     }
 
-    pub fn expire_at(&mut self, key: &str, at: &DateTime<Utc>) {
-        self.expirations.push(key.to_string(), *at);
+    pub fn expire_at(&mut self, key: &[u8], at: &DateTime<Utc>) {
+        self.expirations.push(key.to_vec(), Reverse(*at));
+        self.touch(key);
     }
 
-    pub fn expire_items(&mut self) {
+    /// Has the active expiration cycle check the hash at the key for fields to expire,
+    /// or stop checking the key if it no longer holds a hash with any,
+    /// and must be called after setting an expiration on one of its fields.
+    pub fn schedule_fields(&mut self, key: &[u8]) {
+        let next = match self.values.get(key) {
+            Some(Value::Hash(hash)) => hash.next_expiration().copied(),
+            _ => None,
+        };
+        match next {
+            Some(at) => {
+                self.hash_expirations.push(key.to_vec(), Reverse(at));
+            }
+            None => {
+                self.hash_expirations.remove(key);
+            }
+        }
+    }
+
+    /// Returns when the next key or hash field expires.
+    pub fn next_expiration(&self) -> Option<&DateTime<Utc>> {
+        let key = self.expirations.peek().map(|(_, Reverse(at))| at);
+        let field = self.hash_expirations.peek().map(|(_, Reverse(at))| at);
+        key.into_iter().chain(field).min()
+    }
+
+    /// Removes up to `limit` keys that have expired and returns how many were removed.
+    pub fn expire_items(&mut self, limit: usize) -> usize {
         let now = Utc::now();
+        let mut expired = 0;
+
+        while expired < limit {
+            match self.expirations.peek() {
+                Some((_, Reverse(at))) if now >= *at => {
+                    let (key, _) = self.expirations.pop().expect("peeked");
+                    self.values.remove(&key);
+                    self.hash_expirations.remove(&key);
+                    self.touch(&key);
+                    expired += 1;
+                }
+                _ => break,
+            }
+        }
+
+        expired
+    }
+
+    /// Removes up to about `limit` hash fields that have expired, deleting any hash left empty,
+    /// and returns how many were removed.
+    pub fn expire_fields(&mut self, limit: usize) -> usize {
+        let now = Utc::now();
+        let mut expired = 0;
+
+        while expired < limit {
+            match self.hash_expirations.peek() {
+                Some((_, Reverse(at))) if now >= *at => {
+                    let (key, _) = self.hash_expirations.pop().expect("peeked");
+                    expired += self.expire_hash_fields(&key, &now);
+                }
+                _ => break,
+            }
+        }
 
-        while let Some((key, at)) = self.expirations.peek() {
-            if now >= *at {
+        expired
+    }
+
+    /// Returns `true` if the key's expiration has passed or all of its hash fields have expired,
+    /// whether or not it has been removed yet.
+    fn is_expired(&self, key: &[u8], now: &DateTime<Utc>) -> bool {
+        self.key_expired(key, now) || self.fields_expired(key, now)
+    }
+
+    fn key_expired(&self, key: &[u8], now: &DateTime<Utc>) -> bool {
+        self.expirations
+            .get_priority(key)
+            .is_some_and(|Reverse(at)| now >= at)
+    }
+
+    fn fields_expired(&self, key: &[u8], now: &DateTime<Utc>) -> bool {
+        matches!(self.values.get(key), Some(Value::Hash(hash)) if hash.is_expired(now))
+    }
+
+    /// Removes the key if its expiration has passed, or its hash fields that have,
+    /// and returns `true` if the key was removed.
+    fn expire_if_due(&mut self, key: &[u8]) -> bool {
+        let now = Utc::now();
+
+        match self.expirations.get_priority(key) {
+            Some(Reverse(at)) if now >= *at => {
                 self.values.remove(key);
-                self.expirations.pop();
-            } else {
-                break;
+                self.forget(key);
+                self.touch(key);
+                return true;
             }
+            _ => (),
+        }
+        match self.hash_expirations.get_priority(key) {
+            Some(Reverse(at)) if now >= *at => {
+                self.hash_expirations.remove(key);
+                self.expire_hash_fields(key, &now);
+                !self.values.contains_key(key)
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the expired fields of the hash at the key, which must not be scheduled,
+    /// and returns how many were removed.
+    fn expire_hash_fields(&mut self, key: &[u8], now: &DateTime<Utc>) -> usize {
+        let Some(Value::Hash(hash)) = self.values.get_mut(key) else {
+            return 0;
+        };
+
+        let expired = hash.expire_fields(now);
+        if hash.is_empty() {
+            self.values.remove(key);
+            self.forget(key);
+        } else {
+            self.schedule_fields(key);
+        }
+        if expired > 0 {
+            self.touch(key);
+        }
+        expired
+    }
+
+    /// Drops the key's expiration and any scheduled field expirations,
+    /// which must be done whenever its value is removed.
+    fn forget(&mut self, key: &[u8]) {
+        self.expirations.remove(key);
+        self.hash_expirations.remove(key);
+    }
+
+    /// Counts a change to the key for `dirty` and any connection watching it,
+    /// and must only be called once the key has changed.
+    fn touch(&mut self, key: &[u8]) {
+        self.dirty += 1;
+        if let Some((_, version)) = self.watched.get_mut(key) {
+            *version += 1;
+            self.ready = true;
+        }
+    }
+
+    fn touch_watched(&mut self) {
+        for (_, version) in self.watched.values_mut() {
+            *version += 1;
+            self.ready = true;
         }
     }
 }
@@ -205,3 +531,85 @@ impl<T> IfKindResult<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Hash;
+
+    #[test]
+    fn lookups_that_change_nothing_leave_watched_keys_alone() {
+        let mut store = Store::new();
+        store.set(b"string", Value::String(b"value".to_vec()));
+        let dirty = store.dirty();
+        let missing = store.watch(b"missing");
+        let string = store.watch(b"string");
+
+        assert!(matches!(
+            store.get_mut_if_kind(Kind::List, b"missing"),
+            IfKindResult::NotSet
+        ));
+        assert!(matches!(
+            store.get_mut_if_kind(Kind::List, b"string"),
+            IfKindResult::NotMatched
+        ));
+        assert!(matches!(
+            store.get_and_remove_if_kind(Kind::List, b"string"),
+            IfKindResult::NotMatched
+        ));
+        assert!(store.get_mut(b"missing").is_none());
+        assert!(store.remove(b"missing").is_none());
+        assert!(!store.persist(b"string"));
+        assert!(!store.rename(b"missing", b"string"));
+
+        assert_eq!(store.version(b"missing"), missing);
+        assert_eq!(store.version(b"string"), string);
+        assert_eq!(store.dirty(), dirty);
+    }
+
+    #[test]
+    fn changes_bump_watched_keys() {
+        let mut store = Store::new();
+        store.set(b"key", Value::List(vec![b"a".to_vec()]));
+        let version = store.watch(b"key");
+
+        assert!(matches!(
+            store.get_mut_if_kind(Kind::List, b"key"),
+            IfKindResult::Matched(_)
+        ));
+        assert_eq!(store.version(b"key"), version + 1);
+
+        store.expire_at(b"key", &(Utc::now() + chrono::TimeDelta::hours(1)));
+        assert!(store.persist(b"key"));
+        assert!(store.remove(b"key").is_some());
+        assert_eq!(store.version(b"key"), version + 4);
+    }
+
+    #[test]
+    fn removing_a_volatile_key_drops_its_expiration() {
+        let mut store = Store::new();
+        let soon = Utc::now() + chrono::TimeDelta::milliseconds(50);
+        let mut hash = Hash::new();
+        hash.insert(b"field".to_vec(), b"value".to_vec());
+        hash.expire_at(b"field", &soon);
+        store.set(b"hash", Value::Hash(hash));
+        store.set(b"string", Value::String(b"value".to_vec()));
+        store.expire_at(b"string", &soon);
+
+        // as GETDEL and HGETDEL do
+        assert!(matches!(
+            store.get_and_remove_if_kind(Kind::String, b"string"),
+            IfKindResult::Matched(_)
+        ));
+        assert!(store.remove(b"hash").is_some());
+        store.set(b"string", Value::List(vec![b"a".to_vec()]));
+        assert!(store.expires(b"string").is_none());
+        assert_eq!(store.next_expiration(), None);
+
+        std::thread::sleep(std::time::Duration::from_millis(60));
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.volatile_len(), 0);
+        assert_eq!(store.expire_items(10) + store.expire_fields(10), 0);
+        assert!(store.get(b"string").is_some());
+    }
+}
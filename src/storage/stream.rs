@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::ConsumerGroup;
+
+// trimming with `~` only removes whole nodes of this many entries
+const NODE_ENTRIES: usize = 100;
+
+/// StreamId orders entries by the millisecond they were added
+/// and then by a sequence number within the millisecond.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StreamId {
+    pub ms: u64,
+    pub seq: u64,
+}
+
+impl StreamId {
+    pub const MIN: StreamId = StreamId { ms: 0, seq: 0 };
+    pub const MAX: StreamId = StreamId {
+        ms: u64::MAX,
+        seq: u64::MAX,
+    };
+
+    pub fn new(ms: u64, seq: u64) -> Self {
+        Self { ms, seq }
+    }
+
+    /// Parses `ms-seq`, or just `ms` with the sequence number `seq`.
+    pub fn parse(id: &[u8], seq: u64) -> Option<StreamId> {
+        let id = std::str::from_utf8(id).ok()?;
+        match id.split_once('-') {
+            Some((ms, explicit)) => Some(StreamId::new(ms.parse().ok()?, explicit.parse().ok()?)),
+            None => Some(StreamId::new(id.parse().ok()?, seq)),
+        }
+    }
+
+    /// Returns the smallest ID greater than this one, if there is one.
+    pub fn next(&self) -> Option<StreamId> {
+        if self.seq < u64::MAX {
+            Some(StreamId::new(self.ms, self.seq + 1))
+        } else if self.ms < u64::MAX {
+            Some(StreamId::new(self.ms + 1, 0))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the largest ID smaller than this one, if there is one.
+    pub fn prev(&self) -> Option<StreamId> {
+        if self.seq > 0 {
+            Some(StreamId::new(self.ms, self.seq - 1))
+        } else if self.ms > 0 {
+            Some(StreamId::new(self.ms - 1, u64::MAX))
+        } else {
+            None
+        }
+    }
+
+    pub fn to_bytes(self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+pub type Fields = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Trim is how many entries trimming keeps at most,
+/// or the smallest ID it keeps.
+#[derive(Clone, Copy, Debug)]
+pub enum Trim {
+    MaxLen(usize),
+    MinId(StreamId),
+}
+
+/// Stream is an append-only log of entries with increasing IDs,
+/// and the consumer groups reading it.
+///
+/// It remembers the last ID it added even after that entry is deleted
+/// so new entries always have greater IDs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stream {
+    entries: BTreeMap<StreamId, Fields>,
+    last_id: StreamId,
+    max_deleted_id: StreamId,
+    entries_added: u64,
+    groups: BTreeMap<Vec<u8>, ConsumerGroup>,
+}
+
+impl Stream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn last_id(&self) -> StreamId {
+        self.last_id
+    }
+
+    pub fn max_deleted_id(&self) -> StreamId {
+        self.max_deleted_id
+    }
+
+    pub fn entries_added(&self) -> u64 {
+        self.entries_added
+    }
+
+    pub fn get(&self, id: &StreamId) -> Option<&Fields> {
+        self.entries.get(id)
+    }
+
+    pub fn first_entry(&self) -> Option<(&StreamId, &Fields)> {
+        self.entries.first_key_value()
+    }
+
+    pub fn last_entry(&self) -> Option<(&StreamId, &Fields)> {
+        self.entries.last_key_value()
+    }
+
+    /// Returns the ID to add an entry with at the millisecond `ms`,
+    /// or after the last ID if the clock went backwards.
+    pub fn next_id(&self, ms: u64) -> Option<StreamId> {
+        if ms > self.last_id.ms {
+            Some(StreamId::new(ms, 0))
+        } else {
+            self.last_id.next()
+        }
+    }
+
+    /// Returns the ID to add an entry with at the millisecond `ms`
+    /// using the next sequence number, if it's greater than the last ID.
+    pub fn next_seq(&self, ms: u64) -> Option<StreamId> {
+        if ms > self.last_id.ms {
+            // 0-0 is never a valid ID
+            Some(StreamId::new(ms, if ms == 0 { 1 } else { 0 }))
+        } else if ms == self.last_id.ms {
+            self.last_id
+                .seq
+                .checked_add(1)
+                .map(|seq| StreamId::new(ms, seq))
+        } else {
+            None
+        }
+    }
+
+    /// Appends the entry, whose ID must be greater than the last ID.
+    pub fn add(&mut self, id: StreamId, fields: Fields) {
+        self.entries.insert(id, fields);
+        self.last_id = id;
+        self.entries_added += 1;
+    }
+
+    pub fn remove(&mut self, id: &StreamId) -> bool {
+        if self.entries.remove(id).is_none() {
+            return false;
+        }
+        self.max_deleted_id = self.max_deleted_id.max(*id);
+        true
+    }
+
+    /// Iterates over the entries with IDs from `start` to `end` inclusive.
+    pub fn range(
+        &self,
+        start: StreamId,
+        end: StreamId,
+    ) -> impl DoubleEndedIterator<Item = (&StreamId, &Fields)> {
+        // an empty range still has to start before it ends
+        self.entries
+            .range(start..=end.max(start))
+            .filter(move |(id, _)| **id <= end)
+    }
+
+    /// Removes the oldest entries beyond the threshold and returns how many were removed.
+    ///
+    /// Approximate trimming removes only whole nodes, and at most `limit` entries,
+    /// so it may leave more entries than the threshold allows.
+    pub fn trim(&mut self, trim: Trim, approximate: bool, limit: usize) -> usize {
+        let excess = match trim {
+            Trim::MaxLen(max_len) => self.len().saturating_sub(max_len),
+            Trim::MinId(min_id) => self.entries.range(..min_id).count(),
+        };
+        let count = if approximate {
+            excess.min(limit) / NODE_ENTRIES * NODE_ENTRIES
+        } else {
+            excess
+        };
+
+        for _ in 0..count {
+            self.entries.pop_first();
+        }
+        count
+    }
+
+    /// Sets the IDs and count that new entries follow on from.
+    pub fn set_last_id(
+        &mut self,
+        last_id: StreamId,
+        entries_added: Option<u64>,
+        max_deleted_id: Option<StreamId>,
+    ) {
+        self.last_id = last_id;
+        if let Some(entries_added) = entries_added {
+            self.entries_added = entries_added;
+        }
+        if let Some(max_deleted_id) = max_deleted_id {
+            self.max_deleted_id = max_deleted_id;
+        }
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = (&Vec<u8>, &ConsumerGroup)> {
+        self.groups.iter()
+    }
+
+    pub fn group(&self, name: &[u8]) -> Option<&ConsumerGroup> {
+        self.groups.get(name)
+    }
+
+    pub fn group_mut(&mut self, name: &[u8]) -> Option<&mut ConsumerGroup> {
+        self.groups.get_mut(name)
+    }
+
+    /// Adds the group and returns `true` if there wasn't one with the name.
+    pub fn create_group(&mut self, name: &[u8], group: ConsumerGroup) -> bool {
+        if self.groups.contains_key(name) {
+            return false;
+        }
+        self.groups.insert(name.to_vec(), group);
+        true
+    }
+
+    pub fn destroy_group(&mut self, name: &[u8]) -> bool {
+        self.groups.remove(name).is_some()
+    }
+
+    /// Returns the number of entries ever added up to and including `id`,
+    /// if deletions haven't made it impossible to tell.
+    pub fn entries_read_at(&self, id: StreamId) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+        if id >= self.last_id {
+            return (id == self.last_id || self.is_empty()).then_some(self.entries_added);
+        }
+        if self.is_empty() {
+            return Some(self.entries_added);
+        }
+
+        // without deletions after the first entry, entries before it were all trimmed
+        let (first_id, _) = self.first_entry().expect("not empty");
+        if self.max_deleted_id == StreamId::MIN || self.max_deleted_id < *first_id {
+            if id < *first_id {
+                return Some(self.entries_added - self.len() as u64);
+            } else if id == *first_id {
+                return Some(self.entries_added - self.len() as u64 + 1);
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if an entry from `id` on was deleted.
+    pub fn deleted_since(&self, id: StreamId) -> bool {
+        !self.is_empty() && self.max_deleted_id != StreamId::MIN && id <= self.max_deleted_id
+    }
+
+    /// Returns the number of entries the group has yet to read, if it can be known.
+    pub fn lag(&self, group: &ConsumerGroup) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+        let entries_read = match group.entries_read {
+            Some(entries_read) if !self.deleted_since(group.last_id) => Some(entries_read),
+            _ => self.entries_read_at(group.last_id),
+        };
+        entries_read.map(|entries_read| self.entries_added.saturating_sub(entries_read))
+    }
+
+    /// Moves the group past the entry it's delivering, keeping count of the entries read.
+    pub fn advance_group(&mut self, name: &[u8], id: StreamId) {
+        let deleted = self.deleted_since(id);
+        let estimate = self.entries_read_at(id);
+
+        if let Some(group) = self.groups.get_mut(name) {
+            group.entries_read = match group.entries_read {
+                Some(entries_read) if !deleted => Some(entries_read + 1),
+                _ => estimate,
+            };
+            group.last_id = id;
+        }
+    }
+}
@@ -1,19 +1,26 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use super::{Hash, SortedSet, Stream};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Kind {
+    Hash,
     List,
-    Integer,
     Set,
+    SortedSet,
+    Stream,
     String,
 }
 
 impl Kind {
     pub fn as_str(&self) -> &'static str {
         match self {
+            Kind::Hash => "hash",
             Kind::List => "list",
-            Kind::Integer => "integer",
             Kind::Set => "set",
+            Kind::SortedSet => "zset",
+            Kind::Stream => "stream",
             Kind::String => "string",
         }
     }
@@ -21,26 +28,46 @@ impl Kind {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Value {
+    Hash(Hash),
+    /// a string holding an integer, kept as the number
     Integer(i64),
-    List(Vec<String>),
-    Set(HashSet<String>),
-    String(String),
+    List(Vec<Vec<u8>>),
+    Set(HashSet<Vec<u8>>),
+    SortedSet(SortedSet),
+    Stream(Stream),
+    String(Vec<u8>),
 }
 
 impl Value {
-    pub fn set_from_vec(members: &[String]) -> Self {
+    /// Returns a string value, holding it as an integer
+    /// if it's written exactly the way the integer would be.
+    pub fn string(s: Vec<u8>) -> Self {
+        match parse_integer(&s) {
+            Some(i) => Value::Integer(i),
+            None => Value::String(s),
+        }
+    }
+
+    pub fn set_from_vec(members: &[Vec<u8>]) -> Self {
         Value::Set(members.iter().cloned().collect())
     }
 
     pub fn kind(&self) -> Kind {
         match self {
+            Value::Hash(_) => Kind::Hash,
             Value::List(_) => Kind::List,
-            Value::Integer(_) => Kind::Integer,
+            Value::Integer(_) => Kind::String,
             Value::Set(_) => Kind::Set,
+            Value::SortedSet(_) => Kind::SortedSet,
+            Value::Stream(_) => Kind::Stream,
             Value::String(_) => Kind::String,
         }
     }
 
+    pub fn is_hash(&self) -> bool {
+        matches!(self, Value::Hash(_))
+    }
+
     pub fn is_integer(&self) -> bool {
         matches!(self, Value::Integer(_))
     }
@@ -53,39 +80,82 @@ impl Value {
         matches!(self, Value::Set(_))
     }
 
+    pub fn is_sorted_set(&self) -> bool {
+        matches!(self, Value::SortedSet(_))
+    }
+
+    pub fn is_stream(&self) -> bool {
+        matches!(self, Value::Stream(_))
+    }
+
     pub fn is_string(&self) -> bool {
-        matches!(self, Value::String(_))
+        matches!(self, Value::String(_) | Value::Integer(_))
+    }
+
+    /// Returns the integer a string holds, if it's written exactly the way the integer would be.
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(i) => Some(*i),
+            Value::String(s) => parse_integer(s),
+            _ => None,
+        }
+    }
+
+    pub fn expect_string(&self) -> Cow<'_, [u8]> {
+        match self {
+            Value::Integer(i) => Cow::Owned(i.to_string().into_bytes()),
+            Value::String(s) => Cow::Borrowed(s),
+            _ => panic!("expected string"),
+        }
+    }
+
+    /// Returns a string's bytes to change, writing out an integer first.
+    pub fn expect_string_mut(&mut self) -> &mut Vec<u8> {
+        if let Value::Integer(i) = self {
+            *self = Value::String(i.to_string().into_bytes());
+        }
+        match self {
+            Value::String(s) => s,
+            _ => panic!("expected string"),
+        }
     }
 
-    pub fn expect_set(&self) -> &HashSet<String> {
+    pub fn expect_set(&self) -> &HashSet<Vec<u8>> {
         match self {
             Value::Set(members) => members,
             _ => panic!("expected set"),
         }
     }
 
-    pub fn expect_set_mut(&mut self) -> &mut HashSet<String> {
+    pub fn expect_set_mut(&mut self) -> &mut HashSet<Vec<u8>> {
         match self {
             Value::Set(members) => members,
             _ => panic!("expected set"),
         }
     }
 
-    pub fn into_list(self) -> Vec<String> {
+    pub fn expect_hash(&self) -> &Hash {
+        match self {
+            Value::Hash(fields) => fields,
+            _ => panic!("expected hash"),
+        }
+    }
+
+    pub fn into_list(self) -> Vec<Vec<u8>> {
         match self {
             Value::List(list) => list,
             _ => panic!("expected list"),
         }
     }
 
-    pub fn expect_list(&self) -> &Vec<String> {
+    pub fn expect_list(&self) -> &Vec<Vec<u8>> {
         match self {
             Value::List(list) => list,
             _ => panic!("expected list"),
         }
     }
 
-    pub fn expect_list_mut(&mut self) -> &mut Vec<String> {
+    pub fn expect_list_mut(&mut self) -> &mut Vec<Vec<u8>> {
         match self {
             Value::List(list) => list,
             _ => panic!("expected list"),
@@ -93,32 +163,67 @@ impl Value {
     }
 }
 
-impl From<Vec<String>> for Value {
-    fn from(elements: Vec<String>) -> Self {
+impl From<Vec<Vec<u8>>> for Value {
+    fn from(elements: Vec<Vec<u8>>) -> Self {
         Value::List(elements)
     }
 }
 
-impl From<usize> for Value {
-    fn from(n: usize) -> Self {
-        Value::Integer(n as i64)
+impl From<HashMap<Vec<u8>, Vec<u8>>> for Value {
+    fn from(fields: HashMap<Vec<u8>, Vec<u8>>) -> Self {
+        Value::Hash(Hash::from(fields))
     }
 }
 
-impl From<HashSet<String>> for Value {
-    fn from(members: HashSet<String>) -> Self {
+impl From<Hash> for Value {
+    fn from(hash: Hash) -> Self {
+        Value::Hash(hash)
+    }
+}
+
+impl From<SortedSet> for Value {
+    fn from(sorted_set: SortedSet) -> Self {
+        Value::SortedSet(sorted_set)
+    }
+}
+
+impl From<Stream> for Value {
+    fn from(stream: Stream) -> Self {
+        Value::Stream(stream)
+    }
+}
+
+impl From<HashSet<Vec<u8>>> for Value {
+    fn from(members: HashSet<Vec<u8>>) -> Self {
         Value::Set(members)
     }
 }
 
-impl From<String> for Value {
-    fn from(s: String) -> Self {
+impl From<Vec<u8>> for Value {
+    fn from(s: Vec<u8>) -> Self {
         Value::String(s)
     }
 }
 
+impl From<&[u8]> for Value {
+    fn from(s: &[u8]) -> Self {
+        Value::String(s.to_vec())
+    }
+}
+
 impl From<&str> for Value {
     fn from(s: &str) -> Self {
-        Value::String(s.to_string())
+        Value::String(s.as_bytes().to_vec())
+    }
+}
+
+/// Parses an integer with no sign but a leading `-`, no leading zeros and no overflow,
+/// so it's written back the same way.
+fn parse_integer(s: &[u8]) -> Option<i64> {
+    // longer than the most negative integer
+    if s.len() > 20 {
+        return None;
     }
+    let i = std::str::from_utf8(s).ok()?.parse::<i64>().ok()?;
+    (i.to_string().as_bytes() == s).then_some(i)
 }
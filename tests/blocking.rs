@@ -0,0 +1,39 @@
+use std::io::{Read, Write};
+use std::thread;
+use std::time::Duration;
+
+use common::Server;
+
+mod common;
+
+#[test]
+fn blocked_client_gets_the_push_before_a_pipelined_pop() {
+    let server = Server::start("blocking", &[]);
+    let mut blocked = server.connect();
+    let mut other = server.connect();
+
+    blocked
+        .stream
+        .write_all(b"*3\r\n$5\r\nBLPOP\r\n$4\r\nlist\r\n$1\r\n0\r\n")
+        .unwrap();
+    // give the server time to block the client before the push arrives
+    thread::sleep(Duration::from_millis(200));
+
+    other
+        .stream
+        .write_all(
+            b"*3\r\n$5\r\nRPUSH\r\n$4\r\nlist\r\n$1\r\na\r\n*2\r\n$4\r\nLPOP\r\n$4\r\nlist\r\n",
+        )
+        .unwrap();
+    let mut replies = String::new();
+    let mut buffer = [0; 64];
+    while replies.len() < ":1\r\n$-1\r\n".len() {
+        let len = other.stream.read(&mut buffer).unwrap();
+        replies.push_str(&String::from_utf8_lossy(&buffer[..len]));
+    }
+
+    assert_eq!(replies, ":1\r\n$-1\r\n");
+    let mut buffer = [0; 64];
+    let len = blocked.stream.read(&mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"*2\r\n$4\r\nlist\r\n$1\r\na\r\n");
+}
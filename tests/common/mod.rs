@@ -0,0 +1,100 @@
+//! Helpers for tests that run the server as a separate process.
+
+// each test binary uses only some of them
+#![allow(dead_code)]
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A server process that is killed when the test ends.
+pub struct Server {
+    child: Child,
+    pub port: u16,
+    dir: PathBuf,
+}
+
+impl Server {
+    pub fn start(name: &str, args: &[&str]) -> Self {
+        let port = free_port();
+        let dir = std::env::temp_dir().join(format!("{}-{}-{}", name, std::process::id(), port));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let child = Command::new(env!("CARGO_BIN_EXE_redis-starter-rust"))
+            .args(["--port", &port.to_string(), "--dir", dir.to_str().unwrap()])
+            .args(["--save", ""])
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let server = Self { child, port, dir };
+        wait_for(|| TcpStream::connect(("127.0.0.1", server.port)).is_ok());
+        server
+    }
+
+    pub fn connect(&self) -> Connection {
+        Connection::new(TcpStream::connect(("127.0.0.1", self.port)).unwrap())
+    }
+
+    pub fn is_running(&mut self) -> bool {
+        self.child.try_wait().unwrap().is_none()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+pub struct Connection {
+    pub stream: TcpStream,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Self {
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        Self { stream }
+    }
+
+    /// Sends a command and returns the reply, which must fit in a single read.
+    pub fn command(&mut self, args: &[&str]) -> String {
+        let mut data = format!("*{}\r\n", args.len());
+        for arg in args {
+            data.push_str(&format!("${}\r\n{}\r\n", arg.len(), arg));
+        }
+        self.stream.write_all(data.as_bytes()).unwrap();
+
+        let mut buffer = [0; 4096];
+        let len = self.stream.read(&mut buffer).unwrap();
+        String::from_utf8_lossy(&buffer[..len]).into_owned()
+    }
+}
+
+pub fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+pub fn wait_for(mut condition: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "timed out waiting"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
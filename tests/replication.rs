@@ -0,0 +1,177 @@
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use common::{wait_for, Server};
+
+mod common;
+
+/// Forwards connections to the leader and records what it sends back,
+/// so a test can cut the link and see how the replica resumes.
+struct Proxy {
+    port: u16,
+    received: Arc<Mutex<Vec<u8>>>,
+    links: Arc<Mutex<Vec<Link>>>,
+}
+
+/// Both halves of a forwarded connection.
+struct Link {
+    replica: TcpStream,
+    leader: TcpStream,
+    cut: Arc<AtomicBool>,
+}
+
+impl Proxy {
+    fn start(leader_port: u16) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let links = Arc::new(Mutex::new(Vec::new()));
+
+        let proxy = Self {
+            port,
+            received: received.clone(),
+            links: links.clone(),
+        };
+        thread::spawn(move || {
+            for replica in listener.incoming() {
+                let replica = replica.unwrap();
+                let leader = TcpStream::connect(("127.0.0.1", leader_port)).unwrap();
+                let cut = Arc::new(AtomicBool::new(false));
+
+                forward(
+                    replica.try_clone().unwrap(),
+                    leader.try_clone().unwrap(),
+                    cut.clone(),
+                    None,
+                );
+                forward(
+                    leader.try_clone().unwrap(),
+                    replica.try_clone().unwrap(),
+                    cut.clone(),
+                    Some(received.clone()),
+                );
+                links.lock().unwrap().push(Link {
+                    replica,
+                    leader,
+                    cut,
+                });
+            }
+        });
+        proxy
+    }
+
+    fn links(&self) -> usize {
+        self.links.lock().unwrap().len()
+    }
+
+    /// Stops forwarding so data piles up unread on both sides of the link.
+    fn stop(&self) {
+        for link in self.links.lock().unwrap().iter() {
+            link.cut.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Closes the sockets of the stopped links, which resets them
+    /// since they hold unread data.
+    fn reset(&self) {
+        for link in self.links.lock().unwrap().drain(..) {
+            drop(link.replica);
+            drop(link.leader);
+        }
+    }
+
+    fn received(&self) -> String {
+        String::from_utf8_lossy(&self.received.lock().unwrap()).into_owned()
+    }
+}
+
+fn forward(
+    mut from: TcpStream,
+    mut to: TcpStream,
+    cut: Arc<AtomicBool>,
+    record: Option<Arc<Mutex<Vec<u8>>>>,
+) {
+    from.set_read_timeout(Some(Duration::from_millis(20)))
+        .unwrap();
+    thread::spawn(move || {
+        let mut buffer = [0; 4096];
+        while !cut.load(Ordering::SeqCst) {
+            match from.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(len) => {
+                    if let Some(record) = &record {
+                        record.lock().unwrap().extend_from_slice(&buffer[..len]);
+                    }
+                    if to.write_all(&buffer[..len]).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => continue,
+            }
+        }
+        if !cut.load(Ordering::SeqCst) {
+            let _ = to.shutdown(Shutdown::Both);
+        }
+    });
+}
+
+#[test]
+fn reset_link_resumes_with_partial_resync() {
+    let mut leader = Server::start("leader", &[]);
+    let proxy = Proxy::start(leader.port);
+    let mut replica = Server::start(
+        "replica",
+        &["--replicaof", "127.0.0.1", &proxy.port.to_string()],
+    );
+    let mut writer = leader.connect();
+    let mut reader = replica.connect();
+
+    assert_eq!(writer.command(&["SET", "before", "1"]), "+OK\r\n");
+    wait_for(|| reader.command(&["GET", "before"]) == "$1\r\n1\r\n");
+    assert!(proxy.received().contains("+FULLRESYNC"));
+
+    // leave a write from the leader and an ACK from the replica unread, so closing resets both ends
+    proxy.stop();
+    assert_eq!(writer.command(&["SET", "during", "2"]), "+OK\r\n");
+    thread::sleep(Duration::from_millis(1_500));
+    proxy.reset();
+
+    wait_for(|| proxy.links() == 1 && proxy.received().contains("+CONTINUE"));
+    wait_for(|| reader.command(&["GET", "during"]) == "$1\r\n2\r\n");
+    assert_eq!(writer.command(&["SET", "after", "3"]), "+OK\r\n");
+    wait_for(|| reader.command(&["GET", "after"]) == "$1\r\n3\r\n");
+
+    assert!(leader.is_running());
+    assert!(replica.is_running());
+}
+
+#[test]
+fn replica_outlives_its_leader() {
+    let leader = Server::start("leader", &[]);
+    let proxy = Proxy::start(leader.port);
+    let mut replica = Server::start(
+        "replica",
+        &["--replicaof", "127.0.0.1", &proxy.port.to_string()],
+    );
+    let mut reader = replica.connect();
+
+    assert_eq!(leader.connect().command(&["SET", "key", "1"]), "+OK\r\n");
+    wait_for(|| reader.command(&["GET", "key"]) == "$1\r\n1\r\n");
+
+    proxy.stop();
+    thread::sleep(Duration::from_millis(1_500));
+    proxy.reset();
+    drop(leader);
+
+    wait_for(|| {
+        reader
+            .command(&["INFO", "replication"])
+            .contains("master_link_status:down")
+    });
+    assert_eq!(reader.command(&["GET", "key"]), "$1\r\n1\r\n");
+    assert!(replica.is_running());
+}